@@ -0,0 +1,405 @@
+//! Derive macros for the `packed_booleans` crate.
+//!
+//! These are re-exported by `packed_booleans` when its `derive` feature is enabled,
+//! and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, parenthesized, parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitInt, Token, Type, Visibility};
+
+/// Derives conversions between a struct of `bool` fields and a packed type.
+///
+/// The struct must have named fields, all of type `bool`.
+/// Structs with up to 8 fields are packed into a `PackedBools8`,
+/// and structs with up to 16 fields are packed into a `PackedBools16`.
+/// Each field is stored at the index of its position in the struct.
+///
+/// This generates:
+/// - an associated `<FIELD>_INDEX: u8` constant for every field
+/// - `into_packed(&self)` and `from_packed(packed)` methods
+/// - `From` impls converting in both directions
+#[proc_macro_derive(PackBools)]
+pub fn derive_pack_bools(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_pack_bools(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_pack_bools(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(
+                &input.ident,
+                "PackBools can only be derived for structs with named fields",
+            )),
+        },
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            "PackBools can only be derived for structs",
+        )),
+    };
+
+    for field in fields {
+        if !is_bool(&field.ty) {
+            return Err(Error::new_spanned(&field.ty, "PackBools fields must be of type `bool`"));
+        }
+    }
+
+    let packed = match fields.len() {
+        0..=8 => quote!(::packed_booleans::PackedBools8),
+        9..=16 => quote!(::packed_booleans::PackedBools16),
+        _ => return Err(Error::new(
+            Span::call_site(),
+            "PackBools supports at most 16 fields",
+        )),
+    };
+
+    let idents: Vec<_> = fields.iter()
+        .map(|field| field.ident.as_ref().expect("named fields have identifiers"))
+        .collect();
+    let index_consts: Vec<_> = idents.iter()
+        .map(|ident| format_ident!("{}_INDEX", ident.unraw().to_string().to_uppercase()))
+        .collect();
+    let indices = (0..idents.len()).map(|idx| idx as u8);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(
+                #[doc = concat!("The index of the `", stringify!(#idents), "` field in the packed form.")]
+                pub const #index_consts: u8 = #indices;
+            )*
+
+            /// Packs the fields of this struct together.
+            pub fn into_packed(&self) -> #packed {
+                let mut packed = <#packed>::new();
+                #( packed.set(self.#idents, Self::#index_consts); )*
+                packed
+            }
+
+            /// Creates this struct from its packed form.
+            pub fn from_packed(packed: #packed) -> Self {
+                Self {
+                    #( #idents: packed.get(Self::#index_consts), )*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #packed #where_clause {
+            fn from(val: #name #ty_generics) -> #packed {
+                val.into_packed()
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#packed> for #name #ty_generics #where_clause {
+            fn from(packed: #packed) -> Self {
+                Self::from_packed(packed)
+            }
+        }
+    })
+}
+
+/// Derives `BitIndexable` for a fieldless enum, so it can key a `PackedFlags`.
+///
+/// Each variant is given the index of its position in the enum,
+/// ignoring any explicit discriminants.
+/// The flags are stored in the smallest packed type with room for every variant,
+/// so an enum can have up to 128 variants.
+#[proc_macro_derive(BitIndex)]
+pub fn derive_bit_index(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_bit_index(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_bit_index(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            "BitIndex can only be derived for enums",
+        )),
+    };
+
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(variant, "BitIndex variants cannot have fields"));
+        }
+    }
+
+    let storage = match variants.len() {
+        0 => return Err(Error::new_spanned(
+            &input.ident,
+            "BitIndex cannot be derived for an enum with no variants",
+        )),
+        1..=8 => quote!(::packed_booleans::PackedBools8),
+        9..=16 => quote!(::packed_booleans::PackedBools16),
+        17..=32 => quote!(::packed_booleans::PackedBools32),
+        33..=64 => quote!(::packed_booleans::PackedBools64),
+        65..=128 => quote!(::packed_booleans::PackedBools128),
+        _ => return Err(Error::new(
+            Span::call_site(),
+            "BitIndex supports at most 128 variants",
+        )),
+    };
+
+    let idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let indices: Vec<_> = (0..idents.len()).map(|idx| idx as u8).collect();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::packed_booleans::BitIndexable for #name #ty_generics #where_clause {
+            type Storage = #storage;
+
+            fn bit_index(self) -> u8 {
+                match self {
+                    #( Self::#idents => #indices, )*
+                }
+            }
+
+            fn from_bit_index(idx: u8) -> ::core::option::Option<Self> {
+                match idx {
+                    #( #indices => ::core::option::Option::Some(Self::#idents), )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Defines a struct of named fields over a packed type, with getters and setters for each field.
+///
+/// A field declared with a single index is a `bool`,
+/// and a field declared with a range of indices, like `1..=3` or `1..4`,
+/// is an unsigned integer, using the smallest integer type that fits it.
+/// The first index of a field holds its lowest bit.
+/// The fields must fit in the packed type and can't overlap, which is checked at compile time.
+///
+/// For a field `mode`, this generates:
+/// - `mode(self)`, which returns the field
+/// - `set_mode(&mut self, val)`, which sets the field, ignoring the bits of `val` that don't fit
+/// - `with_mode(self, val)`, which returns a copy with the field set
+///
+/// The struct also gets `new`, `from_packed` and `to_packed` methods,
+/// and a `Debug` impl that prints every field.
+/// All the methods are `const`.
+///
+/// ```text
+/// bitfield! {
+///     /// The control register of a timer.
+///     pub struct Ctrl(PackedBools16) {
+///         enable: 0,
+///         mode: 1..=3,
+///         prescaler: 4..=7,
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn bitfield(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as BitfieldInput);
+    expand_bitfield(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct BitfieldInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    packed: Type,
+    fields: Punctuated<BitfieldField, Token![,]>,
+}
+
+struct BitfieldField {
+    attrs: Vec<Attribute>,
+    name: Ident,
+    start: LitInt,
+    /// The last index of the field, which is the same as `start` for a `bool` field.
+    last: u32,
+    is_bool: bool,
+}
+
+impl Parse for BitfieldInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let name = input.parse()?;
+        let packed;
+        parenthesized!(packed in input);
+        let packed = packed.parse()?;
+        let fields;
+        braced!(fields in input);
+        let fields = fields.parse_terminated(BitfieldField::parse, Token![,])?;
+        Ok(Self { attrs, vis, name, packed, fields })
+    }
+}
+
+impl Parse for BitfieldField {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let start: LitInt = input.parse()?;
+        let first = start.base10_parse::<u32>()?;
+        let (last, is_bool) = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            (input.parse::<LitInt>()?.base10_parse::<u32>()?, false)
+        } else if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            let end = input.parse::<LitInt>()?;
+            match end.base10_parse::<u32>()?.checked_sub(1) {
+                Some(last) => (last, false),
+                None => return Err(Error::new_spanned(end, "a field needs at least one bit")),
+            }
+        } else {
+            (first, true)
+        };
+        if last < first {
+            return Err(Error::new_spanned(&name, "a field needs at least one bit"));
+        }
+        Ok(Self { attrs, name, start, last, is_bool })
+    }
+}
+
+fn expand_bitfield(input: BitfieldInput) -> syn::Result<TokenStream2> {
+    let BitfieldInput { attrs, vis, name, packed, fields } = input;
+    let packed_name = match &packed {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last().map(|seg| seg.ident.to_string()),
+        _ => None,
+    };
+    let (capacity, repr) = match packed_name.as_deref() {
+        Some("PackedBools8") => (8, quote!(u8)),
+        Some("PackedBools16") => (16, quote!(u16)),
+        Some("PackedBools32") => (32, quote!(u32)),
+        Some("PackedBools64") => (64, quote!(u64)),
+        Some("PackedBools128") => (128, quote!(u128)),
+        _ => return Err(Error::new_spanned(&packed, "bitfield! needs a PackedBools type")),
+    };
+
+    let mut used = 0u128;
+    let mut accessors = Vec::new();
+    for field in &fields {
+        let BitfieldField { attrs, name: field_name, start, last, is_bool } = field;
+        let first = start.base10_parse::<u32>()?;
+        if *last >= capacity {
+            return Err(Error::new_spanned(field_name, format!("the field doesn't fit in {capacity} bits")));
+        }
+        let width = last - first + 1;
+        let mask = u128::MAX >> (128 - width);
+        if used & (mask << first) != 0 {
+            return Err(Error::new_spanned(field_name, "the field overlaps another field"));
+        }
+        used |= mask << first;
+
+        let setter = format_ident!("set_{}", field_name);
+        let with = format_ident!("with_{}", field_name);
+        let set_doc = format!("Sets the `{field_name}` field to val.");
+        let with_doc = format!("Returns a copy with the `{field_name}` field set to val.");
+        let idx = first as u8;
+        accessors.push(if *is_bool {
+            quote! {
+                #(#attrs)*
+                pub const fn #field_name(self) -> bool {
+                    self.0.get(#idx)
+                }
+
+                #[doc = #set_doc]
+                pub const fn #setter(&mut self, val: bool) {
+                    self.0.set(val, #idx);
+                }
+
+                #[doc = #with_doc]
+                pub const fn #with(mut self, val: bool) -> Self {
+                    self.#setter(val);
+                    self
+                }
+            }
+        } else {
+            let ty = match width {
+                0..=8 => quote!(u8),
+                9..=16 => quote!(u16),
+                17..=32 => quote!(u32),
+                33..=64 => quote!(u64),
+                _ => quote!(u128),
+            };
+            let mask = proc_macro2::Literal::u128_unsuffixed(mask);
+            quote! {
+                #(#attrs)*
+                pub const fn #field_name(self) -> #ty {
+                    ((self.0.to_bits() >> #first) & #mask) as #ty
+                }
+
+                #[doc = #set_doc]
+                ///
+                /// The bits of val that don't fit in the field are ignored.
+                pub const fn #setter(&mut self, val: #ty) {
+                    let cleared = self.0.to_bits() & !(#mask << #first);
+                    self.0 = <#packed>::from_bits(cleared | ((val as #repr & #mask) << #first));
+                }
+
+                #[doc = #with_doc]
+                ///
+                /// The bits of val that don't fit in the field are ignored.
+                pub const fn #with(mut self, val: #ty) -> Self {
+                    self.#setter(val);
+                    self
+                }
+            }
+        });
+    }
+    let field_names = fields.iter().map(|field| &field.name);
+
+    Ok(quote! {
+        #(#attrs)*
+        #[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+        #[repr(transparent)]
+        #vis struct #name(#packed);
+
+        #[allow(dead_code)]
+        impl #name {
+            /// Creates a value with every field zero.
+            pub const fn new() -> Self {
+                Self(<#packed>::new())
+            }
+
+            /// Creates a value from its packed form, keeping the bits outside the fields.
+            pub const fn from_packed(packed: #packed) -> Self {
+                Self(packed)
+            }
+
+            /// Returns the packed form of this value.
+            pub const fn to_packed(self) -> #packed {
+                self.0
+            }
+
+            #(#accessors)*
+        }
+
+        impl ::core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(stringify!(#name))
+                    #( .field(stringify!(#field_names), &self.#field_names()) )*
+                    .finish()
+            }
+        }
+    })
+}
+
+fn is_bool(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident("bool"),
+        Type::Group(group) => is_bool(&group.elem),
+        Type::Paren(paren) => is_bool(&paren.elem),
+        _ => false,
+    }
+}
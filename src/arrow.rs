@@ -0,0 +1,301 @@
+//! Building and reading Apache Arrow validity bitmaps.
+//!
+//! An Arrow validity bitmap uses the same bit order as the rest of this crate:
+//! value `i` is valid if bit `i % 8` of byte `i / 8` is set.
+//! Buffers written here are padded with zero bytes to a multiple of [`ALIGNMENT`] bytes,
+//! which is what the Arrow format recommends.
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{slices, BufferExhausted};
+
+/// The number of bytes Arrow buffers are padded to a multiple of.
+pub const ALIGNMENT: usize = 64;
+
+/// Returns the size in bytes of a padded validity bitmap for `len` values.
+pub const fn padded_len(len: usize) -> usize {
+    len.div_ceil(8).div_ceil(ALIGNMENT) * ALIGNMENT
+}
+
+/// Writes a validity bitmap for the given values into `out`,
+/// returning the number of values written.
+///
+/// The rest of `out` is filled with zeros.
+/// To meet the padding recommendation, `out` should be [`padded_len`] bytes long.
+///
+/// If the values don't all fit, this returns [`BufferExhausted`]
+/// and the contents of `out` are unspecified.
+pub fn write_validity(valid: impl IntoIterator<Item = bool>, out: &mut [u8]) -> Result<usize, BufferExhausted> {
+    out.fill(0);
+    let mut len = 0;
+    for val in valid {
+        if len == out.len() * 8 {
+            return Err(BufferExhausted);
+        }
+        if val {
+            out[len / 8] |= 1 << (len % 8);
+        }
+        len += 1;
+    }
+    Ok(len)
+}
+
+/// Writes a padded validity bitmap for the given values into a new `Vec`,
+/// returning the bitmap and the number of values.
+#[cfg(feature = "alloc")]
+pub fn validity_vec(valid: impl IntoIterator<Item = bool>) -> (alloc::vec::Vec<u8>, usize) {
+    let mut out = alloc::vec::Vec::new();
+    let mut len: usize = 0;
+    for val in valid {
+        if len.is_multiple_of(8) {
+            out.push(0);
+        }
+        if val {
+            out[len / 8] |= 1 << (len % 8);
+        }
+        len += 1;
+    }
+    out.resize(padded_len(len), 0);
+    (out, len)
+}
+
+/// Counts the null (false) values in `len` values of a bitmap, starting at bit `offset`.
+///
+/// # Panics
+///
+/// Panics if the values are past the end of the bitmap.
+pub fn null_count(bytes: &[u8], offset: usize, len: usize) -> usize {
+    Validity::new(bytes, offset, len).null_count()
+}
+
+/// A view of `len` values in a validity bitmap, starting at a bit offset.
+///
+/// This is how Arrow arrays slice their validity bitmaps without copying them.
+///
+/// ```
+/// use packed_booleans::arrow::Validity;
+///
+/// let bitmap = [0b1011_0110, 0b0000_0001];
+/// let validity = Validity::new(&bitmap, 1, 8);
+/// assert!(validity.is_valid(0));
+/// assert_eq!(validity.null_count(), 2);
+/// assert_eq!(validity.slice(2, 3).null_count(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Validity<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> Validity<'a> {
+    /// Creates a view of `len` values of a bitmap, starting at bit `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are past the end of the bitmap.
+    pub fn new(bytes: &'a [u8], offset: usize, len: usize) -> Self {
+        let end = offset.checked_add(len);
+        assert!(end.is_some_and(|end| end <= bytes.len() * 8), "The values cannot be past the end of the bitmap");
+        Self { bytes, offset, len }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit offset of the first value in the bitmap.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the whole underlying bitmap.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns true if the value at the given index is valid (not null).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn is_valid(&self, idx: usize) -> bool {
+        assert!(idx < self.len, "The index must be less than the length");
+        slices::get_bit(self.bytes, (self.offset + idx) as u64)
+    }
+
+    /// Returns true if the value at the given index is null.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn is_null(&self, idx: usize) -> bool {
+        !self.is_valid(idx)
+    }
+
+    /// Counts the null values.
+    pub fn null_count(&self) -> usize {
+        self.len - self.valid_count()
+    }
+
+    /// Counts the valid values.
+    pub fn valid_count(&self) -> usize {
+        if self.len == 0 {
+            return 0;
+        }
+        let end = self.offset + self.len;
+        let (first, last) = (self.offset / 8, (end - 1) / 8);
+        let head_mask = 0xff << (self.offset % 8);
+        let tail_mask = 0xff >> (7 - (end - 1) % 8);
+        if first == last {
+            return (self.bytes[first] & head_mask & tail_mask).count_ones() as usize;
+        }
+        let middle = slices::count_true(&self.bytes[first + 1..last]) as usize;
+        middle
+            + (self.bytes[first] & head_mask).count_ones() as usize
+            + (self.bytes[last] & tail_mask).count_ones() as usize
+    }
+
+    /// Returns a view of `len` values starting at `offset` in this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are past the end of this view.
+    pub fn slice(&self, offset: usize, len: usize) -> Self {
+        let end = offset.checked_add(len);
+        assert!(end.is_some_and(|end| end <= self.len), "The values cannot be past the end of the view");
+        Self { bytes: self.bytes, offset: self.offset + offset, len }
+    }
+
+    /// Copies the values into a bitmap starting at bit 0,
+    /// filling the rest of `out` with zeros.
+    ///
+    /// This fails with [`BufferExhausted`] and writes nothing if the values don't fit.
+    pub fn write_aligned(&self, out: &mut [u8]) -> Result<(), BufferExhausted> {
+        if self.len > out.len() * 8 {
+            return Err(BufferExhausted);
+        }
+        out.fill(0);
+        slices::copy_bits(out, 0, self.bytes, self.offset as u64, self.len as u64);
+        Ok(())
+    }
+
+    /// Copies the values into a new padded bitmap starting at bit 0.
+    #[cfg(feature = "alloc")]
+    pub fn to_aligned_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut out = alloc::vec![0; padded_len(self.len)];
+        slices::copy_bits(&mut out, 0, self.bytes, self.offset as u64, self.len as u64);
+        out
+    }
+
+    /// Returns an iterator over whether each value is valid.
+    pub fn iter(&self) -> ValidityIter<'a> {
+        ValidityIter { validity: *self, range: 0..self.len }
+    }
+}
+
+impl<'a> IntoIterator for Validity<'a> {
+    type Item = bool;
+    type IntoIter = ValidityIter<'a>;
+
+    fn into_iter(self) -> ValidityIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over whether each value in a [`Validity`] is valid.
+#[derive(Debug, Clone)]
+pub struct ValidityIter<'a> {
+    validity: Validity<'a>,
+    range: Range<usize>,
+}
+
+impl Iterator for ValidityIter<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().map(|idx| self.validity.is_valid(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ValidityIter<'_> {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().map(|idx| self.validity.is_valid(idx))
+    }
+}
+
+impl ExactSizeIterator for ValidityIter<'_> {}
+
+impl FusedIterator for ValidityIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{null_count, padded_len, write_validity, Validity, ALIGNMENT};
+    use crate::BufferExhausted;
+
+    #[test]
+    fn padding() {
+        assert_eq!(padded_len(0), 0);
+        assert_eq!(padded_len(1), ALIGNMENT);
+        assert_eq!(padded_len(512), ALIGNMENT);
+        assert_eq!(padded_len(513), 2 * ALIGNMENT);
+    }
+
+    #[test]
+    fn write() {
+        let mut buf = [0xff; 3];
+        let values = [true, false, true, true, false, false, false, false, true, true];
+        assert_eq!(write_validity(values, &mut buf), Ok(10));
+        assert_eq!(buf, [0b0000_1101, 0b11, 0]);
+        assert_eq!(null_count(&buf, 0, 10), 5);
+        assert_eq!(write_validity([true; 25], &mut buf), Err(BufferExhausted));
+    }
+
+    #[test]
+    fn offset_views() {
+        let bytes = [0b1011_0110, 0xff, 0x00, 0b0101_0101];
+        for offset in 0..32 {
+            for len in 0..=32 - offset {
+                let validity = Validity::new(&bytes, offset, len);
+                let nulls = (offset..offset + len)
+                    .filter(|&i| bytes[i / 8] & (1 << (i % 8)) == 0)
+                    .count();
+                assert_eq!(validity.null_count(), nulls, "{offset} {len}");
+                assert!(validity.iter().eq((offset..offset + len).map(|i| bytes[i / 8] & (1 << (i % 8)) != 0)));
+            }
+        }
+        let view = Validity::new(&bytes, 3, 20).slice(4, 10);
+        assert_eq!(view.offset(), 7);
+        let mut out = [0xff; 2];
+        view.write_aligned(&mut out).unwrap();
+        assert_eq!(out, [0xff, 0b01]);
+        assert_eq!(view.write_aligned(&mut [0]), Err(BufferExhausted));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vecs() {
+        let (bitmap, len) = super::validity_vec([true, false, true]);
+        assert_eq!(len, 3);
+        assert_eq!(bitmap.len(), ALIGNMENT);
+        assert_eq!(bitmap[0], 0b101);
+        assert_eq!(Validity::new(&bitmap, 1, 2).to_aligned_vec()[0], 0b10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        Validity::new(&[0], 4, 5);
+    }
+}
@@ -0,0 +1,319 @@
+//! Packed values that can be shared between threads.
+//!
+//! On targets without native atomic read-modify-write operations,
+//! these types are only available with the `critical-section` feature,
+//! which emulates them by doing each operation inside a critical section.
+
+// these are unused on targets where none of the types are available
+#[allow(unused_imports)]
+use core::sync::atomic::Ordering;
+
+#[allow(unused_imports)]
+use crate::{PackedBools8, PackedBools16, PackedBools32, PackedBools64};
+
+/// The integer atomics the packed atomics are built on,
+/// which are emulated where the target doesn't have them.
+mod imp {
+    #[cfg(target_has_atomic = "8")]
+    pub(crate) use core::sync::atomic::AtomicU8;
+    #[cfg(all(not(target_has_atomic = "8"), feature = "critical-section"))]
+    pub(crate) type AtomicU8 = super::cs::CsAtomic<u8>;
+
+    #[cfg(target_has_atomic = "16")]
+    pub(crate) use core::sync::atomic::AtomicU16;
+    #[cfg(all(not(target_has_atomic = "16"), feature = "critical-section"))]
+    pub(crate) type AtomicU16 = super::cs::CsAtomic<u16>;
+
+    #[cfg(target_has_atomic = "32")]
+    pub(crate) use core::sync::atomic::AtomicU32;
+    #[cfg(all(not(target_has_atomic = "32"), feature = "critical-section"))]
+    pub(crate) type AtomicU32 = super::cs::CsAtomic<u32>;
+
+    #[cfg(target_has_atomic = "64")]
+    pub(crate) use core::sync::atomic::AtomicU64;
+    #[cfg(all(not(target_has_atomic = "64"), feature = "critical-section"))]
+    pub(crate) type AtomicU64 = super::cs::CsAtomic<u64>;
+}
+
+macro_rules! atomic_packed {
+    ($name:ident, $pkd:ident, $atomic:ident, $width:literal) => {
+        #[doc = concat!("A `", stringify!($pkd), "` that can be shared between threads.")]
+        ///
+        /// The methods take an [`Ordering`] with the same meaning
+        /// as the matching methods on the integer atomics.
+        #[cfg(any(target_has_atomic = $width, feature = "critical-section"))]
+        #[derive(Default)]
+        pub struct $name(imp::$atomic);
+
+        #[cfg(any(target_has_atomic = $width, feature = "critical-section"))]
+        impl $name {
+            #[doc = concat!("Creates a new `", stringify!($name), "`.")]
+            pub const fn new(val: $pkd) -> Self {
+                Self(imp::$atomic::new(val.to_bits()))
+            }
+
+            /// Returns the value, consuming the atomic.
+            pub fn into_inner(self) -> $pkd {
+                $pkd::from_bits(self.0.into_inner())
+            }
+
+            /// Loads the value.
+            pub fn load(&self, order: Ordering) -> $pkd {
+                $pkd::from_bits(self.0.load(order))
+            }
+
+            /// Stores a value.
+            pub fn store(&self, val: $pkd, order: Ordering) {
+                self.0.store(val.to_bits(), order);
+            }
+
+            /// Stores a value, returning the previous value.
+            pub fn swap(&self, val: $pkd, order: Ordering) -> $pkd {
+                $pkd::from_bits(self.0.swap(val.to_bits(), order))
+            }
+
+            /// Stores `new` if the value is `current`,
+            /// returning the previous value in `Ok` if it was stored and in `Err` if it wasn't.
+            pub fn compare_exchange(
+                &self,
+                current: $pkd,
+                new: $pkd,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$pkd, $pkd> {
+                self.0.compare_exchange(current.to_bits(), new.to_bits(), success, failure)
+                    .map($pkd::from_bits)
+                    .map_err($pkd::from_bits)
+            }
+
+            /// Sets the value to the bitwise AND of itself and `val`, returning the previous value.
+            pub fn fetch_and(&self, val: $pkd, order: Ordering) -> $pkd {
+                $pkd::from_bits(self.0.fetch_and(val.to_bits(), order))
+            }
+
+            /// Sets the value to the bitwise OR of itself and `val`, returning the previous value.
+            pub fn fetch_or(&self, val: $pkd, order: Ordering) -> $pkd {
+                $pkd::from_bits(self.0.fetch_or(val.to_bits(), order))
+            }
+
+            /// Sets the value to the bitwise XOR of itself and `val`, returning the previous value.
+            pub fn fetch_xor(&self, val: $pkd, order: Ordering) -> $pkd {
+                $pkd::from_bits(self.0.fetch_xor(val.to_bits(), order))
+            }
+
+            /// Loads the boolean at the given index.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $width, " - 1.")]
+            #[track_caller]
+            pub fn get(&self, idx: u8, order: Ordering) -> bool {
+                self.load(order).get(idx)
+            }
+
+            /// Sets the boolean at the given index to val, returning its previous value.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $width, " - 1.")]
+            #[track_caller]
+            pub fn fetch_set(&self, val: bool, idx: u8, order: Ordering) -> bool {
+                let mut mask = $pkd::new();
+                mask.set(true, idx);
+                let prev = match val {
+                    true => self.fetch_or(mask, order),
+                    false => self.fetch_and(!mask, order),
+                };
+                prev.get(idx)
+            }
+
+            /// Toggles the boolean at the given index, returning its previous value.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $width, " - 1.")]
+            #[track_caller]
+            pub fn fetch_toggle(&self, idx: u8, order: Ordering) -> bool {
+                let mut mask = $pkd::new();
+                mask.set(true, idx);
+                self.fetch_xor(mask, order).get(idx)
+            }
+
+            /// Replaces the value with the result of applying `f` to it, returning the previous value.
+            ///
+            /// This retries until no other thread changes the value in between,
+            /// so `f` may be called more than once.
+            pub fn modify(&self, set_order: Ordering, fetch_order: Ordering, mut f: impl FnMut($pkd) -> $pkd) -> $pkd {
+                let prev = self.0.fetch_update(set_order, fetch_order, |bits| {
+                    Some(f($pkd::from_bits(bits)).to_bits())
+                });
+                match prev {
+                    Ok(bits) | Err(bits) => $pkd::from_bits(bits),
+                }
+            }
+        }
+
+        #[cfg(any(target_has_atomic = $width, feature = "critical-section"))]
+        impl From<$pkd> for $name {
+            fn from(val: $pkd) -> Self {
+                Self::new(val)
+            }
+        }
+
+        #[cfg(any(target_has_atomic = $width, feature = "critical-section"))]
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+            }
+        }
+    };
+}
+
+atomic_packed!(AtomicPackedBools8, PackedBools8, AtomicU8, "8");
+atomic_packed!(AtomicPackedBools16, PackedBools16, AtomicU16, "16");
+atomic_packed!(AtomicPackedBools32, PackedBools32, AtomicU32, "32");
+atomic_packed!(AtomicPackedBools64, PackedBools64, AtomicU64, "64");
+
+/// Emulated atomics for targets without native read-modify-write operations.
+#[cfg(feature = "critical-section")]
+mod cs {
+    use core::{cell::UnsafeCell, ops::{BitAnd, BitOr, BitXor}, sync::atomic::Ordering};
+
+    /// An integer behind a lock, with the same methods as the integer atomics.
+    ///
+    /// The orderings are ignored, as every operation happens inside a critical section.
+    #[derive(Default)]
+    pub(crate) struct CsAtomic<T>(UnsafeCell<T>);
+
+    // SAFETY: the value is only accessed inside a critical section
+    unsafe impl<T: Send> Sync for CsAtomic<T> {}
+
+    #[allow(dead_code)]
+    impl<T: Copy + PartialEq + BitAnd<Output = T> + BitOr<Output = T> + BitXor<Output = T>> CsAtomic<T> {
+        pub(crate) const fn new(val: T) -> Self {
+            Self(UnsafeCell::new(val))
+        }
+
+        pub(crate) fn into_inner(self) -> T {
+            self.0.into_inner()
+        }
+
+        /// Replaces the value with `f(value)` if that returns `Some`, returning the previous value.
+        fn update(&self, f: impl FnOnce(T) -> Option<T>) -> Result<T, T> {
+            critical_section::with(|_| {
+                // SAFETY: nothing else accesses the value during the critical section
+                let val = unsafe { &mut *self.0.get() };
+                let prev = *val;
+                match f(prev) {
+                    Some(new) => {
+                        *val = new;
+                        Ok(prev)
+                    }
+                    None => Err(prev),
+                }
+            })
+        }
+
+        pub(crate) fn load(&self, _: Ordering) -> T {
+            critical_section::with(|_| {
+                // SAFETY: nothing else accesses the value during the critical section
+                unsafe { *self.0.get() }
+            })
+        }
+
+        pub(crate) fn store(&self, val: T, _: Ordering) {
+            let _ = self.update(|_| Some(val));
+        }
+
+        pub(crate) fn swap(&self, val: T, _: Ordering) -> T {
+            self.update(|_| Some(val)).unwrap_or_else(|prev| prev)
+        }
+
+        pub(crate) fn compare_exchange(&self, current: T, new: T, _: Ordering, _: Ordering) -> Result<T, T> {
+            self.update(|val| (val == current).then_some(new))
+        }
+
+        pub(crate) fn fetch_and(&self, val: T, _: Ordering) -> T {
+            self.update(|prev| Some(prev & val)).unwrap_or_else(|prev| prev)
+        }
+
+        pub(crate) fn fetch_or(&self, val: T, _: Ordering) -> T {
+            self.update(|prev| Some(prev | val)).unwrap_or_else(|prev| prev)
+        }
+
+        pub(crate) fn fetch_xor(&self, val: T, _: Ordering) -> T {
+            self.update(|prev| Some(prev ^ val)).unwrap_or_else(|prev| prev)
+        }
+
+        pub(crate) fn fetch_update(&self, _: Ordering, _: Ordering, f: impl FnMut(T) -> Option<T>) -> Result<T, T> {
+            self.update(f)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use core::sync::atomic::Ordering::SeqCst;
+
+        use super::CsAtomic;
+
+        #[test]
+        fn emulated_ops() {
+            let atomic = CsAtomic::new(0b1100_u8);
+            assert_eq!(atomic.fetch_or(0b0001, SeqCst), 0b1100);
+            assert_eq!(atomic.fetch_and(0b0111, SeqCst), 0b1101);
+            assert_eq!(atomic.fetch_xor(0b1111, SeqCst), 0b0101);
+            assert_eq!(atomic.swap(0xff, SeqCst), 0b1010);
+            assert_eq!(atomic.compare_exchange(0, 1, SeqCst, SeqCst), Err(0xff));
+            assert_eq!(atomic.compare_exchange(0xff, 1, SeqCst, SeqCst), Ok(0xff));
+            assert_eq!(atomic.fetch_update(SeqCst, SeqCst, |v| Some(v + 1)), Ok(1));
+            atomic.store(7, SeqCst);
+            assert_eq!(atomic.load(SeqCst), 7);
+            assert_eq!(atomic.into_inner(), 7);
+        }
+    }
+}
+
+#[cfg(all(test, any(all(target_has_atomic = "8", target_has_atomic = "64"), feature = "critical-section")))]
+mod tests {
+    use core::sync::atomic::Ordering::SeqCst;
+
+    use super::{AtomicPackedBools8, AtomicPackedBools64};
+    use crate::{PackedBools8, PackedBools64};
+
+    #[test]
+    fn bit_ops() {
+        let atomic = AtomicPackedBools8::new(PackedBools8::from_bits(0b0001));
+        assert!(!atomic.fetch_set(true, 3, SeqCst));
+        assert!(atomic.fetch_set(false, 0, SeqCst));
+        assert!(!atomic.fetch_toggle(7, SeqCst));
+        assert!(atomic.get(3, SeqCst));
+        assert_eq!(atomic.load(SeqCst), 0b1000_1000);
+        assert_eq!(atomic.fetch_xor(PackedBools8::from_bits(0xff), SeqCst), 0b1000_1000);
+        assert_eq!(atomic.into_inner(), 0b0111_0111);
+    }
+
+    #[test]
+    fn modify() {
+        let atomic = AtomicPackedBools64::default();
+        let prev = atomic.modify(SeqCst, SeqCst, |val| val | PackedBools64::from_bits(1 << 40));
+        assert_eq!(prev, PackedBools64::new());
+        assert_eq!(atomic.swap(PackedBools64::new(), SeqCst), 1 << 40);
+        assert_eq!(
+            atomic.compare_exchange(PackedBools64::new(), PackedBools64::from_bits(5), SeqCst, SeqCst),
+            Ok(PackedBools64::new())
+        );
+    }
+
+    #[test]
+    fn shared() {
+        extern crate std;
+        let atomic = AtomicPackedBools64::default();
+        std::thread::scope(|s| {
+            for idx in 0..64 {
+                let atomic = &atomic;
+                s.spawn(move || atomic.fetch_set(true, idx, SeqCst));
+            }
+        });
+        assert_eq!(atomic.into_inner(), u64::MAX);
+    }
+}
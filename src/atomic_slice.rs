@@ -0,0 +1,329 @@
+//! A bitmap shared between threads.
+
+use core::sync::atomic::Ordering;
+#[cfg(not(all(test, loom)))]
+use core::sync::atomic::AtomicU8;
+// loom checks the tests below under every interleaving of its own atomics
+#[cfg(all(test, loom))]
+use loom::sync::atomic::AtomicU8;
+
+/// A view of a slice of atomic bytes as a bitmap that can be shared between threads.
+///
+/// Bit `i` of the bitmap is bit `i % 8` of byte `i / 8`.
+/// Each method on a single bit is one atomic operation on the byte containing it.
+///
+/// ```
+/// use core::sync::atomic::{AtomicU8, Ordering};
+/// use packed_booleans::AtomicBitSlice;
+///
+/// let bytes = [AtomicU8::new(0), AtomicU8::new(0)];
+/// let bits = AtomicBitSlice::new(&bytes);
+/// assert!(!bits.test_and_set(9, Ordering::AcqRel));
+/// assert!(bits.test_and_set(9, Ordering::AcqRel));
+/// assert_eq!(bits.count_true(Ordering::Relaxed), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AtomicBitSlice<'a> {
+    bytes: &'a [AtomicU8],
+}
+
+impl<'a> AtomicBitSlice<'a> {
+    /// Creates a view of the given bytes.
+    pub const fn new(bytes: &'a [AtomicU8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the viewed bytes.
+    pub const fn bytes(&self) -> &'a [AtomicU8] {
+        self.bytes
+    }
+
+    /// Returns the number of bits in the bitmap.
+    pub const fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    /// Returns true if the bitmap has no bits.
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Loads the bit at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn get(&self, idx: usize, order: Ordering) -> bool {
+        let (byte, mask) = self.position(idx);
+        byte.load(order) & mask != 0
+    }
+
+    /// Sets the bit at the given index to true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn set(&self, idx: usize, order: Ordering) {
+        self.test_and_set(idx, order);
+    }
+
+    /// Sets the bit at the given index to false.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn clear(&self, idx: usize, order: Ordering) {
+        self.test_and_clear(idx, order);
+    }
+
+    /// Sets the bit at the given index to true, returning its previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn test_and_set(&self, idx: usize, order: Ordering) -> bool {
+        let (byte, mask) = self.position(idx);
+        byte.fetch_or(mask, order) & mask != 0
+    }
+
+    /// Sets the bit at the given index to false, returning its previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn test_and_clear(&self, idx: usize, order: Ordering) -> bool {
+        let (byte, mask) = self.position(idx);
+        byte.fetch_and(!mask, order) & mask != 0
+    }
+
+    /// Toggles the bit at the given index, returning its previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the length of the bitmap.
+    pub fn test_and_toggle(&self, idx: usize, order: Ordering) -> bool {
+        let (byte, mask) = self.position(idx);
+        byte.fetch_xor(mask, order) & mask != 0
+    }
+
+    /// Counts how many bits are true.
+    ///
+    /// Each byte is loaded separately,
+    /// so this isn't a snapshot if other threads are changing the bitmap.
+    pub fn count_true(&self, order: Ordering) -> usize {
+        self.bytes.iter().map(|b| b.load(order).count_ones() as usize).sum()
+    }
+
+    /// Finds the first false bit and sets it to true, returning its index,
+    /// or returns `None` if every bit is true.
+    ///
+    /// This is a single atomic compare-and-swap of the claimed bit's byte,
+    /// so two threads can never claim the same bit.
+    /// A successful claim has `Acquire` ordering,
+    /// so it synchronizes with clearing the bit using `Release` ordering.
+    ///
+    /// As bits may be cleared while this runs,
+    /// `None` only means that every bit was true when it was checked.
+    pub fn claim_first_clear(&self) -> Option<usize> {
+        for (byte_idx, byte) in self.bytes.iter().enumerate() {
+            let mut current = byte.load(Ordering::Relaxed);
+            while current != u8::MAX {
+                let bit = current.trailing_ones();
+                match byte.compare_exchange_weak(current, current | (1 << bit), Ordering::Acquire, Ordering::Relaxed) {
+                    Ok(_) => return Some(byte_idx * 8 + bit as usize),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        None
+    }
+
+    fn position(&self, idx: usize) -> (&'a AtomicU8, u8) {
+        assert!(idx < self.len(), "The index must be less than the length of the bitmap");
+        (&self.bytes[idx / 8], 1 << (idx % 8))
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    extern crate std;
+    use core::sync::atomic::{AtomicU8, Ordering::{Relaxed, Release, SeqCst}};
+
+    use super::AtomicBitSlice;
+
+    #[test]
+    fn single_bits() {
+        let bytes = [AtomicU8::new(0b1000_0000), AtomicU8::new(0)];
+        let bits = AtomicBitSlice::new(&bytes);
+        assert_eq!(bits.len(), 16);
+        assert!(bits.get(7, SeqCst));
+        bits.set(8, SeqCst);
+        bits.clear(7, SeqCst);
+        assert!(!bits.test_and_toggle(15, SeqCst));
+        assert!(bits.test_and_clear(15, SeqCst));
+        assert_eq!(bytes.map(|b| b.into_inner()), [0, 1]);
+    }
+
+    #[test]
+    fn shared() {
+        let bytes: [AtomicU8; 32] = Default::default();
+        let bits = AtomicBitSlice::new(&bytes);
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                s.spawn(move || {
+                    for idx in (t..bits.len()).step_by(4) {
+                        bits.set(idx, Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(bits.count_true(Relaxed), 256);
+    }
+
+    #[test]
+    fn claim() {
+        let bytes = [AtomicU8::new(0b1011_1111), AtomicU8::new(0xff), AtomicU8::new(0b1111_1110)];
+        let bits = AtomicBitSlice::new(&bytes);
+        assert_eq!(bits.claim_first_clear(), Some(6));
+        assert_eq!(bits.claim_first_clear(), Some(16));
+        assert_eq!(bits.claim_first_clear(), None);
+        bits.clear(9, Release);
+        assert_eq!(bits.claim_first_clear(), Some(9));
+    }
+
+    #[test]
+    fn claim_concurrently() {
+        const THREADS: usize = 8;
+        let bytes: [AtomicU8; 16] = Default::default();
+        let bits = AtomicBitSlice::new(&bytes);
+        // every slot is claimed by exactly one thread, and then none are left
+        let claimed: std::vec::Vec<std::vec::Vec<usize>> = std::thread::scope(|s| {
+            let handles: std::vec::Vec<_> = (0..THREADS)
+                .map(|_| s.spawn(move || core::iter::from_fn(|| bits.claim_first_clear()).collect()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let mut all: std::vec::Vec<usize> = claimed.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..128).collect::<std::vec::Vec<_>>());
+        assert_eq!(bits.claim_first_clear(), None);
+
+        // slots released while other threads claim are all reclaimed
+        std::thread::scope(|s| {
+            for t in 0..THREADS {
+                s.spawn(move || {
+                    for round in 0..100 {
+                        let idx = (t * 16 + round) % 128;
+                        if bits.test_and_clear(idx, Release) {
+                            // a scan can miss a slot released behind it, but one stays clear until claimed
+                            while bits.claim_first_clear().is_none() {
+                                core::hint::spin_loop();
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(bits.count_true(Relaxed), 128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        AtomicBitSlice::new(&[AtomicU8::new(0)]).get(8, SeqCst);
+    }
+}
+
+/// Models of the concurrent operations, which loom runs under every interleaving of the threads.
+///
+/// Run them with `RUSTFLAGS="--cfg loom" cargo test --release --lib atomic_slice::loom_tests`.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    extern crate std;
+    use core::sync::atomic::Ordering::{Relaxed, Release, SeqCst};
+    use std::vec::Vec;
+
+    use loom::{cell::UnsafeCell, sync::{atomic::AtomicU8, Arc}, thread};
+
+    use super::AtomicBitSlice;
+
+    fn shared(bytes: &[u8]) -> Arc<Vec<AtomicU8>> {
+        Arc::new(bytes.iter().map(|&b| AtomicU8::new(b)).collect())
+    }
+
+    #[test]
+    fn claims_are_distinct() {
+        loom::model(|| {
+            let bytes = shared(&[0b1111_1100]);
+            let handle = {
+                let bytes = bytes.clone();
+                thread::spawn(move || AtomicBitSlice::new(&bytes).claim_first_clear())
+            };
+            let ours = AtomicBitSlice::new(&bytes).claim_first_clear();
+            let theirs = handle.join().unwrap();
+            let mut claimed = [ours.unwrap(), theirs.unwrap()];
+            claimed.sort_unstable();
+            assert_eq!(claimed, [0, 1]);
+            assert_eq!(bytes[0].load(Relaxed), u8::MAX);
+        });
+    }
+
+    #[test]
+    fn last_bit_claimed_once() {
+        loom::model(|| {
+            let bytes = shared(&[0b1110_1111, u8::MAX]);
+            let handle = {
+                let bytes = bytes.clone();
+                thread::spawn(move || AtomicBitSlice::new(&bytes).claim_first_clear())
+            };
+            let ours = AtomicBitSlice::new(&bytes).claim_first_clear();
+            let theirs = handle.join().unwrap();
+            assert!(matches!((ours, theirs), (Some(4), None) | (None, Some(4))));
+        });
+    }
+
+    #[test]
+    fn claim_races_set() {
+        loom::model(|| {
+            let bytes = shared(&[0b1111_0111, 0]);
+            let handle = {
+                let bytes = bytes.clone();
+                thread::spawn(move || AtomicBitSlice::new(&bytes).test_and_set(3, SeqCst))
+            };
+            let claimed = AtomicBitSlice::new(&bytes).claim_first_clear();
+            let was_set = handle.join().unwrap();
+            // whichever got to bit 3 first owns it, and the claim moves on to the next byte otherwise
+            match was_set {
+                true => assert_eq!(claimed, Some(3)),
+                false => assert_eq!(claimed, Some(8)),
+            }
+        });
+    }
+
+    #[test]
+    fn release_then_claim_hands_off() {
+        loom::model(|| {
+            let bytes = shared(&[u8::MAX]);
+            let slot = Arc::new(UnsafeCell::new(0));
+            let handle = {
+                let (bytes, slot) = (bytes.clone(), slot.clone());
+                thread::spawn(move || {
+                    // SAFETY: bit 2 is set, so this thread owns the slot until it clears the bit
+                    slot.with_mut(|val| unsafe { *val = 7 });
+                    AtomicBitSlice::new(&bytes).clear(2, Release);
+                })
+            };
+            let bits = AtomicBitSlice::new(&bytes);
+            let idx = loop {
+                match bits.claim_first_clear() {
+                    Some(idx) => break idx,
+                    None => thread::yield_now(),
+                }
+            };
+            assert_eq!(idx, 2);
+            // SAFETY: claiming bit 2 synchronizes with the release, so the other thread is done with the slot
+            assert_eq!(slot.with(|val| unsafe { *val }), 7);
+            handle.join().unwrap();
+        });
+    }
+}
@@ -0,0 +1,388 @@
+//! A fixed-size array of packed booleans of any length.
+
+use core::{fmt, ops};
+
+use crate::{slices, BitPositions, BitSlice, BitSliceIter, BitSliceMut};
+
+/// An owned array of `BYTES * 8` packed booleans.
+///
+/// This is for when the fixed types are too small but allocating isn't an option.
+/// Boolean `i` is bit `i % 8` of byte `i / 8`.
+///
+/// ```
+/// use packed_booleans::BitArray;
+///
+/// let mut flags = BitArray::<32>::new();
+/// flags.set(true, 200);
+/// flags.set(true, 3);
+/// assert!(flags.get(200));
+/// assert_eq!(flags.count_true(), 2);
+/// assert!(flags.iter_ones().eq([3, 200]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitArray<const BYTES: usize> {
+    bytes: [u8; BYTES],
+}
+
+impl<const BYTES: usize> BitArray<BYTES> {
+    /// The number of booleans in the array.
+    pub const LEN: usize = BYTES * 8;
+
+    /// Creates a new `BitArray` with every boolean false.
+    pub const fn new() -> Self {
+        Self { bytes: [0; BYTES] }
+    }
+
+    /// Creates a new `BitArray` from its packed bytes.
+    pub const fn from_bytes(bytes: [u8; BYTES]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the packed bytes.
+    pub const fn to_bytes(self) -> [u8; BYTES] {
+        self.bytes
+    }
+
+    /// Returns a reference to the packed bytes.
+    pub const fn as_bytes(&self) -> &[u8; BYTES] {
+        &self.bytes
+    }
+
+    /// Returns a mutable reference to the packed bytes.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8; BYTES] {
+        &mut self.bytes
+    }
+
+    /// Returns the number of booleans in the array.
+    pub const fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    /// Returns true if the array holds no booleans, which is only when `BYTES` is 0.
+    pub const fn is_empty(&self) -> bool {
+        BYTES == 0
+    }
+
+    /// Gets the boolean at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub const fn get(&self, idx: usize) -> bool {
+        assert!(idx < Self::LEN, "The index is out of bounds");
+        (self.bytes[idx / 8] >> (idx % 8)) & 1 != 0
+    }
+
+    /// Gets the boolean at the given index, or `None` if it is out of bounds.
+    pub const fn try_get(&self, idx: usize) -> Option<bool> {
+        if idx < Self::LEN {
+            Some(self.get(idx))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the boolean at the given index to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, val: bool, idx: usize) {
+        slices::set_bit(&mut self.bytes, val, idx as u64);
+    }
+
+    /// Toggles the boolean at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn toggle(&mut self, idx: usize) {
+        slices::toggle_bit(&mut self.bytes, idx as u64);
+    }
+
+    /// Sets every boolean to val.
+    pub fn fill(&mut self, val: bool) {
+        self.bytes = [if val { 0xff } else { 0 }; BYTES];
+    }
+
+    /// Counts how many booleans are true.
+    pub fn count_true(&self) -> usize {
+        slices::count_true(&self.bytes) as usize
+    }
+
+    /// Returns true if any boolean is true.
+    pub fn any(&self) -> bool {
+        slices::any(&self.bytes)
+    }
+
+    /// Returns true if every boolean is true.
+    pub fn all(&self) -> bool {
+        slices::all(&self.bytes, Self::LEN as u64)
+    }
+
+    /// Returns an iterator over the booleans.
+    pub fn iter(&self) -> BitSliceIter<'_> {
+        self.as_bit_slice().iter()
+    }
+
+    /// Returns an iterator over the indices of the true booleans, in increasing order.
+    pub fn iter_ones(&self) -> BitPositions<'_> {
+        self.as_bit_slice().iter_ones()
+    }
+
+    /// Returns a view of all the booleans.
+    pub fn as_bit_slice(&self) -> BitSlice<'_> {
+        BitSlice::from_bytes(&self.bytes)
+    }
+
+    /// Returns a mutable view of all the booleans.
+    pub fn as_bit_slice_mut(&mut self) -> BitSliceMut<'_> {
+        BitSliceMut::from_bytes(&mut self.bytes)
+    }
+
+    /// Copies the booleans out of a view,
+    /// or returns `None` if the view doesn't have exactly [`LEN`](Self::LEN) booleans.
+    pub fn from_bit_slice(bits: BitSlice<'_>) -> Option<Self> {
+        if bits.len() != Self::LEN {
+            return None;
+        }
+        let mut array = Self::new();
+        array.as_bit_slice_mut().copy_from_bitslice(bits);
+        Some(array)
+    }
+}
+
+impl<const BYTES: usize> Default for BitArray<BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize> fmt::Debug for BitArray<BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitArray(")?;
+        self.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))?;
+        f.write_str(")")
+    }
+}
+
+impl<const BYTES: usize> From<[u8; BYTES]> for BitArray<BYTES> {
+    fn from(bytes: [u8; BYTES]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<const BYTES: usize> From<BitArray<BYTES>> for [u8; BYTES] {
+    fn from(array: BitArray<BYTES>) -> Self {
+        array.bytes
+    }
+}
+
+impl<'a, const BYTES: usize> From<&'a BitArray<BYTES>> for BitSlice<'a> {
+    fn from(array: &'a BitArray<BYTES>) -> Self {
+        array.as_bit_slice()
+    }
+}
+
+impl<'a, const BYTES: usize> From<&'a mut BitArray<BYTES>> for BitSliceMut<'a> {
+    fn from(array: &'a mut BitArray<BYTES>) -> Self {
+        array.as_bit_slice_mut()
+    }
+}
+
+impl<'a, const BYTES: usize> IntoIterator for &'a BitArray<BYTES> {
+    type Item = bool;
+    type IntoIter = BitSliceIter<'a>;
+
+    fn into_iter(self) -> BitSliceIter<'a> {
+        self.iter()
+    }
+}
+
+macro_rules! impl_array_binop {
+    ($op:tt, $trait:ident $method:ident, $assign_trait:ident $assign_method:ident) => {
+        impl<const BYTES: usize> ops::$assign_trait for BitArray<BYTES> {
+            fn $assign_method(&mut self, rhs: Self) {
+                self.bytes.iter_mut()
+                    .zip(rhs.bytes)
+                    .for_each(|(a, b)| *a = *a $op b);
+            }
+        }
+
+        impl<const BYTES: usize> ops::$trait for BitArray<BYTES> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                ops::$assign_trait::$assign_method(&mut self, rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_array_binop!(&, BitAnd bitand, BitAndAssign bitand_assign);
+impl_array_binop!(|, BitOr bitor, BitOrAssign bitor_assign);
+impl_array_binop!(^, BitXor bitxor, BitXorAssign bitxor_assign);
+
+impl<const BYTES: usize> ops::Not for BitArray<BYTES> {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.bytes.iter_mut().for_each(|b| *b = !*b);
+        self
+    }
+}
+
+/// Serializes as a string of `0`s and `1`s in human-readable formats,
+/// and as the packed bytes otherwise.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use core::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+
+    use super::BitArray;
+
+    struct BitString<'a, const BYTES: usize>(&'a BitArray<BYTES>);
+
+    impl<const BYTES: usize> fmt::Display for BitString<'_, BYTES> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))
+        }
+    }
+
+    impl<const BYTES: usize> Serialize for BitArray<BYTES> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&BitString(self))
+            } else {
+                serializer.serialize_bytes(&self.bytes)
+            }
+        }
+    }
+
+    struct BitArrayVisitor<const BYTES: usize>;
+
+    impl<'de, const BYTES: usize> Visitor<'de> for BitArrayVisitor<BYTES> {
+        type Value = BitArray<BYTES>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a string of {} 0s and 1s, or {BYTES} bytes", BYTES * 8)
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<BitArray<BYTES>, E> {
+            if s.len() != BYTES * 8 {
+                return Err(E::invalid_length(s.len(), &self));
+            }
+            let mut array = BitArray::new();
+            for (idx, b) in s.bytes().enumerate() {
+                match b {
+                    b'0' => {}
+                    b'1' => array.set(true, idx),
+                    _ => return Err(E::invalid_value(de::Unexpected::Str(s), &self)),
+                }
+            }
+            Ok(array)
+        }
+
+        fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<BitArray<BYTES>, E> {
+            bytes.try_into()
+                .map(BitArray::from_bytes)
+                .map_err(|_| E::invalid_length(bytes.len(), &self))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BitArray<BYTES>, A::Error> {
+            let mut bytes = [0; BYTES];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(de::Error::invalid_length(BYTES + 1, &self));
+            }
+            Ok(BitArray::from_bytes(bytes))
+        }
+    }
+
+    impl<'de, const BYTES: usize> Deserialize<'de> for BitArray<BYTES> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(BitArrayVisitor)
+            } else {
+                deserializer.deserialize_bytes(BitArrayVisitor)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde::de::value::{BytesDeserializer, Error, StrDeserializer};
+        use serde::de::{Deserialize, IntoDeserializer};
+
+        use super::BitArray;
+
+        #[test]
+        fn deserialize() {
+            let de: StrDeserializer<'_, Error> = "0100000011000000".into_deserializer();
+            assert_eq!(BitArray::<2>::deserialize(de).unwrap().to_bytes(), [0b10, 0b11]);
+            let de: StrDeserializer<'_, Error> = "0101".into_deserializer();
+            assert!(BitArray::<2>::deserialize(de).is_err());
+
+            let bytes = [0xd4, 0x01];
+            let array = BitArray::<2>::deserialize(BytesDeserializer::<Error>::new(&bytes)).unwrap();
+            assert_eq!(array.to_bytes(), bytes);
+            assert!(BitArray::<3>::deserialize(BytesDeserializer::<Error>::new(&bytes)).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitArray;
+    use crate::BitSlice;
+
+    #[test]
+    fn get_set() {
+        let mut array = BitArray::<4>::new();
+        assert_eq!(array.len(), 32);
+        array.set(true, 31);
+        array.toggle(9);
+        assert!(array.get(31) && array.get(9));
+        assert_eq!(array.try_get(32), None);
+        assert_eq!(array.to_bytes(), [0, 0b10, 0, 0x80]);
+        assert!(array.any() && !array.all());
+        array.fill(true);
+        assert!(array.all());
+        assert_eq!(array.count_true(), 32);
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        let a = BitArray::from_bytes([0b1100, 0xf0, 0x0f]);
+        let b = BitArray::from_bytes([0b1010, 0xff, 0x00]);
+        assert_eq!((a & b).to_bytes(), [0b1000, 0xf0, 0x00]);
+        assert_eq!((a | b).to_bytes(), [0b1110, 0xff, 0x0f]);
+        assert_eq!((a ^ b).to_bytes(), [0b0110, 0x0f, 0x0f]);
+        assert_eq!((!a).to_bytes(), [0xf3, 0x0f, 0xf0]);
+    }
+
+    #[test]
+    fn views() {
+        let bytes = [0xd4, 0x35, 0x7e];
+        let array = BitArray::<2>::from_bit_slice(BitSlice::new(&bytes, 4..20)).unwrap();
+        assert_eq!(array.to_bytes(), [0x5d, 0xe3]);
+        assert!(array.iter().eq(BitSlice::new(&bytes, 4..20)));
+        assert_eq!(BitArray::<2>::from_bit_slice(BitSlice::new(&bytes, 4..19)), None);
+        assert_eq!(BitSlice::from(&array), array.as_bit_slice());
+
+        let mut array = BitArray::<1>::new();
+        array.as_bit_slice_mut().slice_mut(2..5).fill(true);
+        assert_eq!(array.to_bytes(), [0b11100]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        BitArray::<2>::new().get(16);
+    }
+}
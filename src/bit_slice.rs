@@ -0,0 +1,1234 @@
+//! Views of a range of bits in a packed buffer.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{self, Bound, Range, RangeBounds};
+
+use crate::{slices, PackedBools8};
+
+/// A shared view of a range of bits in a packed buffer.
+///
+/// Bit `i` of the buffer is bit `i % 8` of byte `i / 8`,
+/// and the view can start and end partway through a byte.
+///
+/// ```
+/// use packed_booleans::BitSlice;
+///
+/// let bytes = [0b1010_0000, 0b0000_0011];
+/// let bits = BitSlice::new(&bytes, 5..10);
+/// assert_eq!(bits.len(), 5);
+/// assert_eq!(bits.get(0), Some(true));
+/// assert_eq!(bits.count_true(), 4);
+/// ```
+#[derive(Clone, Copy)]
+pub struct BitSlice<'a> {
+    /// The bytes holding the view, starting with the byte holding its first bit.
+    bytes: &'a [u8],
+    /// The index of the first bit in `bytes`, which is less than 8.
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Creates a view of the given range of bits in a buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds for the buffer.
+    pub fn new(bytes: &'a [u8], range: impl RangeBounds<usize>) -> Self {
+        let range = to_range(range, bytes.len() * 8);
+        Self::from_raw_parts(bytes, range.start, range.len())
+    }
+
+    /// Creates a view of every bit in a buffer.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0, len: bytes.len() * 8 }
+    }
+
+    /// Creates a view of `len` bits starting at bit `offset`,
+    /// which must already be known to be in bounds.
+    pub(crate) fn from_raw_parts(bytes: &'a [u8], offset: usize, len: usize) -> Self {
+        let bytes = &bytes[offset / 8..(offset + len).div_ceil(8).max(offset / 8)];
+        Self { bytes, offset: offset % 8, len }
+    }
+
+    /// Returns the number of bits in the view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the view has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes holding the view, and the index of its first bit in them.
+    ///
+    /// The bits of the first and last bytes outside the view are included.
+    pub fn as_raw_parts(&self) -> (&'a [u8], usize) {
+        (self.bytes, self.offset)
+    }
+
+    /// Gets the bit at the given index, or `None` if it is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        (idx < self.len).then(|| slices::get_bit(self.bytes, (self.offset + idx) as u64))
+    }
+
+    /// Counts how many bits are true.
+    pub fn count_true(&self) -> usize {
+        if self.len == 0 {
+            return 0;
+        }
+        let end = self.offset + self.len;
+        let last = self.bytes.len() - 1;
+        let head_mask = 0xff << self.offset;
+        let tail_mask = 0xff >> (7 - (end - 1) % 8);
+        if last == 0 {
+            return (self.bytes[0] & head_mask & tail_mask).count_ones() as usize;
+        }
+        slices::count_true(&self.bytes[1..last]) as usize
+            + (self.bytes[0] & head_mask).count_ones() as usize
+            + (self.bytes[last] & tail_mask).count_ones() as usize
+    }
+
+    /// Returns a view of the given range of these bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let range = to_range(range, self.len);
+        self.sub(range.start, range.len())
+    }
+
+    /// Returns a view of the given range of these bits,
+    /// or `None` if the range is out of bounds.
+    pub fn get_slice(&self, range: impl RangeBounds<usize>) -> Option<Self> {
+        try_range(range, self.len).map(|range| self.sub(range.start, range.len()))
+    }
+
+    /// Returns a view of the given range of these bits,
+    /// or an error saying why the range is out of bounds.
+    pub fn slice_checked(&self, range: impl RangeBounds<usize>) -> Result<Self, BitRangeError> {
+        checked_range(range, self.len).map(|range| self.sub(range.start, range.len()))
+    }
+
+    /// Reads the bits in the given range as an integer, with the first bit as the lowest bit.
+    ///
+    /// This returns an error if the range is out of bounds or has more than 64 bits.
+    ///
+    /// ```
+    /// use packed_booleans::BitSlice;
+    ///
+    /// let packet = [0b1011_0000, 0b0000_0010];
+    /// let bits = BitSlice::from_bytes(&packet);
+    /// assert_eq!(bits.get_range_checked(4..10), Ok(0b10_1011));
+    /// assert!(bits.get_range_checked(12..20).is_err());
+    /// ```
+    pub fn get_range_checked(&self, range: impl RangeBounds<usize>) -> Result<u64, BitRangeError> {
+        let range = checked_range(range, self.len)?;
+        match range.len() {
+            0..=64 => Ok(self.load(range.start, range.len() as u8)),
+            _ => Err(BitRangeError::new(range.start, range.end, self.len)),
+        }
+    }
+
+    /// Splits the view in two at the given index,
+    /// so that the first view has `mid` bits.
+    ///
+    /// ```
+    /// use packed_booleans::BitSlice;
+    ///
+    /// let bytes = [0b1101_0110];
+    /// let (kind, rest) = BitSlice::from_bytes(&bytes).split_at(3);
+    /// assert_eq!(kind.count_true(), 2);
+    /// assert_eq!(rest.len(), 5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length.
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        self.split_at_checked(mid).expect("The split index cannot be greater than the length")
+    }
+
+    /// Splits the view in two at the given index,
+    /// or returns `None` if `mid` is greater than the length.
+    pub fn split_at_checked(&self, mid: usize) -> Option<(Self, Self)> {
+        (mid <= self.len).then(|| (self.sub(0, mid), self.sub(mid, self.len - mid)))
+    }
+
+    /// Splits the view into the bits before the first byte boundary, the whole bytes after it,
+    /// and the bits left over at the end, like [`slice::align_to`].
+    ///
+    /// The head and tail have fewer than 8 bits each,
+    /// so algorithms can handle them bit by bit and work on the body a byte or word at a time.
+    ///
+    /// ```
+    /// use packed_booleans::BitSlice;
+    ///
+    /// let bytes = [0b1110_0000, 0xab, 0xcd, 0b0000_0101];
+    /// let (head, body, tail) = BitSlice::new(&bytes, 5..27).split_aligned();
+    /// assert_eq!(head.count_true(), 3);
+    /// assert_eq!(body, [0xab, 0xcd]);
+    /// assert_eq!(tail.len(), 3);
+    /// ```
+    pub fn split_aligned(&self) -> (Self, &'a [u8], Self) {
+        let head_len = if self.offset == 0 { 0 } else { (8 - self.offset).min(self.len) };
+        let body_len = (self.len - head_len) / 8;
+        let start = usize::from(self.offset != 0);
+        let body = &self.bytes[start.min(self.bytes.len())..][..body_len];
+        let tail_start = head_len + body_len * 8;
+        (self.sub(0, head_len), body, self.sub(tail_start, self.len - tail_start))
+    }
+
+    /// Returns an iterator over the bits.
+    pub fn iter(&self) -> BitSliceIter<'a> {
+        BitSliceIter { bits: *self, range: 0..self.len }
+    }
+
+    /// Returns an iterator over the indices of the true bits, in increasing order.
+    ///
+    /// This reads 64 bits at a time, skipping over runs of false bits quickly.
+    pub fn iter_ones(&self) -> BitPositions<'a> {
+        BitPositions { bits: *self, next_word: 0, word: 0, base: 0, invert: false }
+    }
+
+    /// Returns an iterator over the indices of the false bits, in increasing order.
+    ///
+    /// This reads 64 bits at a time, skipping over runs of true bits quickly.
+    pub fn iter_zeros(&self) -> BitPositions<'a> {
+        BitPositions { bits: *self, next_word: 0, word: 0, base: 0, invert: true }
+    }
+
+    /// Returns an iterator over views of `n` bits at a time, starting from the first bit.
+    /// The last view is shorter if the length isn't a multiple of `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn chunks(&self, n: usize) -> BitChunks<'a> {
+        assert!(n != 0, "The chunk size cannot be 0");
+        BitChunks { bits: *self, size: n }
+    }
+
+    /// Returns an iterator over every view of `n` consecutive bits, overlapping each other.
+    /// There are none if the length is less than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn windows(&self, n: usize) -> BitWindows<'a> {
+        assert!(n != 0, "The window size cannot be 0");
+        BitWindows { bits: *self, size: n, range: 0..(self.len + 1).saturating_sub(n) }
+    }
+
+    /// Returns an iterator over the bits 8 at a time, as `PackedBools8` values.
+    ///
+    /// The last `len % 8` bits aren't yielded, and are available from [`PackedChunks8::remainder`].
+    ///
+    /// ```
+    /// use packed_booleans::BitSlice;
+    ///
+    /// let bytes = [0b1010_0000, 0b1111_0011];
+    /// let mut chunks = BitSlice::new(&bytes, 4..15).chunks_packed8();
+    /// assert_eq!(chunks.next().map(|pkd| pkd.to_bits()), Some(0b0011_1010));
+    /// assert_eq!(chunks.next(), None);
+    /// assert_eq!(chunks.remainder().len(), 3);
+    /// ```
+    pub fn chunks_packed8(&self) -> PackedChunks8<'a> {
+        let full = self.len - self.len % 8;
+        PackedChunks8 { bits: self.sub(0, full), remainder: self.sub(full, self.len % 8) }
+    }
+
+    /// Returns a view of `len` bits starting at `start`,
+    /// which must already be known to be in bounds.
+    fn sub(&self, start: usize, len: usize) -> Self {
+        Self::from_raw_parts(self.bytes, self.offset + start, len)
+    }
+
+    /// Reads `n` bits, up to 64, starting at the given index.
+    fn load(&self, start: usize, n: u8) -> u64 {
+        slices::load_bits(self.bytes, (self.offset + start) as u64, n)
+    }
+
+    /// Copies the bits into a new `PackedBoolVec`.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> crate::PackedBoolVec {
+        let mut bytes = alloc::vec![0; self.len.div_ceil(8)];
+        slices::copy_bits(&mut bytes, 0, self.bytes, self.offset as u64, self.len as u64);
+        crate::PackedBoolVec::from_bytes(&bytes, self.len)
+    }
+}
+
+impl PartialEq for BitSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BitSlice<'_> {}
+
+impl fmt::Debug for BitSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitSlice(")?;
+        self.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))?;
+        f.write_str(")")
+    }
+}
+
+impl<'a> IntoIterator for BitSlice<'a> {
+    type Item = bool;
+    type IntoIter = BitSliceIter<'a>;
+
+    fn into_iter(self) -> BitSliceIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the bits in a [`BitSlice`].
+#[derive(Debug, Clone)]
+pub struct BitSliceIter<'a> {
+    bits: BitSlice<'a>,
+    range: Range<usize>,
+}
+
+impl Iterator for BitSliceIter<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().and_then(|idx| self.bits.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<bool> {
+        self.range.nth(n).and_then(|idx| self.bits.get(idx))
+    }
+}
+
+impl DoubleEndedIterator for BitSliceIter<'_> {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bits.get(idx))
+    }
+}
+
+impl ExactSizeIterator for BitSliceIter<'_> {}
+
+impl FusedIterator for BitSliceIter<'_> {}
+
+/// An iterator over the indices of the true (or false) bits in a [`BitSlice`].
+///
+/// This is created by [`BitSlice::iter_ones`] and [`BitSlice::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct BitPositions<'a> {
+    bits: BitSlice<'a>,
+    /// The index of the first bit that hasn't been read yet.
+    next_word: usize,
+    /// The bits that haven't been yielded yet from the last read, already inverted if needed.
+    word: u64,
+    /// The index of the first bit of the last read.
+    base: usize,
+    invert: bool,
+}
+
+impl Iterator for BitPositions<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            if self.next_word >= self.bits.len {
+                return None;
+            }
+            let n = (self.bits.len - self.next_word).min(64) as u8;
+            let mask = u64::MAX >> (64 - n);
+            self.word = self.bits.load(self.next_word, n) ^ if self.invert { mask } else { 0 };
+            self.base = self.next_word;
+            self.next_word += usize::from(n);
+        }
+        let idx = self.base + self.word.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.word &= self.word - 1;
+        Some(idx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.word.count_ones() as usize;
+        (left, Some(left + self.bits.len - self.next_word))
+    }
+}
+
+impl FusedIterator for BitPositions<'_> {}
+
+/// An iterator over non-overlapping views of a [`BitSlice`].
+///
+/// This is created by [`BitSlice::chunks`].
+#[derive(Debug, Clone)]
+pub struct BitChunks<'a> {
+    /// The bits that haven't been yielded yet.
+    bits: BitSlice<'a>,
+    size: usize,
+}
+
+impl<'a> Iterator for BitChunks<'a> {
+    type Item = BitSlice<'a>;
+
+    fn next(&mut self) -> Option<BitSlice<'a>> {
+        if self.bits.is_empty() {
+            return None;
+        }
+        let n = self.size.min(self.bits.len);
+        let chunk = self.bits.sub(0, n);
+        self.bits = self.bits.sub(n, self.bits.len - n);
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len.div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BitChunks<'a> {
+    fn next_back(&mut self) -> Option<BitSlice<'a>> {
+        if self.bits.is_empty() {
+            return None;
+        }
+        // the last chunk is the short one
+        let n = match self.bits.len % self.size {
+            0 => self.size,
+            rem => rem,
+        };
+        let start = self.bits.len - n;
+        let chunk = self.bits.sub(start, n);
+        self.bits = self.bits.sub(0, start);
+        Some(chunk)
+    }
+}
+
+impl ExactSizeIterator for BitChunks<'_> {}
+
+impl FusedIterator for BitChunks<'_> {}
+
+/// An iterator over the bits of a [`BitSlice`] 8 at a time, as `PackedBools8` values.
+///
+/// This is created by [`BitSlice::chunks_packed8`].
+#[derive(Debug, Clone)]
+pub struct PackedChunks8<'a> {
+    /// The whole chunks that haven't been yielded yet.
+    bits: BitSlice<'a>,
+    remainder: BitSlice<'a>,
+}
+
+impl<'a> PackedChunks8<'a> {
+    /// Returns the last bits, which don't make up a whole chunk.
+    pub fn remainder(&self) -> BitSlice<'a> {
+        self.remainder
+    }
+}
+
+impl Iterator for PackedChunks8<'_> {
+    type Item = PackedBools8;
+
+    fn next(&mut self) -> Option<PackedBools8> {
+        if self.bits.is_empty() {
+            return None;
+        }
+        let chunk = PackedBools8::from_bits(self.bits.load(0, 8) as u8);
+        self.bits = self.bits.sub(8, self.bits.len - 8);
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.len / 8;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<PackedBools8> {
+        let skip = n.saturating_mul(8).min(self.bits.len);
+        self.bits = self.bits.sub(skip, self.bits.len - skip);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for PackedChunks8<'_> {
+    fn next_back(&mut self) -> Option<PackedBools8> {
+        if self.bits.is_empty() {
+            return None;
+        }
+        let start = self.bits.len - 8;
+        let chunk = PackedBools8::from_bits(self.bits.load(start, 8) as u8);
+        self.bits = self.bits.sub(0, start);
+        Some(chunk)
+    }
+}
+
+impl ExactSizeIterator for PackedChunks8<'_> {}
+
+impl FusedIterator for PackedChunks8<'_> {}
+
+/// An iterator over overlapping views of a [`BitSlice`].
+///
+/// This is created by [`BitSlice::windows`].
+#[derive(Debug, Clone)]
+pub struct BitWindows<'a> {
+    bits: BitSlice<'a>,
+    size: usize,
+    /// The starting indices of the windows that haven't been yielded yet.
+    range: Range<usize>,
+}
+
+impl<'a> Iterator for BitWindows<'a> {
+    type Item = BitSlice<'a>;
+
+    fn next(&mut self) -> Option<BitSlice<'a>> {
+        self.range.next().map(|start| self.bits.sub(start, self.size))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<BitSlice<'a>> {
+        self.range.nth(n).map(|start| self.bits.sub(start, self.size))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BitWindows<'a> {
+    fn next_back(&mut self) -> Option<BitSlice<'a>> {
+        self.range.next_back().map(|start| self.bits.sub(start, self.size))
+    }
+}
+
+impl ExactSizeIterator for BitWindows<'_> {}
+
+impl FusedIterator for BitWindows<'_> {}
+
+/// A mutable view of a range of bits in a packed buffer.
+///
+/// Writes through the view only change the bits in its range,
+/// even where it starts or ends partway through a byte.
+///
+/// ```
+/// use packed_booleans::BitSliceMut;
+///
+/// let mut bytes = [0; 2];
+/// let mut bits = BitSliceMut::new(&mut bytes, 6..12);
+/// bits.set(true, 0);
+/// bits.set(true, 5);
+/// assert_eq!(bytes, [0b0100_0000, 0b0000_1000]);
+/// ```
+pub struct BitSliceMut<'a> {
+    /// The bytes holding the view, starting with the byte holding its first bit.
+    bytes: &'a mut [u8],
+    /// The index of the first bit in `bytes`, which is less than 8.
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> BitSliceMut<'a> {
+    /// Creates a mutable view of the given range of bits in a buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds for the buffer.
+    pub fn new(bytes: &'a mut [u8], range: impl RangeBounds<usize>) -> Self {
+        let range = to_range(range, bytes.len() * 8);
+        Self::from_raw_parts(bytes, range.start, range.len())
+    }
+
+    /// Creates a mutable view of every bit in a buffer.
+    pub fn from_bytes(bytes: &'a mut [u8]) -> Self {
+        let len = bytes.len() * 8;
+        Self { bytes, offset: 0, len }
+    }
+
+    /// Creates a mutable view of `len` bits starting at bit `offset`,
+    /// which must already be known to be in bounds.
+    pub(crate) fn from_raw_parts(bytes: &'a mut [u8], offset: usize, len: usize) -> Self {
+        let bytes = &mut bytes[offset / 8..(offset + len).div_ceil(8).max(offset / 8)];
+        Self { bytes, offset: offset % 8, len }
+    }
+
+    /// Returns the number of bits in the view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the view has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a shared view of the same bits.
+    pub fn as_bit_slice(&self) -> BitSlice<'_> {
+        BitSlice { bytes: self.bytes, offset: self.offset, len: self.len }
+    }
+
+    /// Returns a mutable view of the given range of these bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> BitSliceMut<'_> {
+        let range = to_range(range, self.len);
+        BitSliceMut::from_raw_parts(self.bytes, self.offset + range.start, range.len())
+    }
+
+    /// Returns a mutable view of the given range of these bits,
+    /// or `None` if the range is out of bounds.
+    pub fn get_slice_mut(&mut self, range: impl RangeBounds<usize>) -> Option<BitSliceMut<'_>> {
+        let range = try_range(range, self.len)?;
+        Some(BitSliceMut::from_raw_parts(self.bytes, self.offset + range.start, range.len()))
+    }
+
+    /// Returns a mutable view of the given range of these bits,
+    /// or an error saying why the range is out of bounds.
+    pub fn slice_mut_checked(&mut self, range: impl RangeBounds<usize>) -> Result<BitSliceMut<'_>, BitRangeError> {
+        let range = checked_range(range, self.len)?;
+        Ok(BitSliceMut::from_raw_parts(self.bytes, self.offset + range.start, range.len()))
+    }
+
+    /// Returns a shorter-lived mutable view of the same bits.
+    pub fn reborrow(&mut self) -> BitSliceMut<'_> {
+        BitSliceMut { bytes: self.bytes, offset: self.offset, len: self.len }
+    }
+
+    /// Gets the bit at the given index, or `None` if it is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        self.as_bit_slice().get(idx)
+    }
+
+    /// Sets the bit at the given index to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, val: bool, idx: usize) {
+        self.check_index(idx);
+        slices::set_bit(self.bytes, val, (self.offset + idx) as u64);
+    }
+
+    /// Toggles the bit at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn toggle(&mut self, idx: usize) {
+        self.check_index(idx);
+        slices::toggle_bit(self.bytes, (self.offset + idx) as u64);
+    }
+
+    /// Reads the bits in the given range as an integer, with the first bit as the lowest bit.
+    ///
+    /// This returns an error if the range is out of bounds or has more than 64 bits.
+    pub fn get_range_checked(&self, range: impl RangeBounds<usize>) -> Result<u64, BitRangeError> {
+        self.as_bit_slice().get_range_checked(range)
+    }
+
+    /// Writes the low bits of `val` to the given range, with the lowest bit going to the first index.
+    /// The bits of `val` that don't fit are ignored.
+    ///
+    /// This returns an error, leaving the bits unchanged,
+    /// if the range is out of bounds or has more than 64 bits.
+    ///
+    /// ```
+    /// use packed_booleans::BitSliceMut;
+    ///
+    /// let mut header = [0; 2];
+    /// let mut bits = BitSliceMut::from_bytes(&mut header);
+    /// bits.set_range_checked(4..10, 0b10_1011).unwrap();
+    /// assert_eq!(header, [0b1011_0000, 0b0000_0010]);
+    /// ```
+    pub fn set_range_checked(&mut self, range: impl RangeBounds<usize>, val: u64) -> Result<(), BitRangeError> {
+        let range = checked_range(range, self.len)?;
+        if range.len() > 64 {
+            return Err(BitRangeError::new(range.start, range.end, self.len));
+        }
+        slices::store_bits(self.bytes, (self.offset + range.start) as u64, range.len() as u8, val);
+        Ok(())
+    }
+
+    /// Counts how many bits are true.
+    pub fn count_true(&self) -> usize {
+        self.as_bit_slice().count_true()
+    }
+
+    /// Sets every bit to val.
+    ///
+    /// The whole bytes in the view are filled at once,
+    /// and only the partial bytes at the ends are masked.
+    pub fn fill(&mut self, val: bool) {
+        if self.len == 0 {
+            return;
+        }
+        let fill = if val { 0xff } else { 0 };
+        let end = self.offset + self.len;
+        let last = self.bytes.len() - 1;
+        let head_mask = 0xff << self.offset;
+        let tail_mask = 0xff >> (7 - (end - 1) % 8);
+        let set_masked = |byte: &mut u8, mask: u8| *byte = (*byte & !mask) | (fill & mask);
+        if last == 0 {
+            set_masked(&mut self.bytes[0], head_mask & tail_mask);
+            return;
+        }
+        set_masked(&mut self.bytes[0], head_mask);
+        set_masked(&mut self.bytes[last], tail_mask);
+        self.bytes[1..last].fill(fill);
+    }
+
+    /// Sets every bit in the given range to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, val: bool) {
+        self.slice_mut(range).fill(val);
+    }
+
+    /// Copies every bit from `src`.
+    ///
+    /// The two views don't need to start at the same position within a byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the views have different lengths.
+    pub fn copy_from_bitslice(&mut self, src: BitSlice<'_>) {
+        assert_eq!(self.len, src.len, "The views must have the same length");
+        slices::copy_bits(self.bytes, self.offset as u64, src.bytes, src.offset as u64, self.len as u64);
+    }
+
+    fn check_index(&self, idx: usize) {
+        assert!(idx < self.len, "The index {idx} is out of bounds for a length of {}", self.len);
+    }
+
+    /// Combines the bits of `src` into these bits with `op`, 64 bits at a time.
+    ///
+    /// The two views don't need to start at the same position within a byte,
+    /// as each read is shifted to line up with the destination.
+    fn combine(&mut self, src: BitSlice<'_>, op: fn(u64, u64) -> u64) {
+        assert_eq!(self.len, src.len, "The views must have the same length");
+        let mut done = 0;
+        while done < self.len {
+            let n = (self.len - done).min(64) as u8;
+            let dst_idx = (self.offset + done) as u64;
+            let val = op(slices::load_bits(self.bytes, dst_idx, n), src.load(done, n));
+            slices::store_bits(self.bytes, dst_idx, n, val);
+            done += usize::from(n);
+        }
+    }
+}
+
+macro_rules! impl_view_assign_op {
+    ($op:tt, $trait:ident $method:ident) => {
+        /// Combines each bit with the bit at the same index of the other view.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the views have different lengths.
+        impl ops::$trait<BitSlice<'_>> for BitSliceMut<'_> {
+            fn $method(&mut self, rhs: BitSlice<'_>) {
+                self.combine(rhs, |a, b| a $op b);
+            }
+        }
+
+        /// Combines each bit with the bit at the same index of the other view.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the views have different lengths.
+        impl ops::$trait<&BitSliceMut<'_>> for BitSliceMut<'_> {
+            fn $method(&mut self, rhs: &BitSliceMut<'_>) {
+                self.combine(rhs.as_bit_slice(), |a, b| a $op b);
+            }
+        }
+    };
+}
+
+impl_view_assign_op!(&, BitAndAssign bitand_assign);
+impl_view_assign_op!(|, BitOrAssign bitor_assign);
+impl_view_assign_op!(^, BitXorAssign bitxor_assign);
+
+impl<'a> From<BitSliceMut<'a>> for BitSlice<'a> {
+    fn from(bits: BitSliceMut<'a>) -> Self {
+        BitSlice { bytes: bits.bytes, offset: bits.offset, len: bits.len }
+    }
+}
+
+impl fmt::Debug for BitSliceMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitSliceMut(")?;
+        self.as_bit_slice().iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))?;
+        f.write_str(")")
+    }
+}
+
+/// The error returned when a range of bits is out of bounds for a view,
+/// or is too long to be read or written as an integer.
+///
+/// ```
+/// use packed_booleans::BitSlice;
+///
+/// let bytes = [0; 2];
+/// let err = BitSlice::from_bytes(&bytes).slice_checked(4..20).unwrap_err();
+/// assert_eq!((err.start(), err.end(), err.len()), (4, 20, 16));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitRangeError {
+    start: usize,
+    end: usize,
+    len: usize,
+}
+
+impl BitRangeError {
+    const fn new(start: usize, end: usize, len: usize) -> Self {
+        Self { start, end, len }
+    }
+
+    /// Returns the start of the range.
+    ///
+    /// A start that overflowed is `usize::MAX`.
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end of the range, after its last index.
+    ///
+    /// An end that overflowed is `usize::MAX`.
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the length of the view.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for BitRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { start, end, len } = self;
+        if start > end {
+            write!(f, "the range starts at {start} but ends at {end}")
+        } else if end > len {
+            write!(f, "the range ends at {end}, which is out of bounds for a length of {len}")
+        } else {
+            write!(f, "the range {start}..{end} has more than 64 bits")
+        }
+    }
+}
+
+impl core::error::Error for BitRangeError {}
+
+/// Turns a range of bit indices into a `Range`,
+/// or returns an error if it is out of bounds for `len` bits or ends before it starts.
+fn checked_range(range: impl RangeBounds<usize>, len: usize) -> Result<Range<usize>, BitRangeError> {
+    let (start, end) = bounds(&range, len);
+    let (start, end) = (start.unwrap_or(usize::MAX), end.unwrap_or(usize::MAX));
+    match start <= end && end <= len {
+        true => Ok(start..end),
+        false => Err(BitRangeError::new(start, end, len)),
+    }
+}
+
+/// Turns a range of bit indices into a `Range`.
+///
+/// # Panics
+///
+/// Panics if the range is out of bounds for `len` bits or ends before it starts.
+pub(crate) fn to_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let (start, end) = bounds(&range, len);
+    let (start, end) = (start.expect("The range start overflowed"), end.expect("The range end overflowed"));
+    assert!(start <= end, "The range starts at {start} but ends at {end}");
+    assert!(end <= len, "The range ends at {end}, which is out of bounds for a length of {len}");
+    start..end
+}
+
+/// Turns a range of bit indices into a `Range`,
+/// or returns `None` if it is out of bounds for `len` bits or ends before it starts.
+pub(crate) fn try_range(range: impl RangeBounds<usize>, len: usize) -> Option<Range<usize>> {
+    match bounds(&range, len) {
+        (Some(start), Some(end)) if start <= end && end <= len => Some(start..end),
+        _ => None,
+    }
+}
+
+/// Returns the start and end of a range, or `None` for either if it overflows.
+fn bounds(range: &impl RangeBounds<usize>, len: usize) -> (Option<usize>, Option<usize>) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => start.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1),
+        Bound::Excluded(&end) => Some(end),
+        Bound::Unbounded => Some(len),
+    };
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Bound;
+
+    use super::{BitRangeError, BitSlice, BitSliceMut};
+
+    const BYTES: [u8; 4] = [0b1011_0110, 0xff, 0x00, 0b0101_0101];
+
+    #[test]
+    fn views() {
+        for start in 0..32 {
+            for end in start..=32 {
+                let bits = BitSlice::new(&BYTES, start..end);
+                assert_eq!(bits.len(), end - start);
+                let expected = (start..end).filter(|&i| BYTES[i / 8] & (1 << (i % 8)) != 0).count();
+                assert_eq!(bits.count_true(), expected, "{start}..{end}");
+                for idx in 0..bits.len() {
+                    assert_eq!(bits.get(idx), Some(BYTES[(start + idx) / 8] & (1 << ((start + idx) % 8)) != 0));
+                }
+                assert_eq!(bits.get(bits.len()), None);
+            }
+        }
+        assert_eq!(BitSlice::new(&BYTES, 8..16), BitSlice::new(&[0xff], ..));
+        assert_eq!(BitSlice::from_bytes(&BYTES), BitSlice::new(&BYTES, ..));
+    }
+
+    #[test]
+    fn iterators() {
+        let bytes: [u8; 20] = core::array::from_fn(|i| (i as u8).wrapping_mul(0x9d) ^ 0x0f);
+        let bit = |i: usize| bytes[i / 8] & (1 << (i % 8)) != 0;
+        for range in [0..160, 3..150, 64..128, 7..9, 10..10] {
+            let bits = BitSlice::new(&bytes, range.clone());
+            assert!(bits.iter().eq(range.clone().map(bit)));
+            assert!(bits.iter().rev().eq(range.clone().rev().map(bit)));
+            assert!(bits.iter_ones().eq(range.clone().filter(|&i| bit(i)).map(|i| i - range.start)));
+            assert!(bits.iter_zeros().eq(range.clone().filter(|&i| !bit(i)).map(|i| i - range.start)));
+
+            for n in [1, 3, 8, 64, 200] {
+                let chunks = bits.chunks(n);
+                assert_eq!(chunks.len(), bits.len().div_ceil(n));
+                assert!(chunks.clone().flatten().eq(bits.iter()));
+                assert_eq!(chunks.clone().next_back(), chunks.clone().nth(chunks.len().saturating_sub(1)));
+                assert!(chunks.clone().rev().skip(1).all(|chunk| chunk.len() == n));
+
+                let windows = bits.windows(n);
+                assert_eq!(windows.len(), (bits.len() + 1).saturating_sub(n));
+                for (start, window) in windows.enumerate() {
+                    assert_eq!(window.len(), n);
+                    assert!(window.iter().eq((start..start + n).map(|i| bits.get(i).unwrap())));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packed_chunks() {
+        for range in [0..32, 3..30, 8..24, 5..12, 9..9] {
+            let bits = BitSlice::new(&BYTES, range.clone());
+            let chunks = bits.chunks_packed8();
+            assert_eq!(chunks.len(), bits.len() / 8);
+            assert_eq!(chunks.remainder().len(), bits.len() % 8);
+            assert!(chunks.clone().flatten().chain(chunks.remainder()).eq(bits.iter()));
+            assert!(chunks.clone().rev().eq(bits.chunks(8).rev().filter(|c| c.len() == 8).map(|c| c.chunks_packed8().next().unwrap())));
+        }
+        let mut chunks = BitSlice::new(&BYTES, 1..).chunks_packed8();
+        assert_eq!(chunks.nth(2).map(|pkd| pkd.to_bits()), Some(0b1000_0000));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), BitSlice::new(&BYTES, 25..));
+    }
+
+    #[test]
+    fn sparse_ones() {
+        let mut bytes = [0; 64];
+        bytes[40] = 0b0001_0000;
+        let bits = BitSlice::from_bytes(&bytes);
+        assert!(bits.iter_ones().eq([324]));
+        assert_eq!(bits.iter_zeros().count(), 511);
+    }
+
+    #[test]
+    fn mutable_views() {
+        let mut bytes = BYTES;
+        let mut bits = BitSliceMut::new(&mut bytes, 4..=19);
+        assert_eq!(bits.len(), 16);
+        bits.set(false, 0);
+        bits.toggle(15);
+        assert_eq!(bits.count_true(), 11);
+        let shared: BitSlice<'_> = bits.into();
+        assert_eq!(shared.get(15), Some(true));
+        assert_eq!(bytes, [0b1010_0110, 0xff, 0b0000_1000, 0b0101_0101]);
+    }
+
+    #[test]
+    fn subslices() {
+        let bits = BitSlice::new(&BYTES, 3..29);
+        let sub = bits.slice(4..=10);
+        assert_eq!(sub.len(), 7);
+        assert!(sub.iter().eq((7..14).map(|i| BYTES[i / 8] & (1 << (i % 8)) != 0)));
+        assert_eq!(bits.get_slice(20..27), None);
+        assert_eq!(bits.get_slice(26..), Some(BitSlice::new(&[], ..)));
+
+        for mid in 0..=26 {
+            let (head, tail) = bits.split_at(mid);
+            assert_eq!((head.len(), tail.len()), (mid, 26 - mid));
+            assert!(head.iter().chain(tail).eq(bits.iter()));
+        }
+        assert_eq!(bits.split_at_checked(27), None);
+        assert_eq!(bits.slice_checked(4..=10), Ok(sub));
+        assert_eq!(bits.slice_checked(20..27), Err(BitRangeError::new(20, 27, 26)));
+        assert_eq!(bits.slice_checked((Bound::Included(5), Bound::Excluded(2))), Err(BitRangeError::new(5, 2, 26)));
+        assert_eq!(bits.slice_checked(..=usize::MAX), Err(BitRangeError::new(0, usize::MAX, 26)));
+
+        for start in 0..32 {
+            for end in start..=32 {
+                let bits = BitSlice::new(&BYTES, start..end);
+                let (head, body, tail) = bits.split_aligned();
+                assert!(head.len() < 8 && tail.len() < 8, "{start}..{end}");
+                assert!((start + head.len()).is_multiple_of(8) || body.is_empty() && tail.is_empty());
+                let body = BitSlice::from_bytes(body);
+                assert!(head.iter().chain(body).chain(tail).eq(bits.iter()), "{start}..{end}");
+            }
+        }
+
+        let mut bytes = [0; 3];
+        let mut view = BitSliceMut::new(&mut bytes, 2..22);
+        view.slice_mut(6..).set(true, 0);
+        assert!(view.get_slice_mut(..21).is_none());
+        view.get_slice_mut(19..).unwrap().toggle(0);
+        assert_eq!(bytes, [0, 0b1, 0b0010_0000]);
+    }
+
+    #[test]
+    fn fill_and_copy() {
+        for start in [0, 1, 8, 13] {
+            for len in [0, 1, 3, 8, 20] {
+                for val in [false, true] {
+                    let mut bytes = [0b0101_1010; 5];
+                    let before = bytes;
+                    BitSliceMut::new(&mut bytes, 2..).fill_range(start..start + len, val);
+                    for i in 0..40 {
+                        let expected = if (start + 2..start + 2 + len).contains(&i) {
+                            val
+                        } else {
+                            before[i / 8] & (1 << (i % 8)) != 0
+                        };
+                        assert_eq!(bytes[i / 8] & (1 << (i % 8)) != 0, expected, "{start} {len} {val} {i}");
+                    }
+                }
+            }
+        }
+
+        let mut bytes = [0; 4];
+        let mut view = BitSliceMut::new(&mut bytes, 5..26);
+        view.copy_from_bitslice(BitSlice::new(&BYTES, 1..22));
+        assert_eq!(view.as_bit_slice(), BitSlice::new(&BYTES, 1..22));
+        view.fill(true);
+        assert_eq!(bytes, [0b1110_0000, 0xff, 0xff, 0b11]);
+    }
+
+    type ApplyFn = fn(&mut BitSliceMut<'_>, BitSlice<'_>);
+    type BoolOp = fn(bool, bool) -> bool;
+
+    #[test]
+    fn misaligned_ops() {
+        let src: [u8; 24] = core::array::from_fn(|i| (i as u8).wrapping_mul(0x3b) ^ 0xa5);
+        let bit = |bytes: &[u8], i: usize| bytes[i / 8] & (1 << (i % 8)) != 0;
+        for src_start in [0, 3, 8, 13] {
+            for dst_start in [0, 5, 8, 63] {
+                for len in [0, 1, 7, 64, 100] {
+                    let ops: [(ApplyFn, BoolOp); 3] = [
+                        (|d, s| *d &= s, |a, b| a & b),
+                        (|d, s| *d |= s, |a, b| a | b),
+                        (|d, s| *d ^= s, |a, b| a ^ b),
+                    ];
+                    for (apply, op) in ops {
+                        let mut dst = [0b0110_1001u8; 24];
+                        let before = dst;
+                        apply(
+                            &mut BitSliceMut::new(&mut dst, dst_start..dst_start + len),
+                            BitSlice::new(&src, src_start..src_start + len),
+                        );
+                        for i in 0..dst.len() * 8 {
+                            let expected = match i.checked_sub(dst_start).filter(|&j| j < len) {
+                                Some(j) => op(bit(&before, i), bit(&src, src_start + j)),
+                                None => bit(&before, i),
+                            };
+                            assert_eq!(bit(&dst, i), expected, "{src_start} {dst_start} {len} {i}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_ops() {
+        let mut dst = [0; 2];
+        let mut bits = BitSliceMut::new(&mut dst, 0..9);
+        bits |= BitSlice::new(&[0; 2], 0..8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_range() {
+        BitSlice::new(&BYTES, 30..33);
+    }
+
+    #[test]
+    fn checked_ranges() {
+        let mut bytes = [0; 12];
+        let mut view = BitSliceMut::new(&mut bytes, 3..90);
+        for (range, val) in [(0..0, 0), (0..5, 0b10110), (7..21, 0x2b3c), (20..84, 0xdead_beef_0bad_f00d)] {
+            view.set_range_checked(range.clone(), val).unwrap();
+            assert_eq!(view.get_range_checked(range.clone()), Ok(val));
+            assert!(range.clone().all(|i| view.get(i) == Some(val >> (i - range.start) & 1 != 0)));
+        }
+        // the bits that don't fit are ignored
+        view.set_range_checked(84..87, 0xff).unwrap();
+        assert_eq!(view.get_range_checked(80..), Ok(0b111_1101));
+
+        let before = bytes;
+        let mut view = BitSliceMut::new(&mut bytes, 3..90);
+        assert_eq!(view.set_range_checked(80..88, 0), Err(BitRangeError::new(80, 88, 87)));
+        assert_eq!(view.set_range_checked(0..65, 0), Err(BitRangeError::new(0, 65, 87)));
+        assert_eq!(view.get_range_checked((Bound::Included(10), Bound::Excluded(9))), Err(BitRangeError::new(10, 9, 87)));
+        assert!(view.slice_mut_checked(86..88).is_err());
+        view.slice_mut_checked(86..).unwrap().toggle(0);
+        assert_eq!(bytes[..11], before[..11]);
+        assert_ne!(bytes, before);
+    }
+
+    #[test]
+    fn range_error_messages() {
+        extern crate alloc;
+        use alloc::string::ToString;
+
+        assert_eq!(BitRangeError::new(5, 2, 8).to_string(), "the range starts at 5 but ends at 2");
+        assert_eq!(BitRangeError::new(2, 9, 8).to_string(), "the range ends at 9, which is out of bounds for a length of 8");
+        assert_eq!(BitRangeError::new(0, 65, 80).to_string(), "the range 0..65 has more than 64 bits");
+    }
+
+    #[test]
+    fn range_error_is_error() {
+        let bytes = [0; 2];
+        let err = BitSlice::new(&bytes, 0..16).slice_checked(4..20).unwrap_err();
+        let err: &dyn core::error::Error = &err;
+        assert!(err.source().is_none());
+        assert!(err.is::<BitRangeError>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        let mut bytes = [0; 2];
+        BitSliceMut::new(&mut bytes, 3..5).set(true, 2);
+    }
+}
+
+/// Views serialize the same way as a `PackedBoolVec`, so they can be deserialized into one.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use core::fmt;
+
+    use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+
+    use super::{BitSlice, BitSliceMut};
+
+    impl Serialize for BitSlice<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&BitString(*self))
+            } else {
+                let mut state = serializer.serialize_struct("PackedBoolVec", 2)?;
+                state.serialize_field("len", &(self.len as u64))?;
+                state.serialize_field("bytes", &Bytes(*self))?;
+                state.end()
+            }
+        }
+    }
+
+    impl Serialize for BitSliceMut<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.as_bit_slice().serialize(serializer)
+        }
+    }
+
+    struct BitString<'a>(BitSlice<'a>);
+
+    impl fmt::Display for BitString<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))
+        }
+    }
+
+    /// Serializes the bits of a view as packed bytes, starting from its first bit.
+    struct Bytes<'a>(BitSlice<'a>);
+
+    impl Bytes<'_> {
+        /// Returns the bytes of the view without copying them,
+        /// if the view starts at the start of a byte and the unused bits of its last byte are false.
+        fn borrowed(&self) -> Option<&[u8]> {
+            let BitSlice { bytes, offset, len } = self.0;
+            let clean = len % 8 == 0 || bytes[len / 8] >> (len % 8) == 0;
+            (offset == 0 && clean).then_some(bytes)
+        }
+
+        fn realigned(&self) -> impl Iterator<Item = u8> + '_ {
+            let bits = self.0;
+            (0..bits.len).step_by(8).map(move |start| bits.load(start, (bits.len - start).min(8) as u8) as u8)
+        }
+    }
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if let Some(bytes) = self.borrowed() {
+                return serializer.serialize_bytes(bytes);
+            }
+            // a sequence of bytes is read back the same way as bytes, and doesn't need a buffer
+            let mut seq = serializer.serialize_seq(Some(self.0.len.div_ceil(8)))?;
+            self.realigned().try_for_each(|byte| seq.serialize_element(&byte))?;
+            seq.end()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        extern crate alloc;
+        use alloc::{format, vec::Vec};
+
+        use super::{BitString, Bytes};
+        use crate::BitSlice;
+
+        #[test]
+        fn bytes() {
+            let buf = [0b1011_0110, 0b0000_0101, 0xff];
+            let aligned = Bytes(BitSlice::new(&buf, ..11));
+            assert_eq!(aligned.borrowed(), Some(&buf[..2]));
+            assert_eq!(Bytes(BitSlice::new(&buf, ..10)).borrowed(), None);
+            assert_eq!(Bytes(BitSlice::new(&buf, ..0)).borrowed(), Some(&[][..]));
+
+            let shifted = Bytes(BitSlice::new(&buf, 1..13));
+            assert_eq!(shifted.borrowed(), None);
+            assert_eq!(shifted.realigned().collect::<Vec<_>>(), [0b1101_1011, 0b10]);
+            assert_eq!(format!("{}", BitString(BitSlice::new(&buf, 1..6))), "11011");
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn into_owned() {
+            use serde::de::value::{Error, StrDeserializer};
+            use serde::de::{Deserialize, IntoDeserializer};
+
+            let buf = [0b1011_0110, 0b0000_0101];
+            let bits = BitSlice::new(&buf, 3..14);
+            let text = format!("{}", BitString(bits));
+            let de: StrDeserializer<'_, Error> = text.as_str().into_deserializer();
+            assert_eq!(crate::PackedBoolVec::deserialize(de).unwrap(), bits.to_vec());
+        }
+    }
+}
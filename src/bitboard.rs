@@ -0,0 +1,235 @@
+//! Utilities for using a `PackedBools64` as an 8x8 board.
+//!
+//! The board is laid out rank by rank, so square `(file, rank)` is at index `rank * 8 + file`.
+//! File 0 is the "west" edge and rank 0 is the "south" edge,
+//! so index 0 is the south-west corner and index 63 is the north-east corner.
+//!
+//! The shifts move every square one step in a direction,
+//! dropping the squares that move off the board.
+
+use crate::PackedBools64;
+
+/// All the squares on file 0 (the west edge).
+pub const FILE_A: PackedBools64 = PackedBools64::from_bits(0x0101_0101_0101_0101);
+/// All the squares on file 7 (the east edge).
+pub const FILE_H: PackedBools64 = PackedBools64::from_bits(0x8080_8080_8080_8080);
+/// All the squares on rank 0 (the south edge).
+pub const RANK_1: PackedBools64 = PackedBools64::from_bits(0x0000_0000_0000_00ff);
+/// All the squares on rank 7 (the north edge).
+pub const RANK_8: PackedBools64 = PackedBools64::from_bits(0xff00_0000_0000_0000);
+
+const NOT_FILE_A: u64 = !FILE_A.to_bits();
+const NOT_FILE_H: u64 = !FILE_H.to_bits();
+
+/// Returns the index of the square on the given file and rank.
+///
+/// # Panics
+///
+/// Panics if the file or rank is greater than 7.
+pub const fn square(file: u8, rank: u8) -> u8 {
+    assert!(file < 8 && rank < 8, "The file and rank cannot be greater than 7");
+    rank * 8 + file
+}
+
+/// Returns all the squares on the given file.
+///
+/// # Panics
+///
+/// Panics if the file is greater than 7.
+pub const fn file_mask(file: u8) -> PackedBools64 {
+    assert!(file < 8, "The file cannot be greater than 7");
+    PackedBools64::from_bits(FILE_A.to_bits() << file)
+}
+
+/// Returns all the squares on the given rank.
+///
+/// # Panics
+///
+/// Panics if the rank is greater than 7.
+pub const fn rank_mask(rank: u8) -> PackedBools64 {
+    assert!(rank < 8, "The rank cannot be greater than 7");
+    PackedBools64::from_bits(RANK_1.to_bits() << (rank * 8))
+}
+
+/// Moves every square one rank north.
+pub const fn north(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits(board.to_bits() << 8)
+}
+
+/// Moves every square one rank south.
+pub const fn south(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits(board.to_bits() >> 8)
+}
+
+/// Moves every square one file east.
+pub const fn east(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() << 1) & NOT_FILE_A)
+}
+
+/// Moves every square one file west.
+pub const fn west(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() >> 1) & NOT_FILE_H)
+}
+
+/// Moves every square one step north-east.
+pub const fn north_east(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() << 9) & NOT_FILE_A)
+}
+
+/// Moves every square one step north-west.
+pub const fn north_west(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() << 7) & NOT_FILE_H)
+}
+
+/// Moves every square one step south-east.
+pub const fn south_east(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() >> 7) & NOT_FILE_A)
+}
+
+/// Moves every square one step south-west.
+pub const fn south_west(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits((board.to_bits() >> 9) & NOT_FILE_H)
+}
+
+/// Returns the squares reachable from `seed` by steps north, south, east and west
+/// without leaving the squares in `mask`.
+///
+/// The seed squares outside the mask are ignored.
+pub const fn flood_fill(seed: PackedBools64, mask: PackedBools64) -> PackedBools64 {
+    let mut fill = PackedBools64::from_bits(seed.to_bits() & mask.to_bits());
+    loop {
+        let grown = fill.to_bits() | north(fill).to_bits() | south(fill).to_bits() | east(fill).to_bits() | west(fill).to_bits();
+        let next = PackedBools64::from_bits(grown & mask.to_bits());
+        if next.to_bits() == fill.to_bits() {
+            return fill;
+        }
+        fill = next;
+    }
+}
+
+/// Mirrors the board across the horizontal center line, swapping rank 0 with rank 7.
+pub const fn flip_vertical(board: PackedBools64) -> PackedBools64 {
+    PackedBools64::from_bits(board.to_bits().swap_bytes())
+}
+
+/// Mirrors the board across the vertical center line, swapping file 0 with file 7.
+pub const fn mirror_horizontal(board: PackedBools64) -> PackedBools64 {
+    const K1: u64 = 0x5555_5555_5555_5555;
+    const K2: u64 = 0x3333_3333_3333_3333;
+    const K4: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+    // reverse the bits of every byte, by swapping bits, then pairs, then nibbles
+    let mut x = board.to_bits();
+    x = ((x >> 1) & K1) | ((x & K1) << 1);
+    x = ((x >> 2) & K2) | ((x & K2) << 2);
+    x = ((x >> 4) & K4) | ((x & K4) << 4);
+    PackedBools64::from_bits(x)
+}
+
+/// Mirrors the board across the diagonal from square 0 to square 63,
+/// swapping `(file, rank)` with `(rank, file)`.
+pub const fn flip_diagonal(board: PackedBools64) -> PackedBools64 {
+    const K1: u64 = 0x5500_5500_5500_5500;
+    const K2: u64 = 0x3333_0000_3333_0000;
+    const K4: u64 = 0x0f0f_0f0f_0000_0000;
+    // swap the off-diagonal 4x4 blocks, then 2x2 blocks, then single squares
+    let mut x = board.to_bits();
+    let mut t = K4 & (x ^ (x << 28));
+    x ^= t ^ (t >> 28);
+    t = K2 & (x ^ (x << 14));
+    x ^= t ^ (t >> 14);
+    t = K1 & (x ^ (x << 7));
+    x ^= t ^ (t >> 7);
+    PackedBools64::from_bits(x)
+}
+
+/// Mirrors the board across the diagonal from square 7 to square 56,
+/// swapping `(file, rank)` with `(7 - rank, 7 - file)`.
+pub const fn flip_anti_diagonal(board: PackedBools64) -> PackedBools64 {
+    const K1: u64 = 0xaa00_aa00_aa00_aa00;
+    const K2: u64 = 0xcccc_0000_cccc_0000;
+    const K4: u64 = 0xf0f0_f0f0_0f0f_0f0f;
+    // swap the off-diagonal 4x4 blocks, then 2x2 blocks, then single squares
+    let mut x = board.to_bits();
+    let mut t = x ^ (x << 36);
+    x ^= K4 & (t ^ (x >> 36));
+    t = K2 & (x ^ (x << 18));
+    x ^= t ^ (t >> 18);
+    t = K1 & (x ^ (x << 9));
+    x ^= t ^ (t >> 9);
+    PackedBools64::from_bits(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a board by mapping every square to the square it comes from.
+    fn remap(board: PackedBools64, from: impl Fn(u8, u8) -> Option<(u8, u8)>) -> PackedBools64 {
+        let mut out = PackedBools64::new();
+        for rank in 0..8 {
+            for file in 0..8 {
+                if let Some((f, r)) = from(file, rank) {
+                    out.set(board.get(square(f, r)), square(file, rank));
+                }
+            }
+        }
+        out
+    }
+
+    fn boards() -> impl Iterator<Item = PackedBools64> {
+        [0, u64::MAX, 0x0123_4567_89ab_cdef, 0x8100_0000_0000_0081, 0x0000_0018_1800_0000, 1 << 9 | 1 << 46]
+            .into_iter()
+            .map(PackedBools64::from_bits)
+    }
+
+    #[test]
+    fn masks() {
+        assert_eq!(file_mask(0), FILE_A);
+        assert_eq!(file_mask(7), FILE_H);
+        assert_eq!(rank_mask(0), RANK_1);
+        assert_eq!(rank_mask(7), RANK_8);
+        assert_eq!(square(3, 2), 19);
+        assert!(file_mask(5).get(square(5, 6)));
+        assert!(!rank_mask(5).get(square(5, 6)));
+    }
+
+    #[test]
+    fn shifts() {
+        let step = |df: i8, dr: i8| move |file: u8, rank: u8| {
+            let (f, r) = (file as i8 - df, rank as i8 - dr);
+            ((0..8).contains(&f) && (0..8).contains(&r)).then_some((f as u8, r as u8))
+        };
+        for board in boards() {
+            assert_eq!(north(board), remap(board, step(0, 1)));
+            assert_eq!(south(board), remap(board, step(0, -1)));
+            assert_eq!(east(board), remap(board, step(1, 0)));
+            assert_eq!(west(board), remap(board, step(-1, 0)));
+            assert_eq!(north_east(board), remap(board, step(1, 1)));
+            assert_eq!(north_west(board), remap(board, step(-1, 1)));
+            assert_eq!(south_east(board), remap(board, step(1, -1)));
+            assert_eq!(south_west(board), remap(board, step(-1, -1)));
+        }
+    }
+
+    #[test]
+    fn flood() {
+        // a wall along file 3 with a gap on rank 7
+        let wall = file_mask(3) & !rank_mask(7);
+        let open = !wall;
+        let left = flood_fill(PackedBools64::from_bits(1), open & (file_mask(0) | file_mask(1) | file_mask(2)));
+        assert_eq!(left.count_true(), 24);
+        assert_eq!(flood_fill(PackedBools64::from_bits(1), open), open);
+        assert_eq!(flood_fill(PackedBools64::from_bits(1), wall), PackedBools64::new());
+        assert_eq!(flood_fill(PackedBools64::from_bits(1 << 63), RANK_8 | FILE_A).count_true(), 15);
+    }
+
+    #[test]
+    fn mirrors() {
+        for board in boards() {
+            assert_eq!(flip_vertical(board), remap(board, |f, r| Some((f, 7 - r))));
+            assert_eq!(mirror_horizontal(board), remap(board, |f, r| Some((7 - f, r))));
+            assert_eq!(flip_diagonal(board), remap(board, |f, r| Some((r, f))));
+            assert_eq!(flip_anti_diagonal(board), remap(board, |f, r| Some((7 - r, 7 - f))));
+        }
+    }
+}
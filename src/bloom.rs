@@ -0,0 +1,158 @@
+//! A small Bloom filter stored in packed bits.
+
+/// A Bloom filter with `BYTES * 8` bits of packed storage.
+///
+/// The filter doesn't hash items itself.
+/// Instead, every operation takes the hashes of an item,
+/// which should be the same number of independent hashes each time.
+/// Each hash sets (or checks) the bit at `hash % (BYTES * 8)`.
+///
+/// ```
+/// use packed_booleans::BloomFilter;
+///
+/// // two cheap hashes of a small integer
+/// fn hashes(x: u64) -> [u64; 2] {
+///     [x.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32, x.wrapping_mul(0xc2b2_ae3d_27d4_eb4f) >> 32]
+/// }
+///
+/// let mut filter = BloomFilter::<16>::new();
+/// filter.insert(hashes(42));
+/// assert!(filter.maybe_contains(hashes(42)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BloomFilter<const BYTES: usize> {
+    bits: [u8; BYTES],
+}
+
+impl<const BYTES: usize> BloomFilter<BYTES> {
+    const BITS: u64 = BYTES as u64 * 8;
+
+    /// Creates a new, empty `BloomFilter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics at compile time if `BYTES` is 0.
+    pub const fn new() -> Self {
+        const { assert!(BYTES > 0, "A BloomFilter needs at least one byte") };
+        Self { bits: [0; BYTES] }
+    }
+
+    /// Adds an item to the filter, given its hashes.
+    pub fn insert(&mut self, hashes: impl IntoIterator<Item = u64>) {
+        for hash in hashes {
+            let (byte, bit) = Self::position(hash);
+            self.bits[byte] |= 1 << bit;
+        }
+    }
+
+    /// Checks whether an item may have been added to the filter, given its hashes.
+    ///
+    /// This never returns false for an item that was added,
+    /// but may return true for an item that wasn't.
+    pub fn maybe_contains(&self, hashes: impl IntoIterator<Item = u64>) -> bool {
+        hashes.into_iter().all(|hash| {
+            let (byte, bit) = Self::position(hash);
+            self.bits[byte] & (1 << bit) != 0
+        })
+    }
+
+    /// Adds every item in `other` to this filter.
+    pub fn union(&mut self, other: &Self) {
+        self.bits.iter_mut()
+            .zip(other.bits)
+            .for_each(|(a, b)| *a |= b);
+    }
+
+    /// Removes every item from the filter.
+    pub fn clear(&mut self) {
+        self.bits = [0; BYTES];
+    }
+
+    /// Returns true if no items have been added to the filter.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&b| b == 0)
+    }
+
+    /// Counts how many bits of the filter are set.
+    pub fn count_true(&self) -> u64 {
+        self.bits.iter().map(|b| u64::from(b.count_ones())).sum()
+    }
+
+    /// Estimates the chance of `maybe_contains` returning true for an item that wasn't added,
+    /// when items are checked with `hash_count` hashes.
+    ///
+    /// This is the chance of all of those hashes landing on set bits,
+    /// based on how many bits are currently set.
+    pub fn false_positive_rate(&self, hash_count: u32) -> f64 {
+        let fill = self.count_true() as f64 / Self::BITS as f64;
+        (0..hash_count).fold(1.0, |rate, _| rate * fill)
+    }
+
+    /// Returns the packed bits of the filter.
+    pub fn as_bytes(&self) -> &[u8; BYTES] {
+        &self.bits
+    }
+
+    fn position(hash: u64) -> (usize, u8) {
+        let idx = hash % Self::BITS;
+        ((idx / 8) as usize, (idx % 8) as u8)
+    }
+}
+
+impl<const BYTES: usize> Default for BloomFilter<BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BloomFilter;
+
+    fn hashes(x: u64) -> [u64; 3] {
+        [
+            x.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 29,
+            x.wrapping_mul(0xc2b2_ae3d_27d4_eb4f) >> 29,
+            x.wrapping_mul(0x1656_67b1_9e37_79f9) >> 29,
+        ]
+    }
+
+    #[test]
+    fn no_false_negatives() {
+        let mut filter = BloomFilter::<32>::new();
+        assert!(filter.is_empty());
+        for x in 0..20 {
+            filter.insert(hashes(x));
+        }
+        for x in 0..20 {
+            assert!(filter.maybe_contains(hashes(x)));
+        }
+        assert!(filter.count_true() <= 60);
+
+        filter.clear();
+        assert!(filter.is_empty());
+        assert!(!filter.maybe_contains(hashes(3)));
+    }
+
+    #[test]
+    fn hash_positions() {
+        let mut filter = BloomFilter::<2>::new();
+        filter.insert([0, 9, 16 + 15]);
+        assert_eq!(filter.as_bytes(), &[0b0000_0001, 0b1000_0010]);
+        assert!(filter.maybe_contains([15]));
+        assert!(!filter.maybe_contains([15, 1]));
+    }
+
+    #[test]
+    fn union_and_rate() {
+        let mut a = BloomFilter::<1>::new();
+        let mut b = BloomFilter::<1>::new();
+        a.insert([0, 1]);
+        b.insert([2, 3]);
+        a.union(&b);
+        assert_eq!(a.as_bytes(), &[0b1111]);
+        assert_eq!(a.false_positive_rate(1), 0.5);
+        assert_eq!(a.false_positive_rate(3), 0.125);
+        assert_eq!(BloomFilter::<4>::default().false_positive_rate(2), 0.0);
+    }
+}
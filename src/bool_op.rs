@@ -0,0 +1,99 @@
+//! Two-input boolean functions chosen at runtime.
+
+/// A boolean function of two inputs, stored as a 4-bit truth table.
+///
+/// The result for inputs `a` and `b` is bit `(a << 1) | b` of the table,
+/// so every one of the 16 two-input functions has a table from `0b0000` to `0b1111`.
+///
+/// ```
+/// use packed_booleans::{BinaryBoolOp, PackedBools8};
+///
+/// let a = PackedBools8::from_bits(0b1100);
+/// let b = PackedBools8::from_bits(0b1010);
+/// assert_eq!(a.apply_binary_op(b, BinaryBoolOp::IMPLIES), 0b1111_1011);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BinaryBoolOp(u8);
+
+impl BinaryBoolOp {
+    /// Always false.
+    pub const FALSE: Self = Self(0b0000);
+    /// `!(a | b)`
+    pub const NOR: Self = Self(0b0001);
+    /// `!a & b`
+    pub const NOT_A_AND_B: Self = Self(0b0010);
+    /// `!a`
+    pub const NOT_A: Self = Self(0b0011);
+    /// `a & !b`
+    pub const A_AND_NOT_B: Self = Self(0b0100);
+    /// `!b`
+    pub const NOT_B: Self = Self(0b0101);
+    /// `a ^ b`
+    pub const XOR: Self = Self(0b0110);
+    /// `!(a & b)`
+    pub const NAND: Self = Self(0b0111);
+    /// `a & b`
+    pub const AND: Self = Self(0b1000);
+    /// `!(a ^ b)`
+    pub const XNOR: Self = Self(0b1001);
+    /// `b`
+    pub const B: Self = Self(0b1010);
+    /// `!a | b`, which is true unless `a` is true and `b` is false.
+    pub const IMPLIES: Self = Self(0b1011);
+    /// `a`
+    pub const A: Self = Self(0b1100);
+    /// `a | !b`, which is true unless `b` is true and `a` is false.
+    pub const IMPLIED_BY: Self = Self(0b1101);
+    /// `a | b`
+    pub const OR: Self = Self(0b1110);
+    /// Always true.
+    pub const TRUE: Self = Self(0b1111);
+
+    /// Creates a function from its truth table,
+    /// if the table is less than 16.
+    pub const fn from_table(table: u8) -> Option<Self> {
+        if table < 16 {
+            Some(Self(table))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the truth table of the function.
+    pub const fn table(self) -> u8 {
+        self.0
+    }
+
+    /// Applies the function to a pair of booleans.
+    pub const fn apply(self, a: bool, b: bool) -> bool {
+        (self.0 >> (((a as u8) << 1) | b as u8)) & 1 != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryBoolOp;
+
+    type BoolFn = fn(bool, bool) -> bool;
+
+    #[test]
+    fn tables() {
+        let ops: [(BinaryBoolOp, BoolFn); 8] = [
+            (BinaryBoolOp::NOR, |a, b| !(a | b)),
+            (BinaryBoolOp::NOT_A_AND_B, |a, b| !a & b),
+            (BinaryBoolOp::A_AND_NOT_B, |a, b| a & !b),
+            (BinaryBoolOp::XOR, |a, b| a ^ b),
+            (BinaryBoolOp::NAND, |a, b| !(a & b)),
+            (BinaryBoolOp::AND, |a, b| a & b),
+            (BinaryBoolOp::IMPLIES, |a, b| !a | b),
+            (BinaryBoolOp::IMPLIED_BY, |a, b| a | !b),
+        ];
+        for (op, f) in ops {
+            for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+                assert_eq!(op.apply(a, b), f(a, b), "{op:?} {a} {b}");
+            }
+        }
+        assert_eq!(BinaryBoolOp::from_table(0b1110), Some(BinaryBoolOp::OR));
+        assert_eq!(BinaryBoolOp::from_table(16), None);
+    }
+}
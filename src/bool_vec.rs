@@ -0,0 +1,1335 @@
+//! A growable, heap-allocated list of packed booleans.
+
+use alloc::vec::Vec;
+use core::{fmt, hash::Hash, iter::FusedIterator, mem::size_of, ops::{self, RangeBounds}, slice};
+
+use crate::bit_slice::to_range;
+use crate::rank_select::Blocks;
+use crate::{slices, BitPositions, BitSlice, BitSliceMut, PackedBools8, PackedBools16, PackedChunks8};
+
+/// An unsigned integer that a [`PackedBoolVec`] can store its booleans in.
+///
+/// This is implemented for `u8`, `u16`, `u32`, `u64` and `usize`.
+/// Wider words let counting, filling, selecting and the bitwise operators
+/// work on more booleans at a time.
+pub trait Word:
+    Copy + Eq + Default + Hash + fmt::Debug + Send + Sync
+    + ops::BitAnd<Output = Self> + ops::BitOr<Output = Self> + ops::BitXor<Output = Self> + ops::Not<Output = Self>
+    + crate::typed_flags::private::Sealed
+{
+    /// The number of booleans in a word.
+    const BITS: usize;
+
+    /// A word of false values.
+    const ZERO: Self;
+
+    /// Returns how many true values are in the word.
+    fn count_ones(self) -> u32;
+
+    /// Converts a word from little-endian byte order to the target's byte order.
+    fn from_le(word: Self) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($word:ident),*) => {
+        $(
+            impl crate::typed_flags::private::Sealed for $word {}
+
+            impl Word for $word {
+                const BITS: usize = <$word>::BITS as usize;
+                const ZERO: Self = 0;
+
+                fn count_ones(self) -> u32 {
+                    <$word>::count_ones(self)
+                }
+
+                fn from_le(word: Self) -> Self {
+                    <$word>::from_le(word)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, usize);
+
+/// A growable list of booleans, packed into words of type `W`.
+///
+/// Boolean `i` is bit `i % 8` of byte `i / 8`, like the buffers in [`slices`](crate::slices),
+/// whatever the word type is, so the storage can always be viewed as packed bytes.
+/// The unused bits of the last word are always false.
+///
+/// The default word is `u8`. A wider [`Word`], like `u64`, makes counting, filling, selecting and the bitwise operators
+/// handle more booleans at a time, at the cost of rounding the storage up to whole words.
+/// Like `HashMap::new`, the constructors and the `From` conversions are only for `u8` words,
+/// so `PackedBoolVec::new()` doesn't need the word type spelled out.
+/// Vectors of other words start from [`default`](Default::default) or are collected from an iterator.
+///
+/// ```
+/// use packed_booleans::PackedBoolVec;
+///
+/// let mut vec = PackedBoolVec::new();
+/// vec.push(true);
+/// vec.push(false);
+/// vec.push(true);
+/// assert_eq!(vec.len(), 3);
+/// assert_eq!(vec.as_bytes(), &[0b101]);
+/// assert_eq!(vec.pop(), Some(true));
+///
+/// let mut words = PackedBoolVec::<u64>::default();
+/// words.resize(100, true);
+/// words.set(false, 70);
+/// assert_eq!(words.count_true(), 99);
+/// assert_eq!(words.as_bytes().len(), 13);
+/// assert_eq!(words.into_parts(), (vec![u64::MAX, (1 << 36) - 1 - (1 << 6)], 100));
+/// ```
+#[derive(Clone, Default)]
+pub struct PackedBoolVec<W: Word = u8> {
+    /// The booleans, with the bytes of each word in little-endian order,
+    /// so the words can be viewed as packed bytes on any target.
+    /// Only operations that treat every bit the same, like counting and the bitwise operators,
+    /// work on whole words; the rest go through the bytes.
+    words: Vec<W>,
+    len: usize,
+    /// The rank/select index, if it has been built since the last change.
+    index: Option<Blocks>,
+}
+
+impl PackedBoolVec {
+    /// Creates a new, empty `PackedBoolVec`.
+    pub const fn new() -> Self {
+        Self { words: Vec::new(), len: 0, index: None }
+    }
+
+    /// Creates a new, empty `PackedBoolVec` with room for at least `capacity` booleans.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Creates a `PackedBoolVec` of `len` copies of `val`.
+    pub fn repeat(val: bool, len: usize) -> Self {
+        let mut vec = Self::new();
+        vec.resize(len, val);
+        vec
+    }
+
+    /// Creates a `PackedBoolVec` holding the first `len` booleans of a packed buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than the number of bits in the buffer.
+    pub fn from_bytes(bytes: &[u8], len: usize) -> Self {
+        assert!(len <= bytes.len() * 8, "The length cannot be greater than the number of bits in the buffer");
+        let mut vec = Self::new();
+        vec.extend_from_bitslice(BitSlice::new(bytes, ..len));
+        vec
+    }
+}
+
+impl<W: Word> PackedBoolVec<W> {
+    /// Returns the number of booleans.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no booleans.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many booleans can be held without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.words.capacity() * W::BITS
+    }
+
+    /// Reserves room for at least `additional` more booleans.
+    pub fn reserve(&mut self, additional: usize) {
+        let words = self.len.saturating_add(additional).div_ceil(W::BITS);
+        self.words.reserve(words - self.words.len());
+    }
+
+    /// Returns the packed bytes. The unused bits of the last byte are false.
+    ///
+    /// The bytes of the unused part of the last word are left out.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes()[..self.len.div_ceil(8)]
+    }
+
+    /// Unwraps this `PackedBoolVec`, returning the words and the length.
+    ///
+    /// Boolean `i` is bit `i % W::BITS` of word `i / W::BITS`,
+    /// and the unused bits of the last word are false.
+    pub fn into_parts(self) -> (Vec<W>, usize) {
+        (self.words.into_iter().map(W::from_le).collect(), self.len)
+    }
+
+    /// Unpacks this `PackedBoolVec` into a `Vec` with one `bool` per value.
+    pub fn into_vec_bool(self) -> Vec<bool> {
+        let mut bools = alloc::vec![false; self.len];
+        slices::unpack_bools(self.as_bytes(), &mut bools);
+        bools
+    }
+
+    /// Gets the boolean at the given index, or `None` if it is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        (idx < self.len).then(|| slices::get_bit(self.bytes(), idx as u64))
+    }
+
+    /// Sets the boolean at the given index to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, val: bool, idx: usize) {
+        self.check_index(idx);
+        slices::set_bit(self.bytes_mut(), val, idx as u64);
+    }
+
+    /// Toggles the boolean at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn toggle(&mut self, idx: usize) {
+        self.check_index(idx);
+        slices::toggle_bit(self.bytes_mut(), idx as u64);
+    }
+
+    /// Adds a boolean to the end.
+    pub fn push(&mut self, val: bool) {
+        if self.len.is_multiple_of(W::BITS) {
+            self.words_mut().push(W::ZERO);
+        }
+        let idx = self.len as u64;
+        self.len += 1;
+        slices::set_bit(self.bytes_mut(), val, idx);
+    }
+
+    /// Adds the 8 booleans in a `PackedBools8` to the end, in index order.
+    pub fn push_packed8(&mut self, val: PackedBools8) {
+        self.push_bits(val.to_bits().into(), 8);
+    }
+
+    /// Adds the 16 booleans in a `PackedBools16` to the end, in index order.
+    pub fn push_packed16(&mut self, val: PackedBools16) {
+        self.push_bits(val.to_bits().into(), 16);
+    }
+
+    /// Removes the last boolean and returns it, or `None` if there are none.
+    pub fn pop(&mut self) -> Option<bool> {
+        let val = self.get(self.len.checked_sub(1)?)?;
+        self.truncate(self.len - 1);
+        Some(val)
+    }
+
+    /// Shortens the list to `len` booleans.
+    /// This does nothing if `len` is not less than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.set_len(len);
+            self.clear_unused();
+        }
+    }
+
+    /// Removes every boolean.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Changes the length to `len`,
+    /// either removing booleans from the end or adding copies of `val` to it.
+    pub fn resize(&mut self, len: usize, val: bool) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        let old_len = self.len;
+        let old_words = self.words.len();
+        self.set_len(len);
+        if val {
+            // fill the rest of the partial last word, then the new words
+            self.bits_mut(old_len..len.min(old_words * W::BITS)).fill(true);
+            self.words_mut()[old_words..].fill(!W::ZERO);
+            self.clear_unused();
+        }
+    }
+
+    /// Sets every boolean to val.
+    pub fn fill(&mut self, val: bool) {
+        self.words_mut().fill(if val { !W::ZERO } else { W::ZERO });
+        self.clear_unused();
+    }
+
+    /// Adds `additional` booleans to the end,
+    /// where `f` is given the index of each new boolean and returns its value.
+    pub fn grow_with(&mut self, additional: usize, mut f: impl FnMut(usize) -> bool) {
+        let start = self.len;
+        self.resize(start + additional, false);
+        let bytes = self.bytes_mut();
+        for idx in start..start + additional {
+            if f(idx) {
+                slices::set_bit(bytes, true, idx as u64);
+            }
+        }
+    }
+
+    /// Adds copies of the bits in a view to the end.
+    pub fn extend_from_bitslice(&mut self, bits: BitSlice<'_>) {
+        let (src, offset) = bits.as_raw_parts();
+        let start = self.len;
+        self.set_len(start + bits.len());
+        if start.is_multiple_of(8) && offset == 0 {
+            // every byte lines up, so they can be copied directly
+            let n = bits.len().div_ceil(8);
+            self.bytes_mut()[start / 8..][..n].copy_from_slice(&src[..n]);
+            self.clear_unused();
+            return;
+        }
+        slices::copy_bits(self.bytes_mut(), start as u64, src, offset as u64, bits.len() as u64);
+    }
+
+    /// Counts how many true values there are.
+    pub fn count_true(&self) -> usize {
+        count_words(&self.words)
+    }
+
+    /// Removes the given range of booleans, shifting the ones after it down,
+    /// and returns an iterator over the removed booleans.
+    ///
+    /// The booleans are removed straight away, even if the iterator isn't used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> BoolVecDrain {
+        self.splice(range, [])
+    }
+
+    /// Replaces the given range of booleans with the ones from `replace_with`,
+    /// shifting the booleans after it to fit,
+    /// and returns an iterator over the removed booleans.
+    ///
+    /// The booleans are replaced straight away, even if the iterator isn't used.
+    ///
+    /// ```
+    /// use packed_booleans::PackedBoolVec;
+    ///
+    /// let mut vec: PackedBoolVec = [true, false, false, true].into_iter().collect();
+    /// let removed: Vec<bool> = vec.splice(1..3, [true; 3]).collect();
+    /// assert_eq!(removed, [false, false]);
+    /// assert_eq!(vec, PackedBoolVec::repeat(true, 5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn splice(&mut self, range: impl RangeBounds<usize>, replace_with: impl IntoIterator<Item = bool>) -> BoolVecDrain {
+        let range = to_range(range, self.len);
+        let removed = self.bits(range.clone()).to_vec();
+        let replacement: PackedBoolVec = replace_with.into_iter().collect();
+        let old_len = self.len;
+        let tail_len = (old_len - range.end) as u64;
+        let new_len = old_len - range.len() + replacement.len;
+        let new_end = (range.start + replacement.len) as u64;
+        if new_len > old_len {
+            self.resize(new_len, false);
+        }
+        slices::copy_bits_within(self.bytes_mut(), range.end as u64, new_end, tail_len);
+        slices::copy_bits(self.bytes_mut(), range.start as u64, replacement.as_bytes(), 0, replacement.len as u64);
+        self.truncate(new_len);
+        BoolVecDrain { range: 0..removed.len, bits: removed }
+    }
+
+    /// Returns a view of the given range of booleans.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn bits(&self, range: impl RangeBounds<usize>) -> BitSlice<'_> {
+        let range = to_range(range, self.len);
+        BitSlice::from_raw_parts(self.bytes(), range.start, range.len())
+    }
+
+    /// Returns a mutable view of the given range of booleans.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn bits_mut(&mut self, range: impl RangeBounds<usize>) -> BitSliceMut<'_> {
+        let range = to_range(range, self.len);
+        BitSliceMut::from_raw_parts(self.bytes_mut(), range.start, range.len())
+    }
+
+    /// Builds the rank/select index, if it isn't already built.
+    ///
+    /// The index makes [`rank`](Self::rank) and [`select`](Self::select) much faster,
+    /// and is kept until the vector is next changed.
+    /// It takes an extra 8 bytes of memory for every 64 bytes of booleans.
+    pub fn build_index(&mut self) {
+        if self.index.is_none() {
+            self.index = Some(Blocks::new(self.as_bytes()));
+        }
+    }
+
+    /// Returns true if the rank/select index is built.
+    pub fn has_index(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Counts how many true values there are before the given index.
+    ///
+    /// This counts every word before the index unless the index has been built with
+    /// [`build_index`](Self::build_index).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the length.
+    pub fn rank(&self, idx: usize) -> usize {
+        assert!(idx <= self.len, "The index cannot be greater than the length");
+        match &self.index {
+            Some(index) => index.rank(self.as_bytes(), idx as u64) as usize,
+            None => {
+                // count the whole words, then the bytes of the partial word
+                let words = idx / W::BITS;
+                let bytes = &self.bytes()[words * size_of::<W>()..];
+                let rest = idx % W::BITS;
+                let partial = bytes.get(rest / 8).map_or(0, |&b| b & ((1 << (rest % 8)) - 1));
+                count_words(&self.words[..words]) + slices::count_true(&bytes[..rest / 8]) as usize + partial.count_ones() as usize
+            }
+        }
+    }
+
+    /// Returns the index of the `n`th true value, counting from 0,
+    /// if there are more than `n` true values.
+    ///
+    /// This scans the words unless the index has been built with
+    /// [`build_index`](Self::build_index).
+    pub fn select(&self, n: usize) -> Option<usize> {
+        if let Some(index) = &self.index {
+            return index.select(self.as_bytes(), n as u64).map(|idx| idx as usize);
+        }
+        let mut remaining = n;
+        for (i, &word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                let bytes = &self.bytes()[i * size_of::<W>()..][..size_of::<W>()];
+                return select_in_bytes(bytes, remaining).map(|idx| i * W::BITS + idx);
+            }
+            remaining -= ones;
+        }
+        None
+    }
+
+    /// Returns an iterator over the booleans.
+    pub fn iter(&self) -> BoolVecIter<'_, W> {
+        BoolVecIter { vec: self, range: 0..self.len }
+    }
+
+    /// Returns an iterator over the booleans, as proxies that can read and write them.
+    pub fn iter_mut(&mut self) -> slices::BitsMut<'_> {
+        let len = self.len;
+        slices::BitsMut::new(PackedBools8::slice_from_bytes_mut(self.bytes_mut()), 0..len)
+    }
+
+    /// Returns an iterator over the booleans 8 at a time, as `PackedBools8` values,
+    /// with the last `len % 8` available from [`PackedChunks8::remainder`].
+    pub fn chunks_packed8(&self) -> PackedChunks8<'_> {
+        self.bits(..).chunks_packed8()
+    }
+
+    /// Returns an iterator over the indices of the true values, in increasing order.
+    ///
+    /// This skips over whole words of false values at a time.
+    pub fn iter_ones(&self) -> BitPositions<'_> {
+        self.bits(..).iter_ones()
+    }
+
+    /// Returns an iterator over the indices of the false values, in increasing order.
+    ///
+    /// This skips over whole words of true values at a time.
+    pub fn iter_zeros(&self) -> BitPositions<'_> {
+        self.bits(..).iter_zeros()
+    }
+
+    /// Returns the words as packed bytes, including the unused bytes of the last word.
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: words are unsigned integers, so every byte of them is initialized,
+        // and u8 has no alignment requirement
+        unsafe { slice::from_raw_parts(self.words.as_ptr().cast(), self.words.len() * size_of::<W>()) }
+    }
+
+    /// Returns the words as packed bytes for changing them, dropping the now outdated rank/select index.
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.words.len() * size_of::<W>();
+        // SAFETY: as in `bytes`, and any bytes make valid words
+        unsafe { slice::from_raw_parts_mut(self.words_mut().as_mut_ptr().cast(), len) }
+    }
+
+    /// Returns the words for changing them, dropping the now outdated rank/select index.
+    fn words_mut(&mut self) -> &mut Vec<W> {
+        self.index = None;
+        &mut self.words
+    }
+
+    /// Changes the length to `len`, adding false words or removing words to fit.
+    ///
+    /// The unused bits of the last word are left as they were.
+    fn set_len(&mut self, len: usize) {
+        self.words_mut().resize(len.div_ceil(W::BITS), W::ZERO);
+        self.len = len;
+    }
+
+    /// Adds the lowest `n` bits of `bits` to the end, where the other bits are false.
+    fn push_bits(&mut self, mut bits: u64, n: usize) {
+        let start = self.len;
+        self.set_len(start + n);
+        let end = self.len.div_ceil(8);
+        let bytes = self.bytes_mut();
+        let mut pos = start / 8;
+        if !start.is_multiple_of(8) {
+            // fill the rest of the partial last byte first
+            bytes[pos] |= (bits << (start % 8)) as u8;
+            bits >>= 8 - start % 8;
+            pos += 1;
+        }
+        bytes[pos..end].copy_from_slice(&bits.to_le_bytes()[..end - pos]);
+    }
+
+    /// Sets the unused bits of the last word to false.
+    fn clear_unused(&mut self) {
+        let len = self.len;
+        let bytes = self.bytes_mut();
+        if !len.is_multiple_of(8) {
+            bytes[len / 8] &= (1 << (len % 8)) - 1;
+        }
+        bytes[len.div_ceil(8)..].fill(0);
+    }
+
+    fn check_index(&self, idx: usize) {
+        assert!(idx < self.len, "The index {idx} is out of bounds for a length of {}", self.len);
+    }
+
+    /// Combines `other` into this vector word by word with `op`,
+    /// first extending this vector with false values to the longer length.
+    fn combine(&mut self, other: &Self, op: fn(W, W) -> W) {
+        if other.len > self.len {
+            self.set_len(other.len);
+        }
+        let (head, tail) = self.words_mut().split_at_mut(other.words.len());
+        for (a, &b) in head.iter_mut().zip(&other.words) {
+            *a = op(*a, b);
+        }
+        // the rest of this vector is combined with false values
+        for a in tail {
+            *a = op(*a, W::ZERO);
+        }
+        self.clear_unused();
+    }
+}
+
+/// Counts the true values in the words.
+fn count_words<W: Word>(words: &[W]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+/// Returns the index of the `n`th true value in the bytes, counting from 0.
+fn select_in_bytes(bytes: &[u8], mut n: usize) -> Option<usize> {
+    for (i, &byte) in bytes.iter().enumerate() {
+        let ones = byte.count_ones() as usize;
+        if n < ones {
+            let mut byte = byte;
+            for _ in 0..n {
+                // clear the lowest set bit
+                byte &= byte - 1;
+            }
+            return Some(i * 8 + byte.trailing_zeros() as usize);
+        }
+        n -= ones;
+    }
+    None
+}
+
+// the index is only a cache, so it is ignored when comparing and hashing
+
+impl<W: Word> PartialEq for PackedBoolVec<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.words == other.words
+    }
+}
+
+impl<W: Word> Eq for PackedBoolVec<W> {}
+
+impl<W: Word> Hash for PackedBoolVec<W> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.words.hash(state);
+    }
+}
+
+impl<W: Word> fmt::Debug for PackedBoolVec<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PackedBoolVec(")?;
+        self.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))?;
+        f.write_str(")")
+    }
+}
+
+impl<W: Word> Extend<bool> for PackedBoolVec<W> {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|b| self.push(b));
+    }
+}
+
+impl<W: Word> Extend<PackedBools8> for PackedBoolVec<W> {
+    fn extend<I: IntoIterator<Item = PackedBools8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0.saturating_mul(8));
+        iter.for_each(|val| self.push_packed8(val));
+    }
+}
+
+impl<W: Word> FromIterator<bool> for PackedBoolVec<W> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut vec = Self::default();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<W: Word> IntoIterator for PackedBoolVec<W> {
+    type Item = bool;
+    type IntoIter = BoolVecIntoIter<W>;
+
+    fn into_iter(self) -> BoolVecIntoIter<W> {
+        BoolVecIntoIter { range: 0..self.len, bits: self }
+    }
+}
+
+impl<'a, W: Word> IntoIterator for &'a PackedBoolVec<W> {
+    type Item = bool;
+    type IntoIter = BoolVecIter<'a, W>;
+
+    fn into_iter(self) -> BoolVecIter<'a, W> {
+        self.iter()
+    }
+}
+
+impl<'a, W: Word> IntoIterator for &'a mut PackedBoolVec<W> {
+    type Item = slices::BitMut<'a>;
+    type IntoIter = slices::BitsMut<'a>;
+
+    fn into_iter(self) -> slices::BitsMut<'a> {
+        self.iter_mut()
+    }
+}
+
+impl From<&[bool]> for PackedBoolVec {
+    fn from(bools: &[bool]) -> Self {
+        let mut vec = Self::new();
+        vec.set_len(bools.len());
+        slices::pack_bools(bools, vec.bytes_mut());
+        vec
+    }
+}
+
+impl From<Vec<bool>> for PackedBoolVec {
+    fn from(bools: Vec<bool>) -> Self {
+        Self::from(bools.as_slice())
+    }
+}
+
+impl<W: Word> From<PackedBoolVec<W>> for Vec<bool> {
+    fn from(vec: PackedBoolVec<W>) -> Self {
+        vec.into_vec_bool()
+    }
+}
+
+macro_rules! impl_vec_binop {
+    ($op:tt, $trait:ident $method:ident, $assign_trait:ident $assign_method:ident) => {
+        impl<W: Word> ops::$assign_trait<&PackedBoolVec<W>> for PackedBoolVec<W> {
+            fn $assign_method(&mut self, rhs: &PackedBoolVec<W>) {
+                self.combine(rhs, |a, b| a $op b);
+            }
+        }
+
+        impl<W: Word> ops::$assign_trait for PackedBoolVec<W> {
+            fn $assign_method(&mut self, rhs: PackedBoolVec<W>) {
+                self.combine(&rhs, |a, b| a $op b);
+            }
+        }
+
+        impl<W: Word> ops::$trait<&PackedBoolVec<W>> for PackedBoolVec<W> {
+            type Output = PackedBoolVec<W>;
+
+            fn $method(mut self, rhs: &PackedBoolVec<W>) -> PackedBoolVec<W> {
+                self.combine(rhs, |a, b| a $op b);
+                self
+            }
+        }
+
+        impl<W: Word> ops::$trait for PackedBoolVec<W> {
+            type Output = PackedBoolVec<W>;
+
+            fn $method(mut self, rhs: PackedBoolVec<W>) -> PackedBoolVec<W> {
+                self.combine(&rhs, |a, b| a $op b);
+                self
+            }
+        }
+
+        impl<W: Word> ops::$trait for &PackedBoolVec<W> {
+            type Output = PackedBoolVec<W>;
+
+            fn $method(self, rhs: &PackedBoolVec<W>) -> PackedBoolVec<W> {
+                let mut out = self.clone();
+                out.combine(rhs, |a, b| a $op b);
+                out
+            }
+        }
+    };
+}
+
+// The binary operators treat the shorter vector as if it were padded with false values,
+// so the result is as long as the longer vector.
+impl_vec_binop!(&, BitAnd bitand, BitAndAssign bitand_assign);
+impl_vec_binop!(|, BitOr bitor, BitOrAssign bitor_assign);
+impl_vec_binop!(^, BitXor bitxor, BitXorAssign bitxor_assign);
+
+impl<W: Word> ops::Not for PackedBoolVec<W> {
+    type Output = PackedBoolVec<W>;
+
+    fn not(mut self) -> PackedBoolVec<W> {
+        self.words_mut().iter_mut().for_each(|w| *w = !*w);
+        self.clear_unused();
+        self
+    }
+}
+
+impl<W: Word> ops::Not for &PackedBoolVec<W> {
+    type Output = PackedBoolVec<W>;
+
+    fn not(self) -> PackedBoolVec<W> {
+        !self.clone()
+    }
+}
+
+/// An iterator over the booleans removed from a [`PackedBoolVec`].
+///
+/// This is created by [`PackedBoolVec::drain`] and [`PackedBoolVec::splice`].
+#[derive(Debug, Clone)]
+pub struct BoolVecDrain {
+    bits: PackedBoolVec,
+    range: ops::Range<usize>,
+}
+
+impl Iterator for BoolVecDrain {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().and_then(|idx| self.bits.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BoolVecDrain {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bits.get(idx))
+    }
+}
+
+impl ExactSizeIterator for BoolVecDrain {}
+
+impl FusedIterator for BoolVecDrain {}
+
+/// An iterator that moves the booleans out of a [`PackedBoolVec`].
+#[derive(Debug, Clone)]
+pub struct BoolVecIntoIter<W: Word = u8> {
+    bits: PackedBoolVec<W>,
+    range: ops::Range<usize>,
+}
+
+impl<W: Word> Iterator for BoolVecIntoIter<W> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().and_then(|idx| self.bits.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<bool> {
+        self.range.nth(n).and_then(|idx| self.bits.get(idx))
+    }
+}
+
+impl<W: Word> DoubleEndedIterator for BoolVecIntoIter<W> {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bits.get(idx))
+    }
+}
+
+impl<W: Word> ExactSizeIterator for BoolVecIntoIter<W> {}
+
+impl<W: Word> FusedIterator for BoolVecIntoIter<W> {}
+
+/// An iterator over the booleans in a [`PackedBoolVec`].
+#[derive(Debug, Clone)]
+pub struct BoolVecIter<'a, W: Word = u8> {
+    vec: &'a PackedBoolVec<W>,
+    range: ops::Range<usize>,
+}
+
+impl<W: Word> Iterator for BoolVecIter<'_, W> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().and_then(|idx| self.vec.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<W: Word> DoubleEndedIterator for BoolVecIter<'_, W> {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.vec.get(idx))
+    }
+}
+
+impl<W: Word> ExactSizeIterator for BoolVecIter<'_, W> {}
+
+impl<W: Word> FusedIterator for BoolVecIter<'_, W> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use super::{PackedBoolVec, Word};
+    use crate::{PackedBools8, PackedBools16};
+
+    fn pattern(len: usize, step: usize) -> PackedBoolVec {
+        (0..len).map(|i| (i * step) % 7 < 3).collect()
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut vec = PackedBoolVec::new();
+        for i in 0..20 {
+            vec.push(i % 3 == 0);
+        }
+        assert_eq!(vec.len(), 20);
+        assert_eq!(vec.count_true(), 7);
+        assert_eq!(vec.get(18), Some(true));
+        assert_eq!(vec.get(20), None);
+        vec.toggle(18);
+        vec.set(true, 19);
+        assert_eq!(vec.pop(), Some(true));
+        assert_eq!(vec.pop(), Some(false));
+        vec.truncate(9);
+        assert_eq!(vec.as_bytes(), &[0b0100_1001, 0]);
+        assert_eq!(format!("{vec:?}"), "PackedBoolVec(100100100)");
+    }
+
+    #[test]
+    fn constructors() {
+        let vec = PackedBoolVec::repeat(true, 11);
+        assert_eq!(vec.as_bytes(), &[0xff, 0b111]);
+        let vec = PackedBoolVec::from_bytes(&[0xff, 0xff, 0xff], 10);
+        assert_eq!(vec.as_bytes(), &[0xff, 0b11]);
+        assert_eq!(vec, PackedBoolVec::repeat(true, 10));
+        assert!(PackedBoolVec::with_capacity(100).capacity() >= 100);
+    }
+
+    #[test]
+    fn vec_bool() {
+        for len in [0, 1, 8, 13, 64, 100] {
+            let bools: Vec<bool> = (0..len).map(|i| i % 3 != 1).collect();
+            let vec = PackedBoolVec::from(bools.clone());
+            assert_eq!(vec, bools.iter().copied().collect());
+            assert_eq!(vec, PackedBoolVec::from(&bools[..]));
+            assert_eq!(vec.into_vec_bool(), bools);
+        }
+        let bools: Vec<bool> = PackedBoolVec::from_bytes(&[0b1010_0101], 5).into();
+        assert_eq!(bools, [true, false, true, false, false]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut vec = pattern(70, 3);
+        let expected: Vec<bool> = vec.iter().collect();
+        assert_eq!((&vec).into_iter().rev().collect::<Vec<_>>(), expected.iter().rev().copied().collect::<Vec<_>>());
+        for bit in &mut vec {
+            bit.toggle();
+        }
+        assert_eq!(vec.iter().zip(&expected).filter(|&(a, &b)| a == b).count(), 0);
+        assert_eq!(vec.count_true(), 70 - expected.iter().filter(|&&b| b).count());
+
+        let mut iter = vec.clone().into_iter();
+        assert_eq!(iter.len(), 70);
+        assert_eq!(iter.next_back(), vec.get(69));
+        assert_eq!(iter.nth(5), vec.get(5));
+        assert_eq!(iter.len(), 63);
+    }
+
+    #[test]
+    fn chunks_packed8() {
+        let mut vec = PackedBoolVec::new();
+        vec.extend([PackedBools8::from_bits(0x12), PackedBools8::from_bits(0x34)]);
+        vec.push(true);
+        let chunks = vec.chunks_packed8();
+        assert_eq!(chunks.remainder().len(), 1);
+        assert_eq!(chunks.map(PackedBools8::to_bits).collect::<Vec<_>>(), [0x12, 0x34]);
+    }
+
+    #[test]
+    fn iter_ones() {
+        let mut vec = PackedBoolVec::repeat(false, 300);
+        for idx in [3, 64, 65, 250, 299] {
+            vec.set(true, idx);
+        }
+        assert_eq!(vec.iter_ones().collect::<Vec<_>>(), [3, 64, 65, 250, 299]);
+        assert_eq!(vec.iter_zeros().count(), 295);
+        assert_eq!(PackedBoolVec::new().iter_ones().next(), None);
+    }
+
+    #[test]
+    fn push_packed() {
+        for start in 0..10 {
+            let mut vec = pattern(start, 3);
+            let mut expected = vec.clone();
+            let a = PackedBools8::from_bits(0b1001_0111);
+            let b = PackedBools16::from_bits(0xf00d);
+            vec.push_packed8(a);
+            vec.push_packed16(b);
+            vec.extend([a, !a]);
+            expected.extend(a.into_iter().chain(b).chain(a).chain(!a));
+            assert_eq!(vec, expected);
+            assert_eq!(vec.as_bytes(), expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        for (a_len, b_len) in [(0, 5), (13, 13), (70, 150), (150, 70), (64, 65)] {
+            let a = pattern(a_len, 3);
+            let b = pattern(b_len, 5);
+            let len = a_len.max(b_len);
+            let padded = |v: &PackedBoolVec, i| v.get(i).unwrap_or(false);
+            let check = |out: PackedBoolVec, op: fn(bool, bool) -> bool| {
+                assert_eq!(out.len(), len);
+                let expected: Vec<bool> = (0..len).map(|i| op(padded(&a, i), padded(&b, i))).collect();
+                assert!(out.iter().eq(expected));
+                assert_eq!(out, out.iter().collect());
+            };
+            check(&a & &b, |x, y| x & y);
+            check(&a | &b, |x, y| x | y);
+            check(a.clone() ^ &b, |x, y| x ^ y);
+            let mut assigned = a.clone();
+            assigned |= &b;
+            assert_eq!(assigned, &a | &b);
+
+            let inverted = !&a;
+            assert_eq!(inverted.len(), a_len);
+            assert_eq!(inverted.count_true(), a_len - a.count_true());
+        }
+    }
+
+    #[test]
+    fn views() {
+        let mut vec = pattern(40, 3);
+        vec.build_index();
+        let bits = vec.bits(5..30);
+        assert_eq!(bits.len(), 25);
+        assert!((0..25).all(|i| bits.get(i) == vec.get(i + 5)));
+        assert_eq!(bits.to_vec(), (5..30).map(|i| vec.get(i).unwrap()).collect());
+
+        let mut bits = vec.bits_mut(38..);
+        bits.set(true, 0);
+        bits.set(false, 1);
+        assert!(!vec.has_index());
+        assert_eq!(vec.get(38), Some(true));
+        assert_eq!(vec.get(39), Some(false));
+        assert_eq!(vec.bits(..).count_true(), vec.count_true());
+        assert!(vec.bits_mut(40..).is_empty());
+    }
+
+    #[test]
+    fn resize_fill() {
+        let mut vec = pattern(5, 1);
+        vec.resize(21, true);
+        assert_eq!(vec.len(), 21);
+        assert!((5..21).all(|i| vec.get(i) == Some(true)));
+        vec.resize(3, true);
+        assert_eq!(vec, pattern(3, 1));
+        vec.resize(10, false);
+        assert_eq!(vec.count_true(), pattern(3, 1).count_true());
+        vec.fill(true);
+        assert_eq!(vec, PackedBoolVec::repeat(true, 10));
+        vec.fill(false);
+        assert_eq!(vec.as_bytes(), &[0, 0]);
+
+        vec.grow_with(7, |i| i % 2 == 0);
+        assert_eq!(vec.len(), 17);
+        assert_eq!(vec.bits(10..).to_vec(), (10..17).map(|i| i % 2 == 0).collect());
+    }
+
+    #[test]
+    fn extend_from_bitslice() {
+        let source = pattern(50, 3);
+        for start_len in [0, 3, 8] {
+            for range in [0..50, 5..21, 16..48, 7..7] {
+                let mut vec = pattern(start_len, 5);
+                vec.extend_from_bitslice(source.bits(range.clone()));
+                let expected: PackedBoolVec = pattern(start_len, 5).iter()
+                    .chain(range.map(|i| source.get(i).unwrap()))
+                    .collect();
+                assert_eq!(vec, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn drain_splice() {
+        let source = pattern(70, 3);
+        let bools: Vec<bool> = source.iter().collect();
+        for range in [0..0, 0..70, 3..9, 8..16, 20..67, 69..70] {
+            for replacement_len in [0, 1, 9, 80] {
+                let replacement = (0..replacement_len).map(|i| i % 4 == 1);
+                let mut vec = source.clone();
+                let removed: Vec<bool> = vec.splice(range.clone(), replacement.clone()).collect();
+                let mut expected = bools.clone();
+                let expected_removed: Vec<bool> = expected.splice(range.clone(), replacement).collect();
+                assert_eq!(removed, expected_removed);
+                assert_eq!(vec, expected.into_iter().collect(), "{range:?} {replacement_len}");
+            }
+        }
+
+        let mut vec = source.clone();
+        let drain = vec.drain(10..);
+        assert_eq!(drain.len(), 60);
+        assert!(drain.rev().eq(bools[10..].iter().copied().rev()));
+        assert_eq!(vec, source.bits(..10).to_vec());
+    }
+
+    #[test]
+    fn rank_select() {
+        let mut vec = pattern(1500, 11);
+        let ones: Vec<usize> = (0..vec.len()).filter(|&i| vec.get(i).unwrap()).collect();
+        for indexed in [false, true] {
+            if indexed {
+                vec.build_index();
+            }
+            assert_eq!(vec.has_index(), indexed);
+            for (n, &idx) in ones.iter().enumerate() {
+                assert_eq!(vec.select(n), Some(idx));
+                assert_eq!(vec.rank(idx), n);
+            }
+            assert_eq!(vec.rank(vec.len()), ones.len());
+            assert_eq!(vec.select(ones.len()), None);
+        }
+
+        // changing the vector drops the index
+        vec.toggle(ones[0]);
+        assert!(!vec.has_index());
+        assert_eq!(vec.select(0), Some(ones[1]));
+        vec.build_index();
+        vec.push(true);
+        assert!(!vec.has_index());
+        assert_eq!(vec.rank(vec.len()), ones.len());
+        assert_eq!(vec, vec.clone());
+    }
+
+    /// Checks that a vector of `W` words behaves the same as one of bytes, through the same changes.
+    fn matches_bytes<W: Word>() {
+        let check = |words: &PackedBoolVec<W>, bytes: &PackedBoolVec| {
+            assert_eq!(words.len(), bytes.len());
+            assert_eq!(words.as_bytes(), bytes.as_bytes());
+            assert_eq!(words.count_true(), bytes.count_true());
+            assert_eq!(format!("{words:?}"), format!("{bytes:?}"));
+        };
+        let mut bytes = pattern(150, 3);
+        let mut words: PackedBoolVec<W> = bytes.iter().collect();
+        check(&words, &bytes);
+
+        fn edit<W: Word>(vec: &mut PackedBoolVec<W>) -> Vec<bool> {
+            vec.truncate(67);
+            vec.push(true);
+            vec.push_packed16(PackedBools16::from_bits(0xf00d));
+            vec.resize(140, true);
+            vec.toggle(3);
+            vec.splice(10..30, [true, false, true]).collect()
+        }
+        assert_eq!(edit(&mut words), edit(&mut bytes));
+        check(&words, &bytes);
+
+        // the index is built from the words, and dropped when they change
+        for indexed in [false, true] {
+            if indexed {
+                words.build_index();
+            }
+            for n in 0..=bytes.count_true() {
+                assert_eq!(words.select(n), bytes.select(n));
+            }
+            for idx in 0..=bytes.len() {
+                assert_eq!(words.rank(idx), bytes.rank(idx));
+            }
+        }
+        words.set(false, bytes.select(0).unwrap());
+        assert!(!words.has_index());
+        assert_eq!(words.rank(words.len()), bytes.count_true() - 1);
+        words.toggle(bytes.select(0).unwrap());
+
+        let other = pattern(200, 5);
+        let other_words: PackedBoolVec<W> = other.iter().collect();
+        check(&(&words & &other_words), &(&bytes & &other));
+        check(&(words.clone() | &other_words), &(bytes.clone() | &other));
+        check(&(&other_words ^ &words), &(&other ^ &bytes));
+        check(&!&words, &!&bytes);
+
+        words.extend_from_bitslice(other.bits(3..77));
+        bytes.extend_from_bitslice(other.bits(3..77));
+        check(&words, &bytes);
+        for bit in &mut words {
+            bit.toggle();
+        }
+        check(&words, &!&bytes);
+        words.fill(true);
+        check(&words, &PackedBoolVec::repeat(true, bytes.len()));
+        words.clear();
+        check(&words, &PackedBoolVec::new());
+        assert_eq!(words.into_parts(), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn word_storage() {
+        matches_bytes::<u16>();
+        matches_bytes::<u32>();
+        matches_bytes::<u64>();
+        matches_bytes::<usize>();
+    }
+
+    #[test]
+    fn word_parts() {
+        let mut vec = PackedBoolVec::<u32>::default();
+        vec.extend([true, false, true]);
+        vec.resize(40, true);
+        assert_eq!(vec.capacity() % 32, 0);
+        assert_eq!(vec.as_bytes(), &[0xfd, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(vec.into_parts(), (alloc::vec![0xffff_fffd, 0xff], 40));
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        PackedBoolVec::repeat(false, 8).set(true, 8);
+    }
+}
+
+/// Serializes as a string of `0`s and `1`s in human-readable formats,
+/// and as the length followed by the packed bytes otherwise.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use alloc::vec::Vec;
+    use core::{fmt, marker::PhantomData};
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::{PackedBoolVec, Word};
+    use crate::BitSlice;
+
+    const FIELDS: &[&str] = &["len", "bytes"];
+
+    /// Serializes a byte slice as bytes rather than as a sequence.
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    struct ByteBuf(Vec<u8>);
+
+    impl<'de> Deserialize<'de> for ByteBuf {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct ByteBufVisitor;
+
+            impl<'de> Visitor<'de> for ByteBufVisitor {
+                type Value = ByteBuf;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("bytes")
+                }
+
+                fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<ByteBuf, E> {
+                    Ok(ByteBuf(bytes.to_vec()))
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+                    let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                    while let Some(byte) = seq.next_element()? {
+                        bytes.push(byte);
+                    }
+                    Ok(ByteBuf(bytes))
+                }
+            }
+
+            deserializer.deserialize_bytes(ByteBufVisitor)
+        }
+    }
+
+    // every word type serializes the same way, as packed bytes
+    impl<W: Word> Serialize for PackedBoolVec<W> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&format_args!("{}", BitString(self)))
+            } else {
+                let mut state = serializer.serialize_struct("PackedBoolVec", 2)?;
+                state.serialize_field("len", &(self.len as u64))?;
+                state.serialize_field("bytes", &Bytes(self.as_bytes()))?;
+                state.end()
+            }
+        }
+    }
+
+    struct BitString<'a, W: Word>(&'a PackedBoolVec<W>);
+
+    impl<W: Word> fmt::Display for BitString<'_, W> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|b| f.write_str(if b { "1" } else { "0" }))
+        }
+    }
+
+    enum Field {
+        Len,
+        Bytes,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct FieldVisitor;
+
+            impl Visitor<'_> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("`len` or `bytes`")
+                }
+
+                fn visit_str<E: de::Error>(self, s: &str) -> Result<Field, E> {
+                    match s {
+                        "len" => Ok(Field::Len),
+                        "bytes" => Ok(Field::Bytes),
+                        _ => Err(E::unknown_field(s, FIELDS)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct PackedBoolVecVisitor<W>(PhantomData<W>);
+
+    impl<W: Word> PackedBoolVecVisitor<W> {
+        fn from_parts<E: de::Error>(bytes: Vec<u8>, len: u64) -> Result<PackedBoolVec<W>, E> {
+            let len = usize::try_from(len).map_err(|_| E::custom("length too large"))?;
+            if bytes.len() != len.div_ceil(8) {
+                return Err(E::invalid_length(bytes.len(), &"one byte for every 8 booleans"));
+            }
+            let mut vec = PackedBoolVec::default();
+            vec.extend_from_bitslice(BitSlice::new(&bytes, ..len));
+            if vec.as_bytes() != bytes {
+                return Err(E::custom("the unused bits of the last byte must be false"));
+            }
+            Ok(vec)
+        }
+    }
+
+    impl<'de, W: Word> Visitor<'de> for PackedBoolVecVisitor<W> {
+        type Value = PackedBoolVec<W>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a string of 0s and 1s, or a length and packed bytes")
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<PackedBoolVec<W>, E> {
+            s.bytes()
+                .map(|b| match b {
+                    b'0' => Ok(false),
+                    b'1' => Ok(true),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(s), &self)),
+                })
+                .collect()
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PackedBoolVec<W>, A::Error> {
+            let len = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let ByteBuf(bytes) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Self::from_parts(bytes, len)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PackedBoolVec<W>, A::Error> {
+            let (mut len, mut bytes) = (None, None);
+            while let Some(field) = map.next_key()? {
+                match field {
+                    Field::Len => len = Some(map.next_value()?),
+                    Field::Bytes => bytes = Some(map.next_value::<ByteBuf>()?.0),
+                }
+            }
+            let len = len.ok_or_else(|| de::Error::missing_field("len"))?;
+            let bytes = bytes.ok_or_else(|| de::Error::missing_field("bytes"))?;
+            Self::from_parts(bytes, len)
+        }
+    }
+
+    impl<'de, W: Word> Deserialize<'de> for PackedBoolVec<W> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(PackedBoolVecVisitor(PhantomData))
+            } else {
+                deserializer.deserialize_struct("PackedBoolVec", FIELDS, PackedBoolVecVisitor(PhantomData))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde::de::value::{Error, StrDeserializer};
+        use serde::de::{Deserialize, IntoDeserializer};
+
+        use super::{PackedBoolVec, PackedBoolVecVisitor};
+
+        #[test]
+        fn from_bit_string() {
+            let de: StrDeserializer<'_, Error> = "10110".into_deserializer();
+            let vec = <PackedBoolVec>::deserialize(de).unwrap();
+            assert_eq!(vec.as_bytes(), &[0b01101]);
+            assert_eq!(alloc::format!("{}", super::BitString(&vec)), "10110");
+
+            let de: StrDeserializer<'_, Error> = "10x".into_deserializer();
+            assert!(<PackedBoolVec>::deserialize(de).is_err());
+        }
+
+        #[test]
+        fn from_parts() {
+            let vec = PackedBoolVecVisitor::from_parts::<Error>(alloc::vec![0xff, 0b1], 9).unwrap();
+            assert_eq!(vec, PackedBoolVec::repeat(true, 9));
+            assert!(PackedBoolVecVisitor::<u8>::from_parts::<Error>(alloc::vec![0xff, 0b11], 9).is_err());
+            assert!(PackedBoolVecVisitor::<u8>::from_parts::<Error>(alloc::vec![0xff], 9).is_err());
+            let words = PackedBoolVecVisitor::<u64>::from_parts::<Error>(alloc::vec![0xff, 0b1], 9).unwrap();
+            assert!(words.iter().eq(vec.iter()));
+            assert!(PackedBoolVecVisitor::<u64>::from_parts::<Error>(alloc::vec![0xff, 0b11], 9).is_err());
+        }
+    }
+}
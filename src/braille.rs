@@ -0,0 +1,75 @@
+//! Compact rendering of packed values as Braille characters.
+
+use core::fmt;
+
+use crate::PackedBools8;
+
+/// Returns the Braille character showing the 8 values in a byte.
+pub(crate) const fn braille_char(byte: u8) -> char {
+    match char::from_u32(0x2800 + byte as u32) {
+        Some(c) => c,
+        None => unreachable!(),
+    }
+}
+
+/// A formatting adapter that displays a packed buffer as one Braille character per byte.
+///
+/// Each Braille character from `U+2800` to `U+28FF` has 8 dots,
+/// so it can show the 8 values of a byte.
+/// Value `i` of a byte is shown by dot `i + 1` of the character,
+/// which follows the Unicode numbering of the dots:
+/// the left column is values 0, 1, 2 and 6 from top to bottom,
+/// and the right column is values 3, 4, 5 and 7.
+///
+/// ```
+/// use packed_booleans::Braille;
+///
+/// assert_eq!(Braille::new(&[0x00, 0x01, 0xff]).to_string(), "⠀⠁⣿");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Braille<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Braille<'a> {
+    /// Creates an adapter that displays the given bytes.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Creates an adapter that displays the given packed values.
+    pub fn from_packed(values: &'a [PackedBools8]) -> Self {
+        Self::new(PackedBools8::slice_as_bytes(values))
+    }
+}
+
+impl fmt::Display for Braille<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.bytes.iter().try_for_each(|&b| fmt::Write::write_char(f, braille_char(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::string::ToString;
+
+    use super::Braille;
+    use crate::PackedBools8;
+
+    #[test]
+    fn chars() {
+        assert_eq!(PackedBools8::new().to_braille(), '\u{2800}');
+        // the left column
+        assert_eq!(PackedBools8::from_bits(0b0100_0111).to_braille(), '⡇');
+        // the right column
+        assert_eq!(PackedBools8::from_bits(0b1011_1000).to_braille(), '⢸');
+    }
+
+    #[test]
+    fn slices() {
+        let values = [0x01, 0x09, 0xff].map(PackedBools8::from_bits);
+        assert_eq!(Braille::from_packed(&values).to_string(), "⠁⠉⣿");
+        assert_eq!(Braille::new(&[]).to_string(), "");
+    }
+}
@@ -0,0 +1,69 @@
+//! Detecting the changes between two packed values.
+
+/// The changes between two snapshots of a packed value.
+///
+/// ```
+/// use packed_booleans::{ChangeSet, PackedBools8};
+///
+/// let before = PackedBools8::from_bits(0b0011);
+/// let after = PackedBools8::from_bits(0b0110);
+/// let changes = ChangeSet::new(before, after);
+/// assert_eq!(changes.raised(), 0b0100);
+/// assert_eq!(changes.cleared(), 0b0001);
+/// assert!(changes.raised_indices().eq([2]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChangeSet<T> {
+    before: T,
+    after: T,
+}
+
+impl<T: Copy> ChangeSet<T> {
+    /// Creates the changes from `before` to `after`.
+    pub const fn new(before: T, after: T) -> Self {
+        Self { before, after }
+    }
+
+    /// Returns the value before the changes.
+    pub const fn before(&self) -> T {
+        self.before
+    }
+
+    /// Returns the value after the changes.
+    pub const fn after(&self) -> T {
+        self.after
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use super::ChangeSet;
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn edges() {
+        let before = PackedBools16::from_bits(0x00ff);
+        let after = PackedBools16::from_bits(0x0ff0);
+        let changes = before.changes_to(after);
+        assert_eq!(changes.raised(), 0x0f00);
+        assert_eq!(changes.cleared(), 0x000f);
+        assert_eq!(changes.changed(), 0x0f0f);
+        assert_eq!(changes.cleared_indices().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(changes.changed_indices().len(), 8);
+        assert!(!changes.is_empty());
+        assert_eq!(changes.apply(before), after);
+        assert_eq!(changes.apply(PackedBools16::new()), 0x0f00);
+    }
+
+    #[test]
+    fn no_changes() {
+        let pkd = PackedBools8::from_bits(0x5a);
+        let changes = ChangeSet::new(pkd, pkd);
+        assert!(changes.is_empty());
+        assert_eq!(changes.raised_indices().next(), None);
+        assert_eq!(changes.after(), pkd);
+    }
+}
@@ -0,0 +1,131 @@
+//! Carry-less multiplication, which multiplies polynomials over GF(2).
+//!
+//! Bit `i` of a value is the coefficient of `x^i`,
+//! so the product of two `n`-bit values fits in `2n` bits.
+
+use crate::{CpuFeatures, PackedBools8, PackedBools16, PackedBools32, PackedBools64, PackedBools128};
+
+macro_rules! impl_clmul {
+    ($($pkd:ident: $repr:ident => $wide_pkd:ident: $wide:ident),*) => {$(
+        impl $pkd {
+            /// Multiplies `self` and `other` as polynomials over GF(2),
+            /// where the value at index `i` is the coefficient of `x^i`.
+            ///
+            /// This is multiplication where the partial products are combined with XOR instead of addition,
+            /// as used by CRCs and GHASH.
+            pub const fn clmul(self, other: Self) -> $wide_pkd {
+                let a = self.to_bits() as $wide;
+                let b = other.to_bits();
+                let mut out = 0;
+                let mut i = 0;
+                while i < <$repr>::BITS {
+                    if (b >> i) & 1 != 0 {
+                        out ^= a << i;
+                    }
+                    i += 1;
+                }
+                $wide_pkd::from_bits(out)
+            }
+        }
+    )*};
+}
+
+impl_clmul! {
+    PackedBools8: u8 => PackedBools16: u16,
+    PackedBools16: u16 => PackedBools32: u32,
+    PackedBools32: u32 => PackedBools64: u64
+}
+
+impl PackedBools64 {
+    /// Multiplies `self` and `other` as polynomials over GF(2),
+    /// where the value at index `i` is the coefficient of `x^i`.
+    ///
+    /// This is multiplication where the partial products are combined with XOR instead of addition,
+    /// as used by CRCs and GHASH.
+    ///
+    /// This uses the CPU's carry-less multiply instruction if [`CpuFeatures::detect`] finds one.
+    pub fn clmul(self, other: Self) -> PackedBools128 {
+        PackedBools128::from_bits(clmul64(self.to_bits(), other.to_bits()))
+    }
+}
+
+fn clmul64(a: u64, b: u64) -> u128 {
+    if CpuFeatures::detect().clmul() {
+        // SAFETY: the CPU has the instruction
+        return unsafe { instr::clmul(a, b) };
+    }
+    clmul64_fallback(a, b)
+}
+
+const fn clmul64_fallback(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    let mut out = 0;
+    let mut i = 0;
+    while i < 64 {
+        if (b >> i) & 1 != 0 {
+            out ^= a << i;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// The carry-less multiply instruction, which must only be called if [`CpuFeatures::clmul`] is true.
+mod instr {
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "pclmulqdq")]
+    pub(super) unsafe fn clmul(a: u64, b: u64) -> u128 {
+        use core::arch::x86_64::{_mm_clmulepi64_si128, _mm_set_epi64x};
+
+        let product = _mm_clmulepi64_si128(_mm_set_epi64x(0, a as i64), _mm_set_epi64x(0, b as i64), 0);
+        // SAFETY: __m128i and u128 are both 16 plain bytes, stored little-endian on x86-64
+        unsafe { core::mem::transmute(product) }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon,aes")]
+    pub(super) unsafe fn clmul(a: u64, b: u64) -> u128 {
+        core::arch::aarch64::vmull_p64(a, b)
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub(super) unsafe fn clmul(_: u64, _: u64) -> u128 {
+        unreachable!("there is no carry-less multiply instruction on this architecture")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::XorShift;
+
+    #[test]
+    fn small() {
+        let three = PackedBools8::from_bits(0b11);
+        assert_eq!(three.clmul(three).to_bits(), 0b101);
+        assert_eq!(PackedBools8::from_bits(0xff).clmul(PackedBools8::from_bits(0xff)).to_bits(), 0x5555);
+        assert_eq!(PackedBools16::from_bits(0x8000).clmul(PackedBools16::from_bits(0x8000)).to_bits(), 1 << 30);
+        assert_eq!(PackedBools32::from_bits(7).clmul(PackedBools32::new()), PackedBools64::new());
+    }
+
+    #[test]
+    fn fast_path_matches_fallback() {
+        let mut rng = XorShift::new(0x1234_5678);
+        for _ in 0..1000 {
+            let (a, b) = (rng.next_u64(), rng.next_u64());
+            assert_eq!(clmul64(a, b), clmul64_fallback(a, b));
+        }
+        assert_eq!(clmul64(u64::MAX, u64::MAX), 0x5555_5555_5555_5555_5555_5555_5555_5555);
+    }
+
+    #[test]
+    fn widths_agree() {
+        let mut rng = XorShift::new(99);
+        for _ in 0..100 {
+            let (a, b) = (rng.next_u64() as u32, rng.next_u64() as u32);
+            let narrow = PackedBools32::from_bits(a).clmul(PackedBools32::from_bits(b));
+            let wide = PackedBools64::from_bits(a.into()).clmul(PackedBools64::from_bits(b.into()));
+            assert_eq!(u128::from(narrow.to_bits()), wide.to_bits());
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Visiting every value with a given number of true values.
+
+/// An iterator over every value of a packed type with exactly `k` true values,
+/// in increasing order of their bits.
+///
+/// This is created by the `combinations` function on the packed types.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Combinations<T> {
+    /// The value yielded next.
+    pub(crate) next: Option<T>,
+}
+
+impl<T> Combinations<T> {
+    pub(crate) const fn new(first: Option<T>) -> Self {
+        Self { next: first }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn all_combinations() {
+        for k in 0..=8 {
+            let expected = (0..=u8::MAX).filter(|bits| bits.count_ones() == u32::from(k));
+            assert!(PackedBools8::combinations(k).map(PackedBools8::to_bits).eq(expected));
+        }
+        assert_eq!(PackedBools8::combinations(9).next(), None);
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(PackedBools16::combinations(0).count(), 1);
+        assert_eq!(PackedBools16::combinations(3).count(), 560);
+        assert_eq!(PackedBools16::combinations(16).count(), 1);
+
+        let mut iter = PackedBools8::combinations(2);
+        assert_eq!(iter.next(), Some(PackedBools8::from_bits(0b0011)));
+        assert_eq!(iter.next(), Some(PackedBools8::from_bits(0b0101)));
+        assert_eq!(iter.next(), Some(PackedBools8::from_bits(0b0110)));
+        assert_eq!(iter.next(), Some(PackedBools8::from_bits(0b1001)));
+        assert_eq!(iter.last(), Some(PackedBools8::from_bits(0b1100_0000)));
+    }
+}
@@ -0,0 +1,199 @@
+//! Errors for converting packed values to and from integers and text.
+
+use core::fmt;
+
+/// The error returned when converting an integer into a packed value
+/// that doesn't have room for all of its true bits.
+///
+/// ```
+/// use packed_booleans::PackedBools8;
+///
+/// let err = PackedBools8::try_from(0x0301_u16).unwrap_err();
+/// assert_eq!(err.stray_bits(), 0x0300);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StrayBitsError {
+    stray: u128,
+    capacity: u8,
+}
+
+impl StrayBitsError {
+    pub(crate) const fn new(stray: u128, capacity: u8) -> Self {
+        Self { stray, capacity }
+    }
+
+    /// Returns the true bits that didn't fit, in their original positions.
+    pub const fn stray_bits(&self) -> u128 {
+        self.stray
+    }
+
+    /// Returns how many booleans the packed type has room for.
+    pub const fn capacity(&self) -> u8 {
+        self.capacity
+    }
+}
+
+impl fmt::Display for StrayBitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the bits {:#x} don't fit in {} booleans", self.stray, self.capacity)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StrayBitsError {}
+
+/// The error returned when parsing a packed value from binary digits fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseBitsError {
+    /// The input didn't have exactly one digit for every boolean.
+    WrongLength {
+        /// How many digits the packed type needs.
+        expected: u8,
+        /// How many digits the input had.
+        found: usize,
+    },
+    /// The input had a byte that wasn't `b'0'` or `b'1'`.
+    InvalidDigit {
+        /// The position of the byte in the input.
+        index: usize,
+        /// The byte itself.
+        byte: u8,
+    },
+}
+
+impl fmt::Display for ParseBitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::WrongLength { expected, found } => {
+                write!(f, "expected {expected} binary digits, found {found}")
+            }
+            Self::InvalidDigit { index, byte } => {
+                write!(f, "invalid binary digit '{}' at position {index}", byte.escape_ascii())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseBitsError {}
+
+/// The error returned when writing a packed value as text into a buffer that is too short.
+///
+/// ```
+/// use packed_booleans::PackedBools16;
+///
+/// let mut buf = [0; 3];
+/// let err = PackedBools16::new().write_lower_hex(&mut buf).unwrap_err();
+/// assert_eq!((err.needed(), err.available()), (4, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufTooSmall {
+    needed: usize,
+    available: usize,
+}
+
+impl BufTooSmall {
+    pub(crate) const fn new(needed: usize, available: usize) -> Self {
+        Self { needed, available }
+    }
+
+    /// Returns how many bytes the text needs.
+    pub const fn needed(&self) -> usize {
+        self.needed
+    }
+
+    /// Returns how many bytes the buffer had.
+    pub const fn available(&self) -> usize {
+        self.available
+    }
+}
+
+impl fmt::Display for BufTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the text needs {} bytes, but the buffer only has {}", self.needed, self.available)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufTooSmall {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::string::ToString;
+
+    use alloc::format;
+
+    use super::{BufTooSmall, ParseBitsError, StrayBitsError};
+    use crate::{PackedBools8, PackedBools16, PackedBools32, PackedBools64, PackedBools128};
+
+    #[test]
+    fn narrowing() {
+        assert_eq!(PackedBools8::try_from(0xff_u16), Ok(PackedBools8::from_bits(0xff)));
+        assert_eq!(PackedBools8::try_from(0x1_0000_0001_u64), Err(StrayBitsError::new(0x1_0000_0000, 8)));
+        assert_eq!(PackedBools16::try_from(0xffff_u128), Ok(PackedBools16::from_bits(0xffff)));
+        let err = PackedBools16::try_from(0xf001_0000_u32).unwrap_err();
+        assert_eq!(err.stray_bits(), 0xf001_0000);
+        assert_eq!(err.capacity(), 16);
+        assert_eq!(err.to_string(), "the bits 0xf0010000 don't fit in 16 booleans");
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(PackedBools8::from_ascii_bits(b"10110010"), Ok(PackedBools8::from_bits(0b1011_0010)));
+        assert_eq!("0000000000000001".parse(), Ok(PackedBools16::from_bits(1)));
+        assert_eq!(
+            PackedBools8::from_ascii_bits(b"1011001"),
+            Err(ParseBitsError::WrongLength { expected: 8, found: 7 })
+        );
+        let err = PackedBools8::from_ascii_bits(b"1011_001").unwrap_err();
+        assert_eq!(err, ParseBitsError::InvalidDigit { index: 4, byte: b'_' });
+        assert_eq!(err.to_string(), "invalid binary digit '_' at position 4");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn index_order_strings() {
+        let pkd = PackedBools16::from_bits(0x1c2f);
+        assert_eq!(pkd.to_binary_string(), "1111010000111000");
+        assert_eq!(pkd.to_hex_string(), "f2c1");
+        assert_eq!(PackedBools8::from_bits(1).to_binary_string(), "10000000");
+        assert_eq!(PackedBools128::from_bits(0xa << 124).to_hex_string().chars().last(), Some('a'));
+        assert_eq!(crate::slices::to_binary_string(&pkd.to_le_bytes()), pkd.to_binary_string());
+        assert_eq!(crate::slices::to_hex_string(&pkd.to_le_bytes()), pkd.to_hex_string());
+    }
+
+    #[test]
+    fn lattice() {
+        let pkd = PackedBools8::from_bits(0xa5);
+        let wide = PackedBools128::from(pkd);
+        assert_eq!(wide.to_bits(), 0xa5);
+        assert_eq!(PackedBools32::from(PackedBools16::from(pkd)).to_bits(), 0xa5);
+        assert_eq!(PackedBools8::try_from(wide), Ok(pkd));
+        assert_eq!(PackedBools64::try_from(wide), Ok(PackedBools64::from(pkd)));
+        let err = PackedBools16::try_from(PackedBools64::from_bits(1 << 40 | 1)).unwrap_err();
+        assert_eq!(err.stray_bits(), 1 << 40);
+    }
+
+    #[test]
+    fn write_text() {
+        let mut buf = [0; 40];
+        let pkd = PackedBools32::from_bits(0x00c0_ffee);
+        assert_eq!(pkd.write_binary(&mut buf), Ok(32));
+        assert_eq!(buf[..32], *format!("{pkd:b}").as_bytes());
+        assert_eq!(pkd.write_lower_hex(&mut buf), Ok(8));
+        assert_eq!(buf[..8], *b"00c0ffee");
+        assert_eq!(pkd.write_upper_hex(&mut buf), Ok(8));
+        assert_eq!(buf[..8], *format!("{pkd:X}").as_bytes());
+
+        let pkd = PackedBools128::from_bits(u128::MAX / 3);
+        assert_eq!(pkd.write_binary(&mut buf), Err(BufTooSmall::new(128, 40)));
+        assert_eq!(pkd.write_lower_hex(&mut buf[..32]), Ok(32));
+        assert_eq!(buf[..32], *format!("{pkd:x}").as_bytes());
+
+        let mut buf = [b'?'; 9];
+        assert_eq!(PackedBools8::from_bits(0b1000_0110).write_binary(&mut buf), Ok(8));
+        assert_eq!(buf, *b"10000110?");
+        assert_eq!(PackedBools8::new().write_upper_hex(&mut buf[..1]).unwrap_err().to_string(), "the text needs 2 bytes, but the buffer only has 1");
+    }
+}
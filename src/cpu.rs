@@ -0,0 +1,114 @@
+//! Detecting the CPU features used by the accelerated routines.
+
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicU8, Ordering::Relaxed};
+
+/// The features found by the first call to [`CpuFeatures::detect`],
+/// or 0 if it hasn't been called yet.
+#[cfg(feature = "std")]
+static DETECTED: AtomicU8 = AtomicU8::new(0);
+
+/// Set in [`DETECTED`] once the features have been found.
+#[cfg(feature = "std")]
+const DETECTED_SET: u8 = 1;
+
+/// Set in [`DETECTED`] if there is a carry-less multiply instruction.
+#[cfg(feature = "std")]
+const DETECTED_CLMUL: u8 = 1 << 1;
+
+/// The CPU features that the accelerated routines can use.
+///
+/// The routines check these themselves and fall back to portable code,
+/// so this is only needed to find out which paths are being used.
+/// The only accelerated routine is currently [`PackedBools64::clmul`](crate::PackedBools64::clmul);
+/// everything else is portable code.
+///
+/// ```
+/// use packed_booleans::CpuFeatures;
+///
+/// let features = CpuFeatures::detect();
+/// // anything enabled at compile time is always detected
+/// assert!(features.clmul() || !CpuFeatures::compile_time().clmul());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CpuFeatures {
+    clmul: bool,
+}
+
+impl CpuFeatures {
+    /// Returns the features of the CPU the program is running on.
+    ///
+    /// With the `std` feature, the first call checks the CPU at runtime and stores the result in a static,
+    /// which later calls read.
+    /// Without it, this is the same as [`compile_time`](Self::compile_time).
+    pub fn detect() -> Self {
+        #[cfg(feature = "std")]
+        {
+            let mut bits = DETECTED.load(Relaxed);
+            if bits == 0 {
+                // racing threads all find the same features, so any of them can store them
+                bits = DETECTED_SET | if detect_clmul() { DETECTED_CLMUL } else { 0 };
+                DETECTED.store(bits, Relaxed);
+            }
+            Self { clmul: bits & DETECTED_CLMUL != 0 }
+        }
+
+        #[cfg(not(feature = "std"))]
+        Self::compile_time()
+    }
+
+    /// Returns the features enabled at compile time, with `-C target-feature` or `-C target-cpu`.
+    pub const fn compile_time() -> Self {
+        Self {
+            clmul: cfg!(any(
+                all(target_arch = "x86_64", target_feature = "pclmulqdq"),
+                all(target_arch = "aarch64", target_feature = "aes"),
+            )),
+        }
+    }
+
+    /// Returns true if there is a carry-less multiply instruction,
+    /// PCLMULQDQ on x86-64 or PMULL on AArch64.
+    ///
+    /// This is used by [`PackedBools64::clmul`](crate::PackedBools64::clmul).
+    pub const fn clmul(&self) -> bool {
+        self.clmul
+    }
+}
+
+#[cfg(feature = "std")]
+fn detect_clmul() -> bool {
+    if CpuFeatures::compile_time().clmul {
+        return true;
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    return std::arch::is_x86_feature_detected!("pclmulqdq");
+
+    #[cfg(target_arch = "aarch64")]
+    return std::arch::is_aarch64_feature_detected!("aes");
+
+    #[allow(unreachable_code)]
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CpuFeatures;
+
+    #[test]
+    fn detect() {
+        let detected = CpuFeatures::detect();
+        assert_eq!(CpuFeatures::detect(), detected);
+        assert!(detected.clmul() || !CpuFeatures::compile_time().clmul());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn detect_is_cached() {
+        let detected = CpuFeatures::detect();
+        let cached = super::DETECTED.load(core::sync::atomic::Ordering::Relaxed);
+        assert_ne!(cached & super::DETECTED_SET, 0);
+        assert_eq!(cached & super::DETECTED_CLMUL != 0, detected.clmul());
+    }
+}
@@ -0,0 +1,178 @@
+//! Writing booleans into a byte buffer.
+
+use core::fmt;
+
+use crate::{PackedBools8, PackedBools16};
+
+/// A cursor that writes booleans, packed values, or n-bit fields into a byte buffer.
+///
+/// Bits are written starting from the least significant bit of each byte,
+/// so writing a `PackedBools8` at the start of a byte writes its bits unchanged.
+///
+/// Writes overwrite the existing bits in the buffer.
+/// A write that doesn't fit in the rest of the buffer
+/// fails with [`BufferExhausted`] and writes nothing.
+#[derive(Debug)]
+pub struct BitCursor<'a> {
+    buf: &'a mut [u8],
+    /// The position of the cursor, in bits.
+    pos: usize,
+}
+
+impl<'a> BitCursor<'a> {
+    /// Creates a new `BitCursor` writing from the start of the given buffer.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Writes a single boolean.
+    pub fn write_bool(&mut self, val: bool) -> Result<(), BufferExhausted> {
+        self.write_bits(val.into(), 1)
+    }
+
+    /// Writes the 8 booleans in a `PackedBools8`.
+    pub fn write_packed8(&mut self, val: PackedBools8) -> Result<(), BufferExhausted> {
+        self.write_bits(val.to_bits().into(), 8)
+    }
+
+    /// Writes the 16 booleans in a `PackedBools16`.
+    pub fn write_packed16(&mut self, val: PackedBools16) -> Result<(), BufferExhausted> {
+        self.write_bits(val.to_bits().into(), 16)
+    }
+
+    /// Writes the lowest `n` bits of `bits`,
+    /// starting with the least significant bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 32.
+    pub fn write_bits(&mut self, bits: u32, n: u8) -> Result<(), BufferExhausted> {
+        assert!(n <= 32, "Cannot write more than 32 bits at once");
+        if usize::from(n) > self.remaining() {
+            return Err(BufferExhausted);
+        }
+        let mut written = 0;
+        while written < n {
+            let byte = &mut self.buf[self.pos / 8];
+            let bit = (self.pos % 8) as u8;
+            let take = (8 - bit).min(n - written);
+            let mask = (((1u16 << take) - 1) as u8) << bit;
+            let val = ((bits >> written) as u8) << bit;
+            *byte = (*byte & !mask) | (val & mask);
+            written += take;
+            self.pos += usize::from(take);
+        }
+        Ok(())
+    }
+
+    /// Writes false bits until the cursor is at the start of a byte.
+    ///
+    /// This can't fail, as a partially written byte is always in the buffer.
+    pub fn align_to_byte(&mut self) {
+        let padding = (8 - self.pos % 8) % 8;
+        // `padding` bits always fit, as they are in the current byte
+        let _ = self.write_bits(0, padding as u8);
+    }
+
+    /// Returns the position of the cursor, in bits.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves the cursor to the given bit position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is past the end of the buffer.
+    pub fn set_position(&mut self, pos: usize) {
+        assert!(pos <= self.capacity(), "The position cannot be past the end of the buffer");
+        self.pos = pos;
+    }
+
+    /// Returns the size of the buffer, in bits.
+    pub fn capacity(&self) -> usize {
+        self.buf.len() * 8
+    }
+
+    /// Returns how many more bits can be written.
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.pos
+    }
+
+    /// Returns the bytes that have been written to,
+    /// including the last partially written byte.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos.div_ceil(8)]
+    }
+
+    /// Unwraps this `BitCursor`, returning the underlying buffer.
+    pub fn into_inner(self) -> &'a mut [u8] {
+        self.buf
+    }
+}
+
+/// The error returned when a buffer has no room left for a write,
+/// such as by a [`BitCursor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferExhausted;
+
+impl fmt::Display for BufferExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not enough space left in the buffer")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferExhausted {}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitCursor, BufferExhausted};
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn write_bools() {
+        let mut buf = [0xff; 1];
+        let mut cursor = BitCursor::new(&mut buf);
+        for b in [true, false, true, false, false, false, false, false] {
+            cursor.write_bool(b).unwrap();
+        }
+        assert_eq!(cursor.write_bool(true), Err(BufferExhausted));
+        assert_eq!(buf, [0b0000_0101]);
+    }
+
+    #[test]
+    fn write_packed() {
+        let mut buf = [0; 3];
+        let mut cursor = BitCursor::new(&mut buf);
+        cursor.write_packed8(PackedBools8::from_bits(0xd4)).unwrap();
+        cursor.write_packed16(PackedBools16::from_bits(0x1234)).unwrap();
+        assert_eq!(cursor.remaining(), 0);
+        assert_eq!(buf, [0xd4, 0x34, 0x12]);
+    }
+
+    #[test]
+    fn write_unaligned() {
+        let mut buf = [0; 2];
+        let mut cursor = BitCursor::new(&mut buf);
+        cursor.write_bits(0b110, 3).unwrap();
+        cursor.write_bits(0b111_0110, 7).unwrap();
+        assert_eq!(cursor.position(), 10);
+        assert_eq!(cursor.written(), &[0b1011_0110, 0b11]);
+        cursor.align_to_byte();
+        assert_eq!(cursor.position(), 16);
+        assert_eq!(buf, [0b1011_0110, 0b11]);
+    }
+
+    #[test]
+    fn failed_write_writes_nothing() {
+        let mut buf = [0; 2];
+        let mut cursor = BitCursor::new(&mut buf);
+        cursor.write_bits(0b101, 3).unwrap();
+        assert_eq!(cursor.write_packed16(PackedBools16::from_bits(0xffff)), Err(BufferExhausted));
+        assert_eq!(cursor.position(), 3);
+        cursor.set_position(0);
+        cursor.write_bits(0b010, 3).unwrap();
+        assert_eq!(buf, [0b010, 0]);
+    }
+}
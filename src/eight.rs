@@ -2,8 +2,12 @@
 
 use core::iter::FusedIterator;
 
+use crate::MiniRange8;
+
 crate::macros::packed_bools_type!{
     NAME = PackedBools8,
+    BUILDER = PackedBools8Builder,
+    INDEX = BitIndex8,
     REPR = u8,
     BOOL_COUNT = 8,
     BCOUNT_MINUS1 = 7,
@@ -12,7 +16,9 @@ crate::macros::packed_bools_type!{
     DEBUG = "PackedBools8({:#010b})",
     BINARY = "{:08b}",
     LOW_HEX = "{:02x}",
-    UPPER_HEX = "{:02X}"
+    UPPER_HEX = "{:02X}",
+    WIDER_INTS = [u16, u32, u64, u128],
+    WIDER_TYPES = [PackedBools16, PackedBools32, PackedBools64, PackedBools128]
 }
 
 impl IntoIterator for PackedBools8 {
@@ -24,74 +30,68 @@ impl IntoIterator for PackedBools8 {
     }
 }
 
-/// This struct is a smaller range than `ops::Range<u8>` for `IntoIter8`,
-/// considering the values will only ever go up to 8.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-#[repr(transparent)]
-struct PackedU8Range(u8);
-
-impl PackedU8Range {
-    #[inline]
-    fn new(start: u8, end: u8) -> Self {
-        Self((start << 4) | (end - 1))
+impl PackedBools8 {
+    /// Returns a Braille character with a dot for each true value.
+    ///
+    /// See [`Braille`](crate::Braille) for how the values are laid out.
+    pub const fn to_braille(self) -> char {
+        crate::braille::braille_char(self.0)
     }
 
-    #[inline]
-    fn get_start(&self) -> u8 {
-        self.0 >> 4
+    /// Returns a byte for each value, which is 0xFF if the value is true and 0x00 if it is false.
+    ///
+    /// This is the layout of SIMD comparison results and select masks.
+    pub const fn to_mask_bytes(&self) -> [u8; 8] {
+        const LSB: u64 = 0x0101_0101_0101_0101;
+        // copy the byte into every lane, and keep only bit i in lane i
+        let spread = (self.0 as u64 * LSB) & 0x8040_2010_0804_0201;
+        // adding 0x7f to each lane sets its top bit if any other bit was set
+        let ones = ((spread + 0x7f * LSB) >> 7) & LSB;
+        (ones * 0xff).to_le_bytes()
     }
 
-    #[inline]
-    fn get_end(&self) -> u8 {
-        self.0 & 0b00001111
+    /// Creates a new `PackedBools8` from the top bit of each byte,
+    /// like the `movemask` SIMD instructions.
+    ///
+    /// This is the inverse of [`to_mask_bytes`](Self::to_mask_bytes).
+    pub const fn from_mask_bytes(bytes: [u8; 8]) -> Self {
+        let tops = (u64::from_le_bytes(bytes) >> 7) & 0x0101_0101_0101_0101;
+        // the multiplication moves the low bit of lane i to bit 56 + i,
+        // with no carries as the partial products never overlap
+        Self((tops.wrapping_mul(0x0102_0408_1020_4080) >> 56) as u8)
     }
 
-    /// Note: this method does no guarding against overflows.
-    #[inline]
-    fn add_to_start(&mut self, val: u8) {
-        self.0 += val << 4
+    /// Views a slice of `PackedBools8` as a slice of bytes.
+    pub fn slice_as_bytes(slice: &[Self]) -> &[u8] {
+        // SAFETY: `PackedBools8` is `repr(transparent)` over `u8`
+        unsafe { core::slice::from_raw_parts(slice.as_ptr().cast(), slice.len()) }
     }
 
-    /// Note: this method does no guarding against underflows.
-    #[inline]
-    fn sub_from_end(&mut self, val: u8) {
-        self.0 -= val
+    /// Views a mutable slice of `PackedBools8` as a mutable slice of bytes.
+    pub fn slice_as_bytes_mut(slice: &mut [Self]) -> &mut [u8] {
+        // SAFETY: `PackedBools8` is `repr(transparent)` over `u8`
+        unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len()) }
     }
 
-    fn iter_next(&mut self) -> Option<u8> {
-        let start = self.get_start();
-        if self.0 < 0b11110000 && start <= self.get_end() {
-            self.add_to_start(1);
-            Some(start)
-        } else {
-            None
-        }
+    /// Views a slice of bytes as a slice of `PackedBools8`.
+    pub fn slice_from_bytes(bytes: &[u8]) -> &[Self] {
+        // SAFETY: `PackedBools8` is `repr(transparent)` over `u8`, and every bit pattern is valid
+        unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len()) }
     }
 
-    fn iter_next_back(&mut self) -> Option<u8> {
-        let end = self.get_end();
-        if end > 0 && self.get_start() <= end {
-            self.sub_from_end(1);
-            Some(end)
-        } else {
-            None
-        }
-    }
-
-    #[inline]
-    fn len(&self) -> u8 {
-        (self.get_end() + 1) - self.get_start()
+    /// Views a mutable slice of bytes as a mutable slice of `PackedBools8`.
+    pub fn slice_from_bytes_mut(bytes: &mut [u8]) -> &mut [Self] {
+        // SAFETY: `PackedBools8` is `repr(transparent)` over `u8`, and every bit pattern is valid
+        unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), bytes.len()) }
     }
 }
 
 /// An iterator over the booleans in a `PackedBools8`.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct IntoIter8 {
     bools: PackedBools8,
-    range: PackedU8Range,
+    range: MiniRange8,
 }
 
 // Can't even deprecate a trait impl. FIXME REMEMBER TO REMOVE THIS!
@@ -103,16 +103,28 @@ impl IntoIter8 {
     fn new(bools: PackedBools8) -> Self {
         Self {
             bools,
-            range: PackedU8Range::new(0, 8),
+            range: MiniRange8::new(0, 8),
         }
     }
+
+    /// Returns the next boolean without advancing the iterator.
+    pub fn peek(&self) -> Option<bool> {
+        let mut range = self.range;
+        range.next().map(|idx| self.bools.get(idx))
+    }
+
+    /// Returns the next boolean from the back without advancing the iterator.
+    pub fn peek_back(&self) -> Option<bool> {
+        let mut range = self.range;
+        range.next_back().map(|idx| self.bools.get(idx))
+    }
 }
 
 impl Iterator for IntoIter8 {
     type Item = bool;
 
     fn next(&mut self) -> Option<bool> {
-        self.range.iter_next().map(|idx| self.bools.get(idx))
+        self.range.next().map(|idx| self.bools.get(idx))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -121,9 +133,7 @@ impl Iterator for IntoIter8 {
     }
 
     fn nth(&mut self, n: usize) -> Option<bool> {
-        let n = u8::try_from(n).ok().filter(|&n| n < self.range.len())?;
-        self.range.add_to_start(n);
-        self.next()
+        self.range.nth(n).map(|idx| self.bools.get(idx))
     }
 
     fn last(mut self) -> Option<bool> {
@@ -133,13 +143,11 @@ impl Iterator for IntoIter8 {
 
 impl DoubleEndedIterator for IntoIter8 {
     fn next_back(&mut self) -> Option<bool> {
-        self.range.iter_next_back().map(|idx| self.bools.get(idx))
+        self.range.next_back().map(|idx| self.bools.get(idx))
     }
 
     fn nth_back(&mut self, n: usize) -> Option<bool> {
-        let n = u8::try_from(n).ok().filter(|&n| n < self.range.len())?;
-        self.range.sub_from_end(n);
-        self.next_back()
+        self.range.nth_back(n).map(|idx| self.bools.get(idx))
     }
 }
 
@@ -151,12 +159,81 @@ impl ExactSizeIterator for IntoIter8 {
 
 impl FusedIterator for IntoIter8 {}
 
+/// Keeps the format `IntoIter8` had before it used a `MiniRange8`,
+/// so iterators serialized by older versions still deserialize to the same position.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::IntoIter8;
+    use crate::{MiniRange8, PackedBools8};
+
+    /// The range, with the start in the high 4 bits and the last index in the low 4 bits.
+    ///
+    /// An end of 0 has no last index, so it is stored as a last index of 15,
+    /// which older versions never wrote.
+    #[derive(Serialize, Deserialize)]
+    struct PackedU8Range(u8);
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "IntoIter8")]
+    struct Repr {
+        bools: PackedBools8,
+        range: PackedU8Range,
+    }
+
+    pub(super) fn to_wire(range: MiniRange8) -> u8 {
+        (range.start() << 4) | (range.end().wrapping_sub(1) & 0x0f)
+    }
+
+    pub(super) fn from_wire(byte: u8) -> Option<MiniRange8> {
+        let end = match byte & 0x0f {
+            0x0f => 0,
+            last => last + 1,
+        };
+        (end <= 8).then(|| MiniRange8::new(byte >> 4, end))
+    }
+
+    impl Serialize for IntoIter8 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Repr { bools: self.bools, range: PackedU8Range(to_wire(self.range)) }.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for IntoIter8 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let Repr { bools, range: PackedU8Range(byte) } = Repr::deserialize(deserializer)?;
+            let range = from_wire(byte).ok_or_else(|| D::Error::custom("an IntoIter8 range cannot end past index 8"))?;
+            Ok(IntoIter8 { bools, range })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate alloc;
     use alloc::format;
 
-    use super::PackedBools8;
+    use super::{PackedBools8, PackedBools8Builder};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn iter_wire_format() {
+        use super::serde_impls::{from_wire, to_wire};
+        use crate::MiniRange8;
+
+        // what older versions wrote for a new iterator, and after taking 3 from the front and 1 from the back
+        assert_eq!(from_wire(0x07), Some(MiniRange8::new(0, 8)));
+        assert_eq!(from_wire(0x36), Some(MiniRange8::new(3, 7)));
+        assert_eq!(from_wire(0x09), None);
+        for start in 0..=8 {
+            for end in 0..=8 {
+                let range = MiniRange8::new(start, end);
+                assert_eq!(from_wire(to_wire(range)), Some(range));
+            }
+        }
+    }
 
     #[test]
     fn set_get() {
@@ -182,6 +259,239 @@ mod tests {
         assert_eq!(pkd, PackedBools8::new_vals(arr));
     }
 
+    #[test]
+    fn builder() {
+        const PKD: PackedBools8 = PackedBools8::builder()
+            .set(3)
+            .set_range(4..7)
+            .clear(5)
+            .toggle(0)
+            .build();
+        assert_eq!(PKD, PackedBools8::from_bits(0b0101_1001));
+        assert_eq!(PackedBools8::builder().set_range(0..8).clear_range(2..2).build(), !PackedBools8::new());
+        assert_eq!(PackedBools8::builder().set_range(0..8).clear_range(1..3).build().to_bits(), 0b1111_1001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_out_of_range() {
+        let _ = PackedBools8::builder().set_range(6..9);
+    }
+
+    #[test]
+    fn slice_casts() {
+        let mut pkds = [PackedBools8::from_bits(0x12), PackedBools8::from_bits(0x34)];
+        assert_eq!(PackedBools8::slice_as_bytes(&pkds), &[0x12, 0x34]);
+        PackedBools8::slice_as_bytes_mut(&mut pkds)[1] = 0x56;
+        assert_eq!(pkds[1], PackedBools8::from_bits(0x56));
+
+        let mut bytes = [0xab, 0xcd];
+        assert_eq!(PackedBools8::slice_from_bytes(&bytes)[0], PackedBools8::from_bits(0xab));
+        PackedBools8::slice_from_bytes_mut(&mut bytes)[0].set(false, 0);
+        assert_eq!(bytes, [0xaa, 0xcd]);
+    }
+
+    #[test]
+    fn int_ops() {
+        let mut pkd = PackedBools8::from_bits(0b1100_1010);
+        assert_eq!((pkd & 0x0f).to_bits(), 0b0000_1010);
+        assert_eq!((&pkd | 0x01).to_bits(), 0b1100_1011);
+        assert_eq!((pkd ^ 0xff).to_bits(), 0b0011_0101);
+        pkd &= 0xf0;
+        pkd |= 0b11;
+        pkd ^= 0b1000_0001;
+        assert_eq!(pkd.to_bits(), 0b0100_0010);
+    }
+
+    #[test]
+    fn array_ops() {
+        const T: bool = true;
+        const F: bool = false;
+        let mut pkd = PackedBools8::from_bits(0b0000_1111);
+        assert_eq!((pkd & [T, F, T, F, T, F, T, F]).to_bits(), 0b0000_0101);
+        assert_eq!((pkd | [F, F, F, F, F, F, F, T]).to_bits(), 0b1000_1111);
+        pkd ^= [T; 8];
+        assert_eq!(pkd.to_bits(), 0b1111_0000);
+    }
+
+    #[test]
+    fn eq_arrays_and_ints() {
+        let pkd = PackedBools8::from_bits(0b0000_0101);
+        assert_eq!(pkd, [true, false, true, false, false, false, false, false]);
+        assert_eq!([true, false, true, false, false, false, false, false], pkd);
+        assert_ne!(pkd, [false; 8]);
+        assert_eq!(pkd, 0b0000_0101);
+        assert_eq!(0b0000_0101, pkd);
+        assert_ne!(pkd, 0);
+    }
+
+    #[test]
+    fn masks() {
+        let a = PackedBools8::from_bits(0b1100_1010);
+        let b = PackedBools8::from_bits(0b1010_1100);
+        assert_eq!(a.eq_mask(b), 0b1001_1001);
+        assert_eq!(a.ne_mask(b), 0b0110_0110);
+        assert_eq!(a.eq_mask(a), !PackedBools8::new());
+    }
+
+    #[test]
+    fn ternary() {
+        let a = PackedBools8::from_bits(0b1111_0000);
+        let b = PackedBools8::from_bits(0b1100_1100);
+        let c = PackedBools8::from_bits(0b1010_1010);
+        assert_eq!(PackedBools8::majority(a, b, c), 0b1110_1000);
+        assert_eq!(PackedBools8::ternary_table(a, b, c, 0b1110_1000), 0b1110_1000);
+        assert_eq!(PackedBools8::ternary_table(a, b, c, 0b1001_0110), 0b1001_0110);
+        assert_eq!(PackedBools8::ternary(a, b, c, |a, b, c| if a { b } else { c }), 0b1100_1010);
+        assert_eq!(PackedBools8::ternary(a, b, c, |_, _, _| false), 0);
+    }
+
+    #[test]
+    fn binary_op() {
+        use crate::BinaryBoolOp;
+        let a = PackedBools8::from_bits(0b1100_1010);
+        let b = PackedBools8::from_bits(0b1010_0110);
+        assert_eq!(a.apply_binary_op(b, BinaryBoolOp::AND), a & b);
+        assert_eq!(a.apply_binary_op(b, BinaryBoolOp::XNOR), !(a ^ b));
+        assert_eq!(a.apply_binary_op(b, BinaryBoolOp::A_AND_NOT_B), a & !b);
+        assert_eq!(a.apply_binary_op(b, BinaryBoolOp::FALSE), 0);
+        assert_eq!(a.apply_binary_op(b, BinaryBoolOp::B), b);
+    }
+
+    #[test]
+    fn count_matching() {
+        let a = PackedBools8::from_bits(0b1100_1010);
+        let b = PackedBools8::from_bits(0b1010_1100);
+        assert_eq!(a.count_matching(b), 4);
+        assert_eq!(a.count_matching(a), 8);
+        assert_eq!(a.count_matching(!a), 0);
+    }
+
+    #[test]
+    fn widen() {
+        let pkd = PackedBools8::from_bits(0xa5);
+        assert_eq!(u16::from(pkd), 0xa5);
+        assert_eq!(u32::from(pkd), 0xa5);
+        assert_eq!(u64::from(pkd), 0xa5);
+        assert_eq!(u128::from(!pkd), 0x5a);
+    }
+
+    #[test]
+    fn checked_index() {
+        use crate::BitIndex8;
+        assert_eq!(BitIndex8::new(8), None);
+        let idx = BitIndex8::new(5).unwrap();
+        let mut pkd = PackedBools8::new();
+        pkd.set_at(true, idx);
+        assert!(pkd.get_at(idx));
+        pkd.toggle_at(BitIndex8::MAX);
+        pkd.set_at(false, idx);
+        assert_eq!(pkd, 0b1000_0000);
+        assert_eq!(u8::from(idx), 5);
+    }
+
+    #[test]
+    fn modify() {
+        let mut pkd = PackedBools8::from_bits(0b1010);
+        pkd.modify(|val| !val & 0x0f);
+        assert_eq!(pkd, 0b0000_0101);
+    }
+
+    #[test]
+    fn replace() {
+        let mut pkd = PackedBools8::new();
+        assert!(!pkd.set_replace(true, 2));
+        assert!(pkd.set_replace(false, 2));
+        assert_eq!(pkd.try_set_replace(true, 8), None);
+        assert_eq!(pkd.try_set_replace(true, 7), Some(false));
+        assert!(pkd.toggle_get(7));
+        assert!(!pkd.toggle_get(0));
+        assert_eq!(pkd.try_toggle_get(8), None);
+        assert_eq!(pkd.to_bits(), 0b0000_0001);
+    }
+
+    #[test]
+    fn find_from() {
+        let pkd = PackedBools8::from_bits(0b0100_1010);
+        assert_eq!(pkd.find_true_from(0), Some(1));
+        assert_eq!(pkd.find_true_from(2), Some(3));
+        assert_eq!(pkd.find_true_from(3), Some(3));
+        assert_eq!(pkd.find_true_from(7), None);
+        assert_eq!(pkd.find_true_from(200), None);
+        assert_eq!(pkd.find_false_from(1), Some(2));
+        assert_eq!(pkd.find_false_from(6), Some(7));
+
+        assert_eq!(pkd.rfind_true_from(7), Some(6));
+        assert_eq!(pkd.rfind_true_from(5), Some(3));
+        assert_eq!(pkd.rfind_true_from(0), None);
+        assert_eq!(pkd.rfind_true_from(200), Some(6));
+        assert_eq!(pkd.rfind_false_from(6), Some(5));
+        assert_eq!(pkd.rfind_false_from(1), Some(0));
+        assert_eq!((!PackedBools8::new()).rfind_false_from(7), None);
+    }
+
+    #[test]
+    fn next_prev() {
+        let pkd = PackedBools8::from_bits(0b1000_1001);
+        assert_eq!(pkd.next_true_after(0), Some(3));
+        assert_eq!(pkd.next_true_after(3), Some(7));
+        assert_eq!(pkd.next_true_after(7), None);
+        assert_eq!(pkd.prev_true_before(7), Some(3));
+        assert_eq!(pkd.prev_true_before(3), Some(0));
+        assert_eq!(pkd.prev_true_before(0), None);
+        assert_eq!(pkd.prev_true_before(200), Some(7));
+    }
+
+    #[test]
+    fn gather_scatter() {
+        let pkd = PackedBools8::from_bits(0b1010_0110);
+        let mut out = [false; 4];
+        pkd.gather(&[7, 0, 2, 2], &mut out);
+        assert_eq!(out, [true, false, true, true]);
+
+        let mut pkd = PackedBools8::new();
+        pkd.scatter(&[6, 1, 3, 6], &[true, true, false, false]);
+        assert_eq!(pkd, PackedBools8::from_bits(0b0000_0010));
+    }
+
+    #[test]
+    #[should_panic]
+    fn gather_out_of_range() {
+        PackedBools8::new().gather(&[8], &mut [false]);
+    }
+
+    #[test]
+    fn prefix_scans() {
+        for bits in 0..=u8::MAX {
+            let pkd = PackedBools8::from_bits(bits);
+            let (mut or, mut xor) = (false, false);
+            let mut expected_or = [false; 8];
+            let mut expected_xor = [false; 8];
+            for (idx, b) in pkd.into_iter().enumerate() {
+                or |= b;
+                xor ^= b;
+                expected_or[idx] = or;
+                expected_xor[idx] = xor;
+            }
+            assert_eq!(pkd.prefix_or().get_all(), expected_or);
+            assert_eq!(pkd.prefix_xor().get_all(), expected_xor);
+        }
+    }
+
+    #[test]
+    fn increment() {
+        let mut pkd = PackedBools8::new();
+        let mut count = 1;
+        while let Some(next) = pkd.checked_increment() {
+            assert_eq!(next.checked_decrement(), Some(pkd));
+            pkd = next;
+            count += 1;
+        }
+        assert_eq!(count, 256);
+        assert_eq!(pkd, !PackedBools8::new());
+        assert_eq!(PackedBools8::new().checked_decrement(), None);
+    }
+
     #[test]
     fn formatting() {
         // formats like 11010100
@@ -197,6 +507,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn iter() {
         let pkd = PackedBools8::new();
         assert_eq!(pkd.into_iter().len(), 8);
@@ -211,6 +522,91 @@ mod tests {
             .for_each(|(b1, b2)| assert_eq!(b1, b2));
     }
 
+    #[test]
+    fn const_counts() {
+        const DEFAULTS: PackedBools8 = PackedBools8::from_bits(0b0010_0101);
+        const _: () = assert!(DEFAULTS.count_true() == 3 && DEFAULTS.count_false() == 5);
+        const _: () = assert!(DEFAULTS.any() && !DEFAULTS.all() && !DEFAULTS.none());
+        assert!(PackedBools8::new().none());
+        assert!(PackedBools8::from_bits(0xff).all());
+        assert_eq!(DEFAULTS.count_matching(PackedBools8::new()), 5);
+    }
+
+    #[test]
+    fn const_indices() {
+        const BITS: PackedBools8 = {
+            let mut bits = PackedBools8::new();
+            bits.set_const::<7>(true);
+            bits.toggle_const::<2>();
+            bits
+        };
+        assert_eq!(BITS.to_bits(), 0b1000_0100);
+        assert!(BITS.get_const::<2>() && !BITS.get_const::<3>());
+        let mut bits = BITS;
+        bits.set_const::<7>(false);
+        bits.toggle_const::<2>();
+        assert!(bits.none());
+    }
+
+    #[test]
+    fn compound_ops() {
+        let a = PackedBools8::from_bits(0b1100);
+        let b = PackedBools8::from_bits(0b1010);
+        assert_eq!(a.nand(b).to_bits(), 0b1111_0111);
+        assert_eq!(a.nor(b).to_bits(), 0b1111_0001);
+        assert_eq!(a.xnor(b).to_bits(), 0b1111_1001);
+        assert_eq!(a.and_not(b).to_bits(), 0b0100);
+        assert_eq!(a.and_not(b), a & !b);
+    }
+
+    #[test]
+    fn mask_bytes() {
+        let pkd = PackedBools8::from_bits(0b1000_0101);
+        assert_eq!(pkd.to_mask_bytes(), [0xff, 0, 0xff, 0, 0, 0, 0, 0xff]);
+        assert_eq!(PackedBools8::from_mask_bytes(pkd.to_mask_bytes()), pkd);
+        assert_eq!(PackedBools8::from_mask_bytes([0x80, 0x7f, 0xff, 0, 1, 0x81, 0, 0xc0]).to_bits(), 0b1010_0101);
+        for bits in 0..=255 {
+            let pkd = PackedBools8::from_bits(bits);
+            assert_eq!(PackedBools8::from_mask_bytes(pkd.to_mask_bytes()), pkd);
+        }
+    }
+
+    #[test]
+    fn const_accessors() {
+        const TABLE: [bool; 8] = {
+            let pkd = PackedBools8::new().with(true, 1).with(true, 6).with(false, 1);
+            let mut table = [false; 8];
+            let mut idx = 0;
+            while idx < 8 {
+                table[idx as usize] = pkd.get(idx);
+                idx += 1;
+            }
+            table
+        };
+        const TOGGLED: PackedBools8 = {
+            let mut pkd = PackedBools8::from_bits(0b11);
+            pkd.toggle(0);
+            pkd
+        };
+        assert_eq!(TABLE, [false, false, false, false, false, false, true, false]);
+        assert_eq!(TOGGLED.to_bits(), 0b10);
+        assert_eq!(const { PackedBools8::new().try_get(8) }, None);
+    }
+
+    #[test]
+    fn peek() {
+        let mut iter = PackedBools8::from_bits(0b1000_0110).into_iter();
+        assert_eq!(iter.peek(), Some(false));
+        assert_eq!(iter.peek_back(), Some(true));
+        assert_eq!(iter.next(), Some(false));
+        assert_eq!(iter.peek(), Some(true));
+        assert_eq!(iter.next_back(), Some(true));
+        assert_eq!(iter.peek_back(), Some(false));
+        assert_eq!(iter.len(), 6);
+        iter.by_ref().for_each(drop);
+        assert_eq!((iter.peek(), iter.peek_back()), (None, None));
+    }
+
     #[test]
     fn iter_back() {
         let arr = [true, false, false, true, true, false, false, false];
@@ -221,6 +617,12 @@ mod tests {
             .for_each(|(b1, b2)| assert_eq!(b1, b2));
     }
 
+    #[test]
+    fn iter_back_full() {
+        let arr = [true, false, false, true, true, false, false, false];
+        assert_eq!(PackedBools8::from(arr).into_iter().rev().count(), 8);
+    }
+
     #[test]
     #[allow(clippy::iter_nth_zero)]
     fn iter_nth() {
@@ -236,4 +638,28 @@ mod tests {
         assert_eq!(iter.nth(12), None);
         assert_eq!(iter.nth_back(100), None);
     }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 9 but the capacity is 8")]
+    fn get_out_of_range() {
+        PackedBools8::new().get(9);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 8 but the capacity is 8")]
+    fn set_out_of_range() {
+        PackedBools8::new().set(true, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 255 but the capacity is 8")]
+    fn toggle_out_of_range() {
+        PackedBools8::new().toggle(255);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 12 but the capacity is 8")]
+    fn builder_index_out_of_range() {
+        let _ = PackedBools8Builder::new().set(12);
+    }
 }
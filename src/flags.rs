@@ -0,0 +1,272 @@
+//! Named flag sets built on top of the packed types.
+
+/// Defines a flag set type with named flags, backed by a packed type.
+///
+/// Each flag is declared with the index of the boolean it occupies.
+/// The generated type has a constant for each flag,
+/// set operations (`contains`, `union`, `insert`, ...),
+/// the `|`, `&`, `^`, `-` and `!` operators,
+/// and a `Debug` impl that prints the names of the set flags.
+///
+/// ```
+/// use packed_booleans::{packed_flags, PackedBools8};
+///
+/// packed_flags! {
+///     /// Permissions on a file.
+///     pub struct Perms: PackedBools8 {
+///         const READ = 0;
+///         const WRITE = 1;
+///         const EXEC = 2;
+///     }
+/// }
+///
+/// let perms = Perms::READ | Perms::EXEC;
+/// assert!(perms.contains(Perms::READ));
+/// assert!(!perms.contains(Perms::WRITE));
+/// assert_eq!(format!("{perms:?}"), "Perms(READ | EXEC)");
+/// ```
+#[macro_export]
+macro_rules! packed_flags {
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident: $inner:ty {
+            $(
+                $(#[$flag_attr:meta])*
+                const $flag:ident = $idx:expr;
+            )*
+        }
+    ) => {
+        $(#[$outer])*
+        #[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+        #[repr(transparent)]
+        $vis struct $name($inner);
+
+        #[allow(dead_code)]
+        impl $name {
+            $(
+                $(#[$flag_attr])*
+                pub const $flag: Self = Self(<$inner>::from_bits(1 << $idx));
+            )*
+
+            /// Returns a value with no flags set.
+            pub const fn empty() -> Self {
+                Self(<$inner>::new())
+            }
+
+            /// Returns a value with every named flag set.
+            pub const fn all() -> Self {
+                Self(<$inner>::from_bits(0 $(| Self::$flag.0.to_bits())*))
+            }
+
+            /// Creates a value from its packed form, keeping any unnamed bits.
+            pub const fn from_packed(packed: $inner) -> Self {
+                Self(packed)
+            }
+
+            /// Returns the packed form of this value.
+            pub const fn to_packed(self) -> $inner {
+                self.0
+            }
+
+            /// Returns true if no flags are set.
+            pub const fn is_empty(self) -> bool {
+                self.0.to_bits() == 0
+            }
+
+            /// Returns true if every named flag is set.
+            pub const fn is_all(self) -> bool {
+                self.contains(Self::all())
+            }
+
+            /// Returns true if every flag in `other` is set in `self`.
+            pub const fn contains(self, other: Self) -> bool {
+                self.0.to_bits() & other.0.to_bits() == other.0.to_bits()
+            }
+
+            /// Returns true if any flag in `other` is set in `self`.
+            pub const fn intersects(self, other: Self) -> bool {
+                self.0.to_bits() & other.0.to_bits() != 0
+            }
+
+            /// Returns the flags set in either value.
+            pub const fn union(self, other: Self) -> Self {
+                Self(<$inner>::from_bits(self.0.to_bits() | other.0.to_bits()))
+            }
+
+            /// Returns the flags set in both values.
+            pub const fn intersection(self, other: Self) -> Self {
+                Self(<$inner>::from_bits(self.0.to_bits() & other.0.to_bits()))
+            }
+
+            /// Returns the flags set in `self` but not in `other`.
+            pub const fn difference(self, other: Self) -> Self {
+                Self(<$inner>::from_bits(self.0.to_bits() & !other.0.to_bits()))
+            }
+
+            /// Returns the flags set in exactly one of the values.
+            pub const fn symmetric_difference(self, other: Self) -> Self {
+                Self(<$inner>::from_bits(self.0.to_bits() ^ other.0.to_bits()))
+            }
+
+            /// Returns the named flags that are not set in `self`.
+            pub const fn complement(self) -> Self {
+                Self::all().difference(self)
+            }
+
+            /// Sets the flags in `other`.
+            pub fn insert(&mut self, other: Self) {
+                *self = self.union(other);
+            }
+
+            /// Clears the flags in `other`.
+            pub fn remove(&mut self, other: Self) {
+                *self = self.difference(other);
+            }
+
+            /// Toggles the flags in `other`.
+            pub fn toggle(&mut self, other: Self) {
+                *self = self.symmetric_difference(other);
+            }
+
+            /// Sets or clears the flags in `other`, depending on `val`.
+            pub fn set(&mut self, other: Self, val: bool) {
+                match val {
+                    true => self.insert(other),
+                    false => self.remove(other),
+                }
+            }
+        }
+
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+            fn bitor(self, rhs: Self) -> Self { self.union(rhs) }
+        }
+
+        impl ::core::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) { self.insert(rhs) }
+        }
+
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+            fn bitand(self, rhs: Self) -> Self { self.intersection(rhs) }
+        }
+
+        impl ::core::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, rhs: Self) { *self = self.intersection(rhs) }
+        }
+
+        impl ::core::ops::BitXor for $name {
+            type Output = Self;
+            fn bitxor(self, rhs: Self) -> Self { self.symmetric_difference(rhs) }
+        }
+
+        impl ::core::ops::BitXorAssign for $name {
+            fn bitxor_assign(&mut self, rhs: Self) { self.toggle(rhs) }
+        }
+
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self { self.difference(rhs) }
+        }
+
+        impl ::core::ops::SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) { self.remove(rhs) }
+        }
+
+        impl ::core::ops::Not for $name {
+            type Output = Self;
+            fn not(self) -> Self { self.complement() }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            #[allow(unused_assignments)]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(concat!(stringify!($name), "("))?;
+                let mut first = true;
+                $(
+                    if self.contains(Self::$flag) {
+                        if !first {
+                            f.write_str(" | ")?;
+                        }
+                        first = false;
+                        f.write_str(stringify!($flag))?;
+                    }
+                )*
+                let unnamed = self.0.to_bits() & !Self::all().0.to_bits();
+                if unnamed != 0 {
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+                    first = false;
+                    write!(f, "{:#x}", unnamed)?;
+                }
+                if first {
+                    f.write_str("empty")?;
+                }
+                f.write_str(")")
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use crate::{PackedBools8, PackedBools16};
+
+    crate::packed_flags! {
+        struct Perms: PackedBools8 {
+            const READ = 0;
+            const WRITE = 1;
+            const EXEC = 2;
+        }
+    }
+
+    crate::packed_flags! {
+        struct Status: PackedBools16 {
+            const READY = 0;
+            const ERROR = 15;
+        }
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(Perms::READ.to_packed(), PackedBools8::from_bits(0b001));
+        assert_eq!(Perms::EXEC.to_packed(), PackedBools8::from_bits(0b100));
+        assert_eq!(Perms::all().to_packed(), PackedBools8::from_bits(0b111));
+        assert_eq!(Status::ERROR.to_packed(), PackedBools16::from_bits(0x8000));
+        assert!(Perms::empty().is_empty());
+        assert!(Perms::all().is_all());
+    }
+
+    #[test]
+    fn set_ops() {
+        let mut perms = Perms::READ | Perms::WRITE;
+        assert!(perms.contains(Perms::READ));
+        assert!(!perms.contains(Perms::READ | Perms::EXEC));
+        assert!(perms.intersects(Perms::READ | Perms::EXEC));
+
+        perms.remove(Perms::WRITE);
+        assert_eq!(perms, Perms::READ);
+        perms.set(Perms::EXEC, true);
+        assert_eq!(perms, Perms::READ | Perms::EXEC);
+        perms ^= Perms::READ;
+        assert_eq!(perms, Perms::EXEC);
+        assert_eq!(!perms, Perms::READ | Perms::WRITE);
+        assert_eq!(Perms::all() - Perms::WRITE, Perms::READ | Perms::EXEC);
+        assert_eq!(Perms::all() & Perms::WRITE, Perms::WRITE);
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Perms::READ | Perms::EXEC), "Perms(READ | EXEC)");
+        assert_eq!(format!("{:?}", Perms::empty()), "Perms(empty)");
+        assert_eq!(format!("{:?}", Status::ERROR), "Status(ERROR)");
+
+        let unnamed = Perms::from_packed(PackedBools8::from_bits(0b1001_0000));
+        assert_eq!(format!("{:?}", unnamed), "Perms(0x90)");
+        assert_eq!(format!("{:?}", unnamed | Perms::WRITE), "Perms(WRITE | 0x90)");
+    }
+}
@@ -0,0 +1,81 @@
+//! Combining many packed values into one.
+
+/// The union (bitwise OR) of a sequence of packed values.
+///
+/// This implements `FromIterator` and `Sum`,
+/// so an iterator of packed values can be collected into their union.
+/// The union of no values has every value false.
+///
+/// ```
+/// use packed_booleans::{PackedBools8, Union};
+///
+/// let masks = [0b0001, 0b0100, 0b0101].map(PackedBools8::from_bits);
+/// let Union(all) = masks.into_iter().collect();
+/// assert_eq!(all, PackedBools8::from_bits(0b0101));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Union<T>(pub T);
+
+/// The intersection (bitwise AND) of a sequence of packed values.
+///
+/// This implements `FromIterator` and `Sum`,
+/// so an iterator of packed values can be collected into their intersection.
+/// The intersection of no values has every value true.
+///
+/// ```
+/// use packed_booleans::{Intersection, PackedBools8};
+///
+/// let masks = [0b0111, 0b0110, 0b1110].map(PackedBools8::from_bits);
+/// let common: Intersection<_> = masks.into_iter().collect();
+/// assert_eq!(common.into_inner(), PackedBools8::from_bits(0b0110));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Intersection<T>(pub T);
+
+impl<T> Union<T> {
+    /// Returns the combined value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Intersection<T> {
+    /// Returns the combined value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Intersection, Union};
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn union() {
+        let vals = [0b0001, 0b1000, 0b0001].map(PackedBools8::from_bits);
+        assert_eq!(vals.iter().collect::<Union<_>>(), Union(PackedBools8::from_bits(0b1001)));
+        assert_eq!(vals.into_iter().sum::<Union<_>>().into_inner(), PackedBools8::from_bits(0b1001));
+
+        let empty: Union<PackedBools16> = core::iter::empty::<PackedBools16>().collect();
+        assert_eq!(empty.0, PackedBools16::new());
+
+        let mut union = Union(PackedBools8::from_bits(0b1));
+        union.extend([PackedBools8::from_bits(0b10)]);
+        assert_eq!(union.0, PackedBools8::from_bits(0b11));
+    }
+
+    #[test]
+    fn intersection() {
+        let vals = [0x0ff0, 0x00ff, 0xf0f0].map(PackedBools16::from_bits);
+        assert_eq!(vals.iter().collect::<Intersection<_>>().0, PackedBools16::from_bits(0x00f0));
+        assert_eq!(vals.into_iter().sum::<Intersection<_>>().0, PackedBools16::from_bits(0x00f0));
+
+        let empty: Intersection<PackedBools8> = core::iter::empty::<PackedBools8>().collect();
+        assert_eq!(empty.0, !PackedBools8::new());
+
+        let mut intersection = Intersection(PackedBools8::from_bits(0b11));
+        intersection.extend([PackedBools8::from_bits(0b10)]);
+        assert_eq!(intersection.0, PackedBools8::from_bits(0b10));
+    }
+}
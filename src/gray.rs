@@ -0,0 +1,53 @@
+//! Visiting every combination of values in Gray code order.
+
+/// An iterator over every value of a packed type in Gray code order,
+/// so that consecutive values differ in exactly one boolean.
+///
+/// This is created by the `gray_codes` function on the packed types.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrayCodes<T> {
+    /// The binary counter whose Gray code is yielded next.
+    pub(crate) counter: Option<T>,
+}
+
+impl<T> GrayCodes<T> {
+    pub(crate) const fn new(start: T) -> Self {
+        Self { counter: Some(start) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn round_trip() {
+        for bits in 0..=u8::MAX {
+            let pkd = PackedBools8::from_bits(bits);
+            assert_eq!(pkd.to_gray().from_gray(), pkd);
+        }
+        for bits in (0..=u16::MAX).step_by(7) {
+            let pkd = PackedBools16::from_bits(bits);
+            assert_eq!(pkd.to_gray().from_gray(), pkd);
+        }
+        assert_eq!(PackedBools8::from_bits(0b0110).to_gray(), PackedBools8::from_bits(0b0101));
+    }
+
+    #[test]
+    fn gray_codes() {
+        let mut seen = [false; 256];
+        let mut prev: Option<PackedBools8> = None;
+        let codes = PackedBools8::gray_codes();
+        assert_eq!(codes.size_hint(), (256, Some(256)));
+        for code in codes {
+            assert!(!seen[usize::from(code.to_bits())]);
+            seen[usize::from(code.to_bits())] = true;
+            if let Some(prev) = prev {
+                assert_eq!((code ^ prev).count_true(), 1);
+            }
+            prev = Some(code);
+        }
+        assert!(seen.iter().all(|&b| b));
+        assert_eq!(PackedBools16::gray_codes().count(), 65536);
+    }
+}
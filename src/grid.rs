@@ -0,0 +1,635 @@
+//! Two-dimensional grids of packed booleans.
+
+use alloc::{vec, vec::Vec};
+
+use crate::morton;
+
+/// How cells past the edges of a grid are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum EdgeMode {
+    /// Cells past the edges are false.
+    #[default]
+    Zero,
+    /// The grid wraps around, so the cells past each edge are the cells on the opposite edge.
+    Wrap,
+    /// Cells past the edges have the value of the nearest cell on the edge.
+    Clamp,
+}
+
+/// A grid of booleans, packed into 64-bit words row by row.
+///
+/// Cell `(x, y)` is in column `x` and row `y`, with `(0, 0)` in a corner.
+///
+/// ```
+/// use packed_booleans::{BitGrid, EdgeMode};
+///
+/// // a blinker in Conway's Game of Life
+/// let mut grid = BitGrid::new(5, 5);
+/// for x in 1..4 {
+///     grid.set(true, x, 2);
+/// }
+/// let counts = grid.neighbor_counts(EdgeMode::Zero);
+/// assert_eq!(counts.get(2, 1), 3);
+/// assert_eq!(counts.get(2, 2), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// How many words each row takes up.
+    row_words: usize,
+    /// The rows, one after the other.
+    /// The bits past the width in the last word of each row are always false.
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a new grid of the given size with every cell false.
+    pub fn new(width: usize, height: usize) -> Self {
+        let row_words = width.div_ceil(64);
+        Self { width, height, row_words, words: vec![0; row_words * height] }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the cell at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.position(x, y);
+        (self.words[word] >> bit) & 1 != 0
+    }
+
+    /// Sets the cell at the given position to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn set(&mut self, val: bool, x: usize, y: usize) {
+        let (word, bit) = self.position(x, y);
+        match val {
+            true => self.words[word] |= 1 << bit,
+            false => self.words[word] &= !(1 << bit),
+        }
+    }
+
+    /// Counts how many true cells there are.
+    pub fn count_true(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Counts how many of the 8 cells around the given position are true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn count_neighbors(&self, x: usize, y: usize, edges: EdgeMode) -> u8 {
+        assert!(x < self.width && y < self.height, "The position must be inside the grid");
+        let mut count = 0;
+        for dy in [-1, 0, 1] {
+            for dx in [-1, 0, 1] {
+                if (dx, dy) == (0, 0) {
+                    continue;
+                }
+                let nx = edge_index(x, dx, self.width, edges);
+                let ny = edge_index(y, dy, self.height, edges);
+                if let (Some(nx), Some(ny)) = (nx, ny) {
+                    count += u8::from(self.get(nx, ny));
+                }
+            }
+        }
+        count
+    }
+
+    /// Counts the true neighbors of every cell at once.
+    ///
+    /// This adds up shifted copies of whole rows,
+    /// so it works on 64 cells at a time.
+    pub fn neighbor_counts(&self, edges: EdgeMode) -> NeighborCounts {
+        let mut planes: [BitGrid; 4] = core::array::from_fn(|_| Self::new(self.width, self.height));
+        let empty = vec![0; self.row_words];
+        let mut west = vec![0; self.row_words];
+        let mut east = vec![0; self.row_words];
+        for y in 0..self.height {
+            let neighbor_rows = [
+                edge_index(y, -1, self.height, edges),
+                Some(y),
+                edge_index(y, 1, self.height, edges),
+            ];
+            for (i, ny) in neighbor_rows.into_iter().enumerate() {
+                let row = ny.map_or(&empty[..], |ny| self.row(ny));
+                self.shift_row(row, &mut west, true, edges);
+                self.shift_row(row, &mut east, false, edges);
+                // the middle row itself isn't a neighbor
+                let inputs = [Some(&west[..]), (i != 1).then_some(row), Some(&east[..])];
+                for input in inputs.into_iter().flatten() {
+                    for (w, &bits) in input.iter().enumerate() {
+                        // a ripple carry add of one bit into the 4 bit planes
+                        let mut carry = bits;
+                        for plane in &mut planes {
+                            let word = &mut plane.words[y * self.row_words + w];
+                            let next = *word & carry;
+                            *word ^= carry;
+                            carry = next;
+                        }
+                    }
+                }
+            }
+        }
+        NeighborCounts { planes }
+    }
+
+    /// Returns the cells connected to the given position by steps up, down, left and right
+    /// through cells with the same value as it.
+    ///
+    /// This grows the region from the starting cell by whole rows at a time,
+    /// masking each step with the cells of the right value, until it stops changing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn connected_region(&self, x: usize, y: usize) -> BitGrid {
+        let mut mask = self.clone();
+        if !self.get(x, y) {
+            mask.words.iter_mut().for_each(|w| *w = !*w);
+            mask.clear_padding();
+        }
+        let mut region = Self::new(self.width, self.height);
+        region.set(true, x, y);
+
+        let rw = self.row_words;
+        let mut next = vec![0; rw];
+        let mut shifted = vec![0; rw];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            // sweeping down and then up spreads the region along whole columns in one pass
+            for y in (0..self.height).chain((0..self.height).rev()) {
+                loop {
+                    next.copy_from_slice(region.row(y));
+                    let above = y.checked_sub(1).map(|y| region.row(y));
+                    let below = (y + 1 < self.height).then(|| region.row(y + 1));
+                    for row in [above, below].into_iter().flatten() {
+                        next.iter_mut().zip(row).for_each(|(n, &r)| *n |= r);
+                    }
+                    for from_west in [true, false] {
+                        region.shift_row(region.row(y), &mut shifted, from_west, EdgeMode::Zero);
+                        next.iter_mut().zip(&shifted).for_each(|(n, &s)| *n |= s);
+                    }
+                    next.iter_mut().zip(mask.row(y)).for_each(|(n, &m)| *n &= m);
+                    if next == region.row(y) {
+                        break;
+                    }
+                    region.words[y * rw..(y + 1) * rw].copy_from_slice(&next);
+                    changed = true;
+                }
+            }
+        }
+        region
+    }
+
+    /// Flips the value of every cell in the [connected region](Self::connected_region) of the given position,
+    /// like the fill tool of a paint program.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn flood_fill(&mut self, x: usize, y: usize) {
+        let region = self.connected_region(x, y);
+        self.words.iter_mut().zip(&region.words).for_each(|(w, &r)| *w ^= r);
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.row_words..(y + 1) * self.row_words]
+    }
+
+    /// Writes `row` shifted by one column into `out`,
+    /// so that each cell holds its west neighbor if `from_west` is true,
+    /// and its east neighbor otherwise.
+    fn shift_row(&self, row: &[u64], out: &mut [u64], from_west: bool, edges: EdgeMode) {
+        if self.width == 0 {
+            return;
+        }
+        let last = self.width - 1;
+        let bit = |x: usize| (row[x / 64] >> (x % 64)) & 1;
+        if from_west {
+            // cell x gets cell x - 1, moving bits up
+            let mut carry = 0;
+            for (o, &w) in out.iter_mut().zip(row) {
+                *o = (w << 1) | carry;
+                carry = w >> 63;
+            }
+            let first = match edges {
+                EdgeMode::Zero => 0,
+                EdgeMode::Wrap => bit(last),
+                EdgeMode::Clamp => bit(0),
+            };
+            out[0] |= first;
+        } else {
+            // cell x gets cell x + 1, moving bits down
+            let mut carry = 0;
+            for (o, &w) in out.iter_mut().zip(row).rev() {
+                *o = (w >> 1) | carry;
+                carry = w << 63;
+            }
+            let fill = match edges {
+                EdgeMode::Zero => 0,
+                EdgeMode::Wrap => bit(0),
+                EdgeMode::Clamp => bit(last),
+            };
+            out[last / 64] = (out[last / 64] & !(1 << (last % 64))) | (fill << (last % 64));
+        }
+        // clear the bits that moved past the width
+        if !self.width.is_multiple_of(64) {
+            out[self.row_words - 1] &= (1 << (self.width % 64)) - 1;
+        }
+    }
+
+    /// Clears the bits past the width in the last word of each row.
+    fn clear_padding(&mut self) {
+        if !self.width.is_multiple_of(64) {
+            let mask = (1 << (self.width % 64)) - 1;
+            for row in self.words.chunks_mut(self.row_words) {
+                row[self.row_words - 1] &= mask;
+            }
+        }
+    }
+
+    fn position(&self, x: usize, y: usize) -> (usize, usize) {
+        assert!(x < self.width && y < self.height, "The position must be inside the grid");
+        (y * self.row_words + x / 64, x % 64)
+    }
+}
+
+/// Finds the index one step away from `idx`, following the edge mode.
+fn edge_index(idx: usize, step: isize, len: usize, edges: EdgeMode) -> Option<usize> {
+    match idx.checked_add_signed(step).filter(|&n| n < len) {
+        Some(n) => Some(n),
+        None => match edges {
+            EdgeMode::Zero => None,
+            EdgeMode::Wrap => Some(if step < 0 { len - 1 } else { 0 }),
+            EdgeMode::Clamp => Some(idx),
+        },
+    }
+}
+
+/// The number of true neighbors of every cell in a [`BitGrid`].
+///
+/// The counts are stored as 4 grids, each holding one bit of every count.
+/// This is created by [`BitGrid::neighbor_counts`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NeighborCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighborCounts {
+    /// Returns the number of true neighbors of the cell at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.planes.iter()
+            .enumerate()
+            .map(|(i, plane)| u8::from(plane.get(x, y)) << i)
+            .sum()
+    }
+
+    /// Returns a grid that is true where a cell has exactly `n` true neighbors.
+    pub fn equal_to(&self, n: u8) -> BitGrid {
+        let mut out = self.planes[0].clone();
+        for (w, word) in out.words.iter_mut().enumerate() {
+            *word = self.planes.iter()
+                .enumerate()
+                .fold(u64::MAX, |acc, (i, plane)| {
+                    let bits = plane.words[w];
+                    acc & if (n >> i) & 1 != 0 { bits } else { !bits }
+                });
+        }
+        // the padding bits are all zero counts, so clear them again
+        out.clear_padding();
+        out
+    }
+}
+
+/// A grid of booleans, packed into 64-bit words in Morton (Z) order.
+///
+/// Cell `(x, y)` is bit [`interleave_coords(x, y)`](morton::interleave_coords) of the words,
+/// so nearby cells are usually in the same word, and every aligned square
+/// with a power of two side is one run of bits.
+/// This makes it a good fit for quadtrees, which can check a whole square at once.
+///
+/// ```
+/// use packed_booleans::{BitGrid, MortonGrid};
+///
+/// let mut grid = BitGrid::new(6, 5);
+/// grid.set(true, 5, 1);
+/// let grid = MortonGrid::from(&grid);
+/// assert_eq!(grid.side(), 8);
+/// // only the top right quadrant has any true cells
+/// assert!(!grid.any_in_square(0, 0, 4));
+/// assert!(grid.any_in_square(4, 0, 4));
+/// assert_eq!(grid.count_in_square(4, 0, 2), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MortonGrid {
+    width: usize,
+    height: usize,
+    /// The cells of the smallest power of two square holding the grid, in Morton order.
+    /// The cells past the width or height are always false.
+    words: Vec<u64>,
+}
+
+impl MortonGrid {
+    /// The greatest width or height of a grid, which keeps every Morton code within a `usize`.
+    const MAX_SIDE: usize = 1 << (usize::BITS / 2 - 1);
+
+    /// Creates a new grid of the given size with every cell false.
+    ///
+    /// This allocates the whole power of two square holding the grid,
+    /// so the memory used grows with the square of the larger of the width and height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is greater than `2^(usize::BITS / 2 - 1)`,
+    /// which is `2^31` on 64-bit targets and `2^15` on 32-bit targets.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width.max(height) <= Self::MAX_SIDE, "The width and height cannot be greater than 2^(usize::BITS / 2 - 1)");
+        let side = width.max(height).next_power_of_two();
+        let cells = side.checked_mul(side).expect("The number of cells in the grid overflowed a usize");
+        Self { width, height, words: vec![0; cells.div_ceil(64)] }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the side of the smallest power of two square holding the grid,
+    /// which is the root square of a quadtree over it.
+    pub fn side(&self) -> usize {
+        self.width.max(self.height).next_power_of_two()
+    }
+
+    /// Gets the cell at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let idx = self.index(x, y);
+        (self.words[idx / 64] >> (idx % 64)) & 1 != 0
+    }
+
+    /// Sets the cell at the given position to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the grid.
+    pub fn set(&mut self, val: bool, x: usize, y: usize) {
+        let idx = self.index(x, y);
+        match val {
+            true => self.words[idx / 64] |= 1 << (idx % 64),
+            false => self.words[idx / 64] &= !(1 << (idx % 64)),
+        }
+    }
+
+    /// Counts how many true cells there are.
+    pub fn count_true(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Counts the true cells in the square of the given size with its corner at `(x, y)`.
+    ///
+    /// The cells of the square past the width or height are false.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is not a power of two, if `x` or `y` are not multiples of it,
+    /// or if the square goes past [`side`](Self::side).
+    pub fn count_in_square(&self, x: usize, y: usize, size: usize) -> usize {
+        self.square_words(x, y, size).map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns true if any cell is true in the square of the given size with its corner at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is not a power of two, if `x` or `y` are not multiples of it,
+    /// or if the square goes past [`side`](Self::side).
+    pub fn any_in_square(&self, x: usize, y: usize, size: usize) -> bool {
+        self.square_words(x, y, size).any(|w| w != 0)
+    }
+
+    /// Returns the words holding an aligned square, with the bits outside it cleared.
+    fn square_words(&self, x: usize, y: usize, size: usize) -> impl Iterator<Item = u64> + '_ {
+        assert!(size.is_power_of_two(), "The size of the square must be a power of two");
+        assert!(x.is_multiple_of(size) && y.is_multiple_of(size), "The square must be aligned to its size");
+        assert!(x + size <= self.side() && y + size <= self.side(), "The square cannot go past the side of the grid");
+        let start = morton::interleave_coords(x as u32, y as u32) as usize;
+        let end = start + size * size;
+        (start / 64..end.div_ceil(64)).map(move |w| {
+            let lo = start.max(w * 64) - w * 64;
+            let hi = end.min(w * 64 + 64) - w * 64;
+            self.words[w] & ((u64::MAX >> (64 - (hi - lo))) << lo)
+        })
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width && y < self.height, "The position must be inside the grid");
+        morton::interleave_coords(x as u32, y as u32) as usize
+    }
+}
+
+impl From<&BitGrid> for MortonGrid {
+    fn from(grid: &BitGrid) -> Self {
+        let mut out = MortonGrid::new(grid.width, grid.height);
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                if grid.get(x, y) {
+                    out.set(true, x, y);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl From<&MortonGrid> for BitGrid {
+    fn from(grid: &MortonGrid) -> Self {
+        let mut out = BitGrid::new(grid.width, grid.height);
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                if grid.get(x, y) {
+                    out.set(true, x, y);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::{BitGrid, EdgeMode, MortonGrid};
+
+    fn pattern(width: usize, height: usize) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                grid.set((x * 7 + y * 13) % 5 < 2, x, y);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn set_get() {
+        let mut grid = BitGrid::new(70, 3);
+        grid.set(true, 69, 2);
+        grid.set(true, 64, 0);
+        assert!(grid.get(69, 2));
+        assert!(!grid.get(68, 2));
+        assert_eq!(grid.count_true(), 2);
+        grid.set(false, 69, 2);
+        assert_eq!(grid.count_true(), 1);
+    }
+
+    #[test]
+    fn counts_match_single_cells() {
+        for (width, height) in [(1, 1), (3, 4), (64, 3), (70, 5), (130, 2)] {
+            let grid = pattern(width, height);
+            for edges in [EdgeMode::Zero, EdgeMode::Wrap, EdgeMode::Clamp] {
+                let counts = grid.neighbor_counts(edges);
+                for y in 0..height {
+                    for x in 0..width {
+                        let n = grid.count_neighbors(x, y, edges);
+                        assert_eq!(counts.get(x, y), n, "{width}x{height} {edges:?} ({x}, {y})");
+                        assert!(counts.equal_to(n).get(x, y));
+                    }
+                }
+                assert_eq!(counts.equal_to(9).count_true(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_modes() {
+        let mut grid = BitGrid::new(3, 3);
+        grid.set(true, 0, 0);
+        assert_eq!(grid.count_neighbors(2, 2, EdgeMode::Zero), 0);
+        assert_eq!(grid.count_neighbors(2, 2, EdgeMode::Wrap), 1);
+        // past the corner, the corner cell counts for 3 of its own neighbors
+        assert_eq!(grid.count_neighbors(0, 0, EdgeMode::Clamp), 3);
+        assert_eq!(grid.count_neighbors(1, 0, EdgeMode::Clamp), 2);
+    }
+
+    /// Finds a connected region one cell at a time.
+    fn naive_region(grid: &BitGrid, x: usize, y: usize) -> BitGrid {
+        let val = grid.get(x, y);
+        let mut region = BitGrid::new(grid.width(), grid.height());
+        let mut stack = alloc::vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if grid.get(x, y) != val || region.get(x, y) {
+                continue;
+            }
+            region.set(true, x, y);
+            if x > 0 { stack.push((x - 1, y)); }
+            if y > 0 { stack.push((x, y - 1)); }
+            if x + 1 < grid.width() { stack.push((x + 1, y)); }
+            if y + 1 < grid.height() { stack.push((x, y + 1)); }
+        }
+        region
+    }
+
+    #[test]
+    fn connected_regions() {
+        for (width, height) in [(1, 1), (5, 4), (64, 3), (70, 6), (130, 3)] {
+            let mut grid = pattern(width, height);
+            // a wall with a gap, so regions have to wind around it
+            for y in 0..height.saturating_sub(1) {
+                grid.set(true, width / 2, y);
+            }
+            for (x, y) in [(0, 0), (width - 1, height - 1), (width / 2, 0), (width / 3, height / 2)] {
+                assert_eq!(grid.connected_region(x, y), naive_region(&grid, x, y), "{width}x{height} ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn flood_fill() {
+        let mut grid = BitGrid::new(70, 3);
+        for y in 0..3 {
+            grid.set(true, 66, y);
+        }
+        grid.flood_fill(0, 0);
+        assert_eq!(grid.count_true(), 70 * 3 - 3 * 3);
+        assert!(!grid.get(69, 1));
+        grid.flood_fill(69, 1);
+        assert_eq!(grid.count_true(), 70 * 3);
+        grid.flood_fill(66, 1);
+        assert_eq!(grid.count_true(), 0);
+    }
+
+    #[test]
+    fn morton_grid() {
+        for (width, height) in [(1, 1), (5, 4), (6, 13), (64, 3), (70, 6)] {
+            let grid = pattern(width, height);
+            let morton = MortonGrid::from(&grid);
+            assert_eq!((morton.width(), morton.height()), (width, height));
+            assert_eq!(morton.count_true(), grid.count_true());
+            assert_eq!(BitGrid::from(&morton), grid);
+
+            let side = morton.side();
+            let mut size = side;
+            while size > 0 {
+                for y in (0..side).step_by(size) {
+                    for x in (0..side).step_by(size) {
+                        let expected = (y..(y + size).min(height))
+                            .flat_map(|y| (x..(x + size).min(width)).map(move |x| (x, y)))
+                            .filter(|&(x, y)| grid.get(x, y))
+                            .count();
+                        assert_eq!(morton.count_in_square(x, y, size), expected, "{width}x{height} ({x}, {y}) {size}");
+                        assert_eq!(morton.any_in_square(x, y, size), expected != 0);
+                    }
+                }
+                size /= 2;
+            }
+        }
+
+        let mut grid = MortonGrid::new(3, 9);
+        grid.set(true, 2, 8);
+        assert!(grid.get(2, 8));
+        grid.set(false, 2, 8);
+        assert_eq!(grid, MortonGrid::new(3, 9));
+    }
+
+    #[test]
+    #[should_panic]
+    fn morton_square_unaligned() {
+        MortonGrid::new(8, 8).count_in_square(2, 0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn morton_too_large() {
+        MortonGrid::new(1, MortonGrid::MAX_SIDE + 1);
+    }
+}
@@ -0,0 +1,45 @@
+//! Formatting packed values in readable groups of bits.
+
+/// A formatting adapter that displays the bits of a packed value in binary,
+/// with the most significant bit first and an underscore between every group of 4 bits.
+///
+/// With the alternate flag (`{:#}`), the output starts with `0b`.
+///
+/// ```
+/// use packed_booleans::PackedBools16;
+///
+/// let pkd = PackedBools16::from_bits(0b0010_1100_0111_0001);
+/// assert_eq!(pkd.grouped().to_string(), "0010_1100_0111_0001");
+/// assert_eq!(format!("{:#}", pkd.grouped()), "0b0010_1100_0111_0001");
+/// ```
+///
+/// This is created by the `grouped` method on the packed types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupedBits<T> {
+    pub(crate) value: T,
+}
+
+impl<T: Copy> GroupedBits<T> {
+    /// Returns the value being formatted.
+    pub const fn value(&self) -> T {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::{format, string::ToString};
+
+    use crate::{PackedBools8, PackedBools64};
+
+    #[test]
+    fn groups() {
+        assert_eq!(PackedBools8::from_bits(0b1101_0100).grouped().to_string(), "1101_0100");
+        assert_eq!(format!("{:#}", PackedBools8::new().grouped()), "0b0000_0000");
+        assert_eq!(
+            PackedBools64::from_bits(0xf000_0000_0000_0001).grouped().to_string(),
+            "1111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! A set of `u16` values that switches between sparse and dense storage.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::{fmt, iter::FusedIterator};
+
+const WORDS: usize = (u16::MAX as usize + 1) / 64;
+
+/// A set of `u16` values, stored as a sorted array when it is small
+/// and as a packed bitmap when it is large.
+///
+/// This is the container used by Roaring bitmaps.
+/// Sets with at most [`SPARSE_LIMIT`](Self::SPARSE_LIMIT) values use 2 bytes per value,
+/// and larger sets use 1 bit for every possible value.
+/// The set switches between the two as values are added and removed.
+///
+/// ```
+/// use packed_booleans::HybridSet;
+///
+/// let a: HybridSet = [1, 5, 900].into_iter().collect();
+/// let b: HybridSet = (0..5000).collect();
+/// assert!(!a.is_dense());
+/// assert!(b.is_dense());
+/// assert!(a.intersection(&b).iter().eq([1, 5, 900]));
+/// assert_eq!(a.union(&b).len(), 5000);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HybridSet {
+    repr: Repr,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    /// At most `HybridSet::SPARSE_LIMIT` sorted values.
+    Sparse(Vec<u16>),
+    /// More than `HybridSet::SPARSE_LIMIT` values, with their count.
+    Dense(Box<[u64]>, usize),
+}
+
+impl HybridSet {
+    /// The most values a set stores as a sorted array.
+    ///
+    /// At this size the array takes up as much memory as the bitmap,
+    /// which is 8 KiB.
+    pub const SPARSE_LIMIT: usize = 4096;
+
+    /// Creates a new, empty set.
+    pub const fn new() -> Self {
+        Self { repr: Repr::Sparse(Vec::new()) }
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Sparse(values) => values.len(),
+            Repr::Dense(_, len) => *len,
+        }
+    }
+
+    /// Returns true if the set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the set is stored as a bitmap.
+    pub fn is_dense(&self) -> bool {
+        matches!(self.repr, Repr::Dense(..))
+    }
+
+    /// Returns true if the set contains the value.
+    pub fn contains(&self, val: u16) -> bool {
+        match &self.repr {
+            Repr::Sparse(values) => values.binary_search(&val).is_ok(),
+            Repr::Dense(words, _) => words[usize::from(val / 64)] & (1 << (val % 64)) != 0,
+        }
+    }
+
+    /// Adds a value to the set, returning true if it wasn't already there.
+    pub fn insert(&mut self, val: u16) -> bool {
+        let inserted = match &mut self.repr {
+            Repr::Sparse(values) => match values.binary_search(&val) {
+                Ok(_) => false,
+                Err(idx) => {
+                    values.insert(idx, val);
+                    true
+                }
+            },
+            Repr::Dense(words, len) => {
+                let word = &mut words[usize::from(val / 64)];
+                let inserted = *word & (1 << (val % 64)) == 0;
+                *word |= 1 << (val % 64);
+                *len += usize::from(inserted);
+                inserted
+            }
+        };
+        self.normalize();
+        inserted
+    }
+
+    /// Removes a value from the set, returning true if it was there.
+    pub fn remove(&mut self, val: u16) -> bool {
+        let removed = match &mut self.repr {
+            Repr::Sparse(values) => match values.binary_search(&val) {
+                Ok(idx) => {
+                    values.remove(idx);
+                    true
+                }
+                Err(_) => false,
+            },
+            Repr::Dense(words, len) => {
+                let word = &mut words[usize::from(val / 64)];
+                let removed = *word & (1 << (val % 64)) != 0;
+                *word &= !(1 << (val % 64));
+                *len -= usize::from(removed);
+                removed
+            }
+        };
+        self.normalize();
+        removed
+    }
+
+    /// Removes every value from the set.
+    pub fn clear(&mut self) {
+        self.repr = Repr::Sparse(Vec::new());
+    }
+
+    /// Returns the set of values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let repr = match (&self.repr, &other.repr) {
+            (Repr::Sparse(a), Repr::Sparse(b)) => {
+                let mut values = Vec::with_capacity(a.len() + b.len());
+                let (mut i, mut j) = (0, 0);
+                while i < a.len() && j < b.len() {
+                    let (x, y) = (a[i], b[j]);
+                    values.push(x.min(y));
+                    i += usize::from(x <= y);
+                    j += usize::from(y <= x);
+                }
+                values.extend_from_slice(&a[i..]);
+                values.extend_from_slice(&b[j..]);
+                Repr::Sparse(values)
+            }
+            (Repr::Dense(words, _), Repr::Sparse(values)) | (Repr::Sparse(values), Repr::Dense(words, _)) => {
+                let mut words = words.clone();
+                for &val in values {
+                    words[usize::from(val / 64)] |= 1 << (val % 64);
+                }
+                Repr::Dense(words, 0)
+            }
+            (Repr::Dense(a, _), Repr::Dense(b, _)) => {
+                Repr::Dense(a.iter().zip(b.iter()).map(|(a, b)| a | b).collect(), 0)
+            }
+        };
+        Self::from_repr(repr)
+    }
+
+    /// Returns the set of values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let repr = match (&self.repr, &other.repr) {
+            (Repr::Sparse(a), Repr::Sparse(b)) => {
+                let mut values = Vec::new();
+                let (mut i, mut j) = (0, 0);
+                while i < a.len() && j < b.len() {
+                    let (x, y) = (a[i], b[j]);
+                    if x == y {
+                        values.push(x);
+                    }
+                    i += usize::from(x <= y);
+                    j += usize::from(y <= x);
+                }
+                Repr::Sparse(values)
+            }
+            (Repr::Dense(..), Repr::Sparse(values)) => {
+                Repr::Sparse(values.iter().copied().filter(|&val| self.contains(val)).collect())
+            }
+            (Repr::Sparse(values), Repr::Dense(..)) => {
+                Repr::Sparse(values.iter().copied().filter(|&val| other.contains(val)).collect())
+            }
+            (Repr::Dense(a, _), Repr::Dense(b, _)) => {
+                Repr::Dense(a.iter().zip(b.iter()).map(|(a, b)| a & b).collect(), 0)
+            }
+        };
+        Self::from_repr(repr)
+    }
+
+    /// Returns an iterator over the values in the set, in increasing order.
+    pub fn iter(&self) -> HybridIter<'_> {
+        let inner = match &self.repr {
+            Repr::Sparse(values) => IterRepr::Sparse(values.iter()),
+            Repr::Dense(words, len) => IterRepr::Dense { words, word: 0, bits: words[0], remaining: *len },
+        };
+        HybridIter { inner }
+    }
+
+    /// Creates a set, recounting dense values and choosing the right storage.
+    fn from_repr(mut repr: Repr) -> Self {
+        if let Repr::Dense(words, len) = &mut repr {
+            *len = words.iter().map(|w| w.count_ones() as usize).sum();
+        }
+        let mut set = Self { repr };
+        set.normalize();
+        set
+    }
+
+    /// Switches to the storage that fits the number of values.
+    fn normalize(&mut self) {
+        match &self.repr {
+            Repr::Sparse(values) if values.len() > Self::SPARSE_LIMIT => {
+                let mut words = vec![0; WORDS].into_boxed_slice();
+                for &val in values {
+                    words[usize::from(val / 64)] |= 1 << (val % 64);
+                }
+                self.repr = Repr::Dense(words, values.len());
+            }
+            Repr::Dense(_, len) if *len <= Self::SPARSE_LIMIT => {
+                self.repr = Repr::Sparse(self.iter().collect());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for HybridSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for HybridSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<u16> for HybridSet {
+    fn extend<I: IntoIterator<Item = u16>>(&mut self, iter: I) {
+        for val in iter {
+            self.insert(val);
+        }
+    }
+}
+
+impl FromIterator<u16> for HybridSet {
+    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a HybridSet {
+    type Item = u16;
+    type IntoIter = HybridIter<'a>;
+
+    fn into_iter(self) -> HybridIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the values in a [`HybridSet`].
+#[derive(Debug, Clone)]
+pub struct HybridIter<'a> {
+    inner: IterRepr<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum IterRepr<'a> {
+    Sparse(core::slice::Iter<'a, u16>),
+    Dense { words: &'a [u64], word: usize, bits: u64, remaining: usize },
+}
+
+impl Iterator for HybridIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match &mut self.inner {
+            IterRepr::Sparse(values) => values.next().copied(),
+            IterRepr::Dense { words, word, bits, remaining } => {
+                if *remaining == 0 {
+                    return None;
+                }
+                while *bits == 0 {
+                    *word += 1;
+                    *bits = words[*word];
+                }
+                let bit = bits.trailing_zeros() as usize;
+                *bits &= *bits - 1;
+                *remaining -= 1;
+                Some((*word * 64 + bit) as u16)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.inner {
+            IterRepr::Sparse(values) => values.len(),
+            IterRepr::Dense { remaining, .. } => *remaining,
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for HybridIter<'_> {}
+
+impl FusedIterator for HybridIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::HybridSet;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = HybridSet::new();
+        assert!(set.insert(7));
+        assert!(!set.insert(7));
+        assert!(set.insert(3));
+        assert!(set.contains(7));
+        assert!(!set.contains(4));
+        assert!(set.iter().eq([3, 7]));
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn switches_storage() {
+        let mut set: HybridSet = (0..HybridSet::SPARSE_LIMIT as u16).map(|x| x * 3).collect();
+        assert!(!set.is_dense());
+        set.insert(u16::MAX);
+        assert!(set.is_dense());
+        assert_eq!(set.len(), HybridSet::SPARSE_LIMIT + 1);
+        assert!(set.contains(u16::MAX) && set.contains(3) && !set.contains(4));
+        assert_eq!(set.iter().len(), HybridSet::SPARSE_LIMIT + 1);
+        assert!(set.iter().zip(set.iter().skip(1)).all(|(a, b)| a < b));
+        set.remove(0);
+        assert!(!set.is_dense());
+        assert_eq!(set.iter().next(), Some(3));
+        assert_eq!(set.iter().last(), Some(u16::MAX));
+    }
+
+    #[test]
+    fn set_operations() {
+        let sets: [HybridSet; 4] = [
+            (0..100).map(|x| x * 5).collect(),
+            (0..300).map(|x| x * 2).collect(),
+            (0..6000).map(|x| x * 3).collect(),
+            (2000..20000).collect(),
+        ];
+        for a in &sets {
+            for b in &sets {
+                let union: Vec<u16> = (0..=u16::MAX).filter(|&x| a.contains(x) || b.contains(x)).collect();
+                let both: Vec<u16> = (0..=u16::MAX).filter(|&x| a.contains(x) && b.contains(x)).collect();
+                assert!(a.union(b).iter().eq(union.iter().copied()));
+                assert!(a.intersection(b).iter().eq(both.iter().copied()));
+                assert_eq!(a.intersection(b).is_dense(), both.len() > HybridSet::SPARSE_LIMIT);
+                assert_eq!(a.union(b), union.into_iter().collect());
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Enums naming every index of the smaller packed types.
+
+macro_rules! index_enum {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $checked:ident, $count:literal, [$($variant:ident = $idx:literal),*]
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[repr(u8)]
+        pub enum $name {
+            $(
+                #[doc = concat!("Index ", $idx, ".")]
+                $variant = $idx,
+            )*
+        }
+
+        impl $name {
+            /// Every index, in increasing order.
+            pub const ALL: [Self; $count] = [$(Self::$variant),*];
+
+            /// Returns the index as a `u8`.
+            pub const fn get(self) -> u8 {
+                self as u8
+            }
+
+            #[doc = concat!("Returns the index as a [`", stringify!($checked), "`](crate::", stringify!($checked), "), for the checked accessors.")]
+            pub const fn to_checked(self) -> crate::$checked {
+                match crate::$checked::new(self.get()) {
+                    Some(idx) => idx,
+                    None => unreachable!(),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(idx: $name) -> u8 { idx.get() }
+        }
+
+        impl From<$name> for crate::$checked {
+            fn from(idx: $name) -> crate::$checked { idx.to_checked() }
+        }
+
+        impl From<crate::$checked> for $name {
+            fn from(idx: crate::$checked) -> $name { Self::ALL[usize::from(idx.get())] }
+        }
+    };
+}
+
+index_enum! {
+    /// An index into a `PackedBools8`, as an enum so that matches over it can be exhaustive.
+    Index8, BitIndex8, 8, [B0 = 0, B1 = 1, B2 = 2, B3 = 3, B4 = 4, B5 = 5, B6 = 6, B7 = 7]
+}
+
+index_enum! {
+    /// An index into a `PackedBools16`, as an enum so that matches over it can be exhaustive.
+    Index16, BitIndex16, 16, [
+        B0 = 0, B1 = 1, B2 = 2, B3 = 3, B4 = 4, B5 = 5, B6 = 6, B7 = 7,
+        B8 = 8, B9 = 9, B10 = 10, B11 = 11, B12 = 12, B13 = 13, B14 = 14, B15 = 15
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Index16, Index8};
+    use crate::{BitIndex16, BitIndex8, PackedBools16, PackedBools8};
+
+    #[test]
+    fn all_in_order() {
+        for (i, idx) in Index8::ALL.into_iter().enumerate() {
+            assert_eq!(usize::from(idx.get()), i);
+            assert_eq!(Index8::from(BitIndex8::from(idx)), idx);
+        }
+        for (i, idx) in Index16::ALL.into_iter().enumerate() {
+            assert_eq!(usize::from(u8::from(idx)), i);
+            assert_eq!(BitIndex16::from(idx).get(), idx.get());
+        }
+    }
+
+    #[test]
+    fn access() {
+        let mut pkd = PackedBools8::new();
+        pkd.set_at(true, Index8::B6.to_checked());
+        pkd.toggle_at(Index8::B0.into());
+        assert!(pkd.get_at(Index8::B6.into()));
+        assert_eq!(pkd, 0b0100_0001);
+
+        let mut pkd = PackedBools16::new();
+        pkd.set_at(true, Index16::B15.to_checked());
+        assert_eq!(pkd, 0x8000);
+    }
+}
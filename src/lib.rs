@@ -2,10 +2,119 @@
 
 #![no_std]
 #![warn(missing_docs)]
+#![cfg_attr(packed_booleans_nightly, feature(const_trait_impl, const_ops))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod macros;
 mod eight;
 mod sixteen;
+mod thirty_two;
+mod sixty_four;
+mod one_twenty_eight;
+mod atomic;
+#[cfg(target_has_atomic = "8")]
+mod atomic_slice;
+pub mod arrow;
+pub mod bitboard;
+mod bit_array;
+mod bit_slice;
+mod bloom;
+mod bool_op;
+mod braille;
+mod changes;
+mod clmul;
+mod combinations;
+mod convert;
+mod cpu;
+mod cursor;
+mod flags;
+mod fold;
+mod gray;
+mod grouped;
+mod index;
+mod literals;
+mod masked;
+mod matrix;
+mod mini_range;
+pub mod morton;
+mod ones;
+mod option;
+mod order;
+mod pairs;
+pub mod rle;
+pub mod slices;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+mod truth_table;
+mod typed_flags;
+mod volatile;
+#[cfg(feature = "alloc")]
+mod bool_vec;
+#[cfg(feature = "alloc")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod hybrid;
+#[cfg(feature = "alloc")]
+mod rank_select;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "embedded-hal")]
+pub mod pins;
+#[cfg(feature = "rayon")]
+pub mod par;
 
-pub use eight::{PackedBools8, IntoIter8};
-pub use sixteen::{PackedBools16};
+pub use eight::{PackedBools8, PackedBools8Builder, BitIndex8, IntoIter8};
+pub use sixteen::{PackedBools16, PackedBools16Builder, BitIndex16, IntoIter16};
+pub use thirty_two::{PackedBools32, PackedBools32Builder, BitIndex32, IntoIter32};
+pub use sixty_four::{PackedBools64, PackedBools64Builder, BitIndex64, IntoIter64};
+pub use one_twenty_eight::{PackedBools128, PackedBools128Builder, BitIndex128, IntoIter128};
+#[cfg(any(target_has_atomic = "8", feature = "critical-section"))]
+pub use atomic::AtomicPackedBools8;
+#[cfg(any(target_has_atomic = "16", feature = "critical-section"))]
+pub use atomic::AtomicPackedBools16;
+#[cfg(any(target_has_atomic = "32", feature = "critical-section"))]
+pub use atomic::AtomicPackedBools32;
+#[cfg(any(target_has_atomic = "64", feature = "critical-section"))]
+pub use atomic::AtomicPackedBools64;
+#[cfg(target_has_atomic = "8")]
+pub use atomic_slice::AtomicBitSlice;
+pub use bit_array::BitArray;
+pub use bit_slice::{BitChunks, BitPositions, BitRangeError, BitSlice, BitSliceIter, BitSliceMut, BitWindows, PackedChunks8};
+pub use bloom::BloomFilter;
+pub use bool_op::BinaryBoolOp;
+pub use braille::Braille;
+pub use changes::ChangeSet;
+pub use cpu::CpuFeatures;
+pub use cursor::{BitCursor, BufferExhausted};
+pub use masked::MaskedBools8;
+pub use matrix::BitMatrix8x8;
+pub use mini_range::MiniRange8;
+pub use ones::Ones;
+pub use option::PackedOptionBools8;
+pub use order::{BitOrder, OrderedBits};
+pub use pairs::Pairs;
+pub use gray::GrayCodes;
+pub use index::{Index16, Index8};
+pub use grouped::GroupedBits;
+pub use combinations::Combinations;
+pub use convert::{BufTooSmall, ParseBitsError, StrayBitsError};
+pub use fold::{Intersection, Union};
+pub use truth_table::{TruthTable8, TruthTableInput};
+pub use typed_flags::{BitIndexable, FlagStorage, FlagsIter, PackedFlags};
+pub use volatile::RegisterBits;
+#[cfg(feature = "alloc")]
+pub use bool_vec::{BoolVecDrain, BoolVecIntoIter, BoolVecIter, PackedBoolVec, Word};
+#[cfg(feature = "alloc")]
+pub use grid::{BitGrid, EdgeMode, MortonGrid, NeighborCounts};
+#[cfg(feature = "alloc")]
+pub use hybrid::{HybridIter, HybridSet};
+#[cfg(feature = "alloc")]
+pub use rank_select::RankSelectIndex;
+#[cfg(feature = "std")]
+pub use reader::BitReader;
+#[cfg(feature = "derive")]
+pub use packed_booleans_derive::{bitfield, BitIndex, PackBools};
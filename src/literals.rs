@@ -0,0 +1,70 @@
+//! Macros for writing packed values as literals.
+
+/// Converts a single `true`/`false`/`1`/`0` token into a `bool`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bool_literal {
+    (true) => { true };
+    (false) => { false };
+    (1) => { true };
+    (0) => { false };
+}
+
+/// Creates a `PackedBools8` from 8 booleans, written as `true`/`false` or `1`/`0`.
+///
+/// The first value is at index 0.
+/// This can be used in const contexts,
+/// and giving the wrong number of values is a compile error.
+///
+/// ```
+/// use packed_booleans::{bools8, PackedBools8};
+///
+/// const FLAGS: PackedBools8 = bools8![1, 0, 1, 1, 0, 0, 0, 1];
+/// assert_eq!(FLAGS, bools8![true, false, true, true, false, false, false, true]);
+/// assert_eq!(FLAGS.to_bits(), 0b1000_1101);
+/// ```
+#[macro_export]
+macro_rules! bools8 {
+    ($($b:tt),* $(,)?) => {
+        $crate::PackedBools8::new_vals([$($crate::__bool_literal!($b)),*])
+    };
+}
+
+/// Creates a `PackedBools16` from 16 booleans, written as `true`/`false` or `1`/`0`.
+///
+/// The first value is at index 0.
+/// This can be used in const contexts,
+/// and giving the wrong number of values is a compile error.
+///
+/// ```
+/// use packed_booleans::{bools16, PackedBools16};
+///
+/// const FLAGS: PackedBools16 = bools16![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+/// assert_eq!(FLAGS.to_bits(), 0x8001);
+/// ```
+#[macro_export]
+macro_rules! bools16 {
+    ($($b:tt),* $(,)?) => {
+        $crate::PackedBools16::new_vals([$($crate::__bool_literal!($b)),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PackedBools8, PackedBools16};
+
+    const EIGHT: PackedBools8 = crate::bools8![0, 1, 1, 0, 1, 0, 0, 1];
+
+    #[test]
+    fn bools8() {
+        assert_eq!(EIGHT, PackedBools8::from_bits(0b1001_0110));
+        assert_eq!(EIGHT, crate::bools8![false, true, true, false, true, false, false, true,]);
+        assert_eq!(crate::bools8![1, false, 1, true, 0, 0, 0, 0], PackedBools8::from_bits(0b1101));
+    }
+
+    #[test]
+    fn bools16() {
+        let pkd = crate::bools16![1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0];
+        assert_eq!(pkd, PackedBools16::from_bits(0x4003));
+    }
+}
@@ -1,55 +1,162 @@
+/// Implements a trait, as a const impl when built with `--cfg packed_booleans_nightly`.
+///
+/// That needs a nightly compiler, as const trait impls aren't stable yet.
+/// The two versions are separate macros, since stable compilers reject
+/// `impl const` even in code that is configured out.
+#[cfg(packed_booleans_nightly)]
+macro_rules! const_impl {
+    (impl $($rest:tt)*) => {
+        impl const $($rest)*
+    };
+}
+
+#[cfg(not(packed_booleans_nightly))]
+macro_rules! const_impl {
+    (impl $($rest:tt)*) => {
+        impl $($rest)*
+    };
+}
+
+pub(crate) use const_impl;
+
+/// Panics with a message naming the out-of-range index and the capacity.
+///
+/// Const panics can only format a single `&str`, so the message is built by hand.
+#[cold]
+#[track_caller]
+pub(crate) const fn index_out_of_range(idx: u8, capacity: u8) -> ! {
+    const fn push_num(buf: &mut [u8; 64], mut len: usize, num: u8) -> usize {
+        if num >= 100 {
+            buf[len] = b'0' + num / 100;
+            len += 1;
+        }
+        if num >= 10 {
+            buf[len] = b'0' + num / 10 % 10;
+            len += 1;
+        }
+        buf[len] = b'0' + num % 10;
+        len + 1
+    }
+
+    const fn push_str(buf: &mut [u8; 64], mut len: usize, s: &str) -> usize {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            buf[len] = bytes[i];
+            len += 1;
+            i += 1;
+        }
+        len
+    }
+
+    let mut buf = [0; 64];
+    let mut len = push_str(&mut buf, 0, "index out of range: the index is ");
+    len = push_num(&mut buf, len, idx);
+    len = push_str(&mut buf, len, " but the capacity is ");
+    len = push_num(&mut buf, len, capacity);
+    match core::str::from_utf8(buf.split_at(len).0) {
+        Ok(msg) => panic!("{}", msg),
+        Err(_) => unreachable!(),
+    }
+}
+
 macro_rules! impl_binop {
     (impl $op:tt for $type:ty: $tr:ident $method:ident $assign_tr:ident $assign_method:ident) => {
         // base impl
-        impl $tr for $type {
-            type Output = $type;
+        crate::macros::const_impl! {
+            impl $tr for $type {
+                type Output = $type;
 
-            fn $method(self, rhs: Self) -> $type {
-                <$type>::from_bits(self.0 $op rhs.0)
+                fn $method(self, rhs: Self) -> $type {
+                    <$type>::from_bits(self.0 $op rhs.0)
+                }
             }
         }
 
         // ref impls
-        impl $tr<$type> for &$type {
-            type Output = $type;
+        crate::macros::const_impl! {
+            impl $tr<$type> for &$type {
+                type Output = $type;
 
-            fn $method(self, rhs: $type) -> $type {
-                $tr::$method(*self, rhs)
+                fn $method(self, rhs: $type) -> $type {
+                    $tr::$method(*self, rhs)
+                }
             }
         }
 
-        impl $tr<&$type> for $type {
-            type Output = $type;
+        crate::macros::const_impl! {
+            impl $tr<&$type> for $type {
+                type Output = $type;
 
-            fn $method(self, rhs: &$type) -> $type {
-                $tr::$method(self, *rhs)
+                fn $method(self, rhs: &$type) -> $type {
+                    $tr::$method(self, *rhs)
+                }
             }
         }
 
-        impl $tr<&$type> for &$type {
-            type Output = $type;
+        crate::macros::const_impl! {
+            impl $tr<&$type> for &$type {
+                type Output = $type;
 
-            fn $method(self, rhs: &$type) -> $type {
-                $tr::$method(*self, *rhs)
+                fn $method(self, rhs: &$type) -> $type {
+                    $tr::$method(*self, *rhs)
+                }
             }
         }
 
         // op= impls
-        impl $assign_tr<$type> for $type {
-            fn $assign_method(&mut self, rhs: Self) {
-                *self = self.$method(rhs)
+        crate::macros::const_impl! {
+            impl $assign_tr<$type> for $type {
+                fn $assign_method(&mut self, rhs: Self) {
+                    *self = self.$method(rhs)
+                }
             }
         }
 
-        impl $assign_tr<&$type> for $type {
-            fn $assign_method(&mut self, rhs: &$type) {
-                *self = self.$method(*rhs)
+        crate::macros::const_impl! {
+            impl $assign_tr<&$type> for $type {
+                fn $assign_method(&mut self, rhs: &$type) {
+                    *self = self.$method(*rhs)
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_binop_with {
+    (impl $op:tt for $type:ty, $rhs:ty => $conv:path: $tr:ident $method:ident $assign_tr:ident $assign_method:ident) => {
+        crate::macros::const_impl! {
+            impl $tr<$rhs> for $type {
+                type Output = $type;
+
+                fn $method(self, rhs: $rhs) -> $type {
+                    $tr::$method(self, $conv(rhs))
+                }
+            }
+        }
+
+        crate::macros::const_impl! {
+            impl $tr<$rhs> for &$type {
+                type Output = $type;
+
+                fn $method(self, rhs: $rhs) -> $type {
+                    $tr::$method(*self, $conv(rhs))
+                }
+            }
+        }
+
+        crate::macros::const_impl! {
+            impl $assign_tr<$rhs> for $type {
+                fn $assign_method(&mut self, rhs: $rhs) {
+                    *self = self.$method($conv(rhs))
+                }
             }
         }
     }
 }
 
 pub(crate) use impl_binop;
+pub(crate) use impl_binop_with;
 
 macro_rules! impl_binops {
     (impl & | ^ for $type: ty) => {
@@ -57,7 +164,12 @@ macro_rules! impl_binops {
         crate::macros::impl_binop!{ impl & for $type: BitAnd bitand BitAndAssign bitand_assign }
         crate::macros::impl_binop!{ impl | for $type: BitOr bitor BitOrAssign bitor_assign }
         crate::macros::impl_binop!{ impl ^ for $type: BitXor bitxor BitXorAssign bitxor_assign }
-    }
+    };
+    (impl & | ^ for $type:ty, $rhs:ty => $conv:path) => {
+        crate::macros::impl_binop_with!{ impl & for $type, $rhs => $conv: BitAnd bitand BitAndAssign bitand_assign }
+        crate::macros::impl_binop_with!{ impl | for $type, $rhs => $conv: BitOr bitor BitOrAssign bitor_assign }
+        crate::macros::impl_binop_with!{ impl ^ for $type, $rhs => $conv: BitXor bitxor BitXorAssign bitxor_assign }
+    };
 }
 
 pub(crate) use impl_binops;
@@ -65,6 +177,8 @@ pub(crate) use impl_binops;
 macro_rules! packed_bools_type {
     (
         NAME = $pkd:ident,
+        BUILDER = $builder:ident,
+        INDEX = $index:ident,
         REPR = $repr:ident,
         BOOL_COUNT = $bcount:literal,
         BCOUNT_MINUS1 = $bcountdec:literal,
@@ -73,7 +187,9 @@ macro_rules! packed_bools_type {
         DEBUG = $debug:literal,
         BINARY = $binary:literal,
         LOW_HEX = $lohex:literal,
-        UPPER_HEX = $uphex:literal
+        UPPER_HEX = $uphex:literal,
+        WIDER_INTS = [$($wider:ident),*],
+        WIDER_TYPES = [$($wider_pkd:ident),*]
     ) => {
         #[doc = concat!("A type containing ", $bcount, " `bool` values,")]
         #[doc = concat!("while only being ", $bdesc, ".")]
@@ -87,24 +203,79 @@ macro_rules! packed_bools_type {
             pub const fn new() -> Self { Self(0) }
 
             #[doc = concat!("Creates a new `", stringify!($pkd), "` from the given bits.")]
-            pub fn from_bits(bits: $repr) -> Self { Self(bits) }
+            pub const fn from_bits(bits: $repr) -> Self { Self(bits) }
+
+            #[doc = concat!("Returns the bits of this `", stringify!($pkd), "`.")]
+            pub const fn to_bits(self) -> $repr { self.0 }
+
+            /// Returns the bits as a byte array in little-endian byte order.
+            pub const fn to_le_bytes(self) -> [u8; core::mem::size_of::<$repr>()] {
+                self.0.to_le_bytes()
+            }
+
+            /// Returns the bits as a byte array in big-endian byte order.
+            pub const fn to_be_bytes(self) -> [u8; core::mem::size_of::<$repr>()] {
+                self.0.to_be_bytes()
+            }
+
+            /// Returns the bits as a byte array in native byte order.
+            pub const fn to_ne_bytes(self) -> [u8; core::mem::size_of::<$repr>()] {
+                self.0.to_ne_bytes()
+            }
+
+            #[doc = concat!("Creates a new `", stringify!($pkd), "` from bits in a little-endian byte array.")]
+            pub const fn from_le_bytes(bytes: [u8; core::mem::size_of::<$repr>()]) -> Self {
+                Self($repr::from_le_bytes(bytes))
+            }
+
+            #[doc = concat!("Creates a new `", stringify!($pkd), "` from bits in a big-endian byte array.")]
+            pub const fn from_be_bytes(bytes: [u8; core::mem::size_of::<$repr>()]) -> Self {
+                Self($repr::from_be_bytes(bytes))
+            }
+
+            #[doc = concat!("Creates a new `", stringify!($pkd), "` from bits in a native-endian byte array.")]
+            pub const fn from_ne_bytes(bytes: [u8; core::mem::size_of::<$repr>()]) -> Self {
+                Self($repr::from_ne_bytes(bytes))
+            }
 
             /// Counts how many true values there are.
-            pub fn count_true(&self) -> u8 {
+            pub const fn count_true(&self) -> u8 {
                 self.0.count_ones() as u8
             }
 
             /// Counts how many false values there are.
-            pub fn count_false(&self) -> u8 {
+            pub const fn count_false(&self) -> u8 {
                 self.0.count_zeros() as u8
             }
 
+            /// Returns true if any value is true.
+            pub const fn any(&self) -> bool {
+                self.0 != 0
+            }
+
+            /// Returns true if every value is true.
+            pub const fn all(&self) -> bool {
+                self.0 == $repr::MAX
+            }
+
+            /// Returns true if every value is false.
+            pub const fn none(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Counts how many indices `self` and `other` have the same value at.
+            pub const fn count_matching(&self, other: Self) -> u8 {
+                self.eq_mask(other).count_true()
+            }
+
             #[doc = concat!("Creates a new `", stringify!($pkd), "` from the given values.")]
-            pub fn new_vals(vals: [bool; $bcount]) -> Self {
-                let out: $repr = vals.into_iter()
-                    .map($repr::from)
-                    .zip(0..$bcount)
-                    .fold(0, |acc, (b, idx)| acc.bitor(b << idx));
+            pub const fn new_vals(vals: [bool; $bcount]) -> Self {
+                let mut out: $repr = 0;
+                let mut idx = 0;
+                while idx < $bcount {
+                    out |= (vals[idx] as $repr) << idx;
+                    idx += 1;
+                }
                 Self(out)
             }
 
@@ -127,14 +298,17 @@ macro_rules! packed_bools_type {
             /// # Panics
             ///
             #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
-            pub fn get(&self, idx: u8) -> bool {
-                self.try_get(idx)
-                    .expect(concat!("The index cannot be greater than ", $bcountdec))
+            #[track_caller]
+            pub const fn get(&self, idx: u8) -> bool {
+                match self.try_get(idx) {
+                    Some(out) => out,
+                    None => crate::macros::index_out_of_range(idx, $bcount),
+                }
             }
             
             /// Gets the boolean at the given index,
             #[doc = concat!("if the index is less than ", $bcount, ".")]
-            pub fn try_get(&self, idx: u8) -> Option<bool> {
+            pub const fn try_get(&self, idx: u8) -> Option<bool> {
                 if idx < $bcount {
                     Some(((self.0 >> idx) & 1) != 0)
                 } else {
@@ -147,14 +321,17 @@ macro_rules! packed_bools_type {
             /// # Panics
             ///
             #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
-            pub fn set(&mut self, val: bool, idx: u8) {
-                self.try_set(val, idx)
-                    .expect(concat!("The index cannot be greater than ", $bcountdec))
+            #[track_caller]
+            pub const fn set(&mut self, val: bool, idx: u8) {
+                match self.try_set(val, idx) {
+                    Some(out) => out,
+                    None => crate::macros::index_out_of_range(idx, $bcount),
+                }
             }
 
             /// Sets the boolean at the given index to val,
             #[doc = concat!("if the index is less than ", $bcount, ".")]
-            pub fn try_set(&mut self, val: bool, idx: u8) -> Option<()> {
+            pub const fn try_set(&mut self, val: bool, idx: u8) -> Option<()> {
                 if idx < $bcount {
                     match val {
                         true => self.0 |= 1 << idx,
@@ -171,14 +348,17 @@ macro_rules! packed_bools_type {
             /// # Panics
             ///
             #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
-            pub fn toggle(&mut self, idx: u8) {
-                self.try_toggle(idx)
-                    .expect(concat!("The index cannot be greater than ", $bcountdec))
+            #[track_caller]
+            pub const fn toggle(&mut self, idx: u8) {
+                match self.try_toggle(idx) {
+                    Some(out) => out,
+                    None => crate::macros::index_out_of_range(idx, $bcount),
+                }
             }
 
             /// Toggles the boolean at the given index,
             #[doc = concat!("if the index is less than ", $bcount, ".")]
-            pub fn try_toggle(&mut self, idx: u8) -> Option<()> {
+            pub const fn try_toggle(&mut self, idx: u8) -> Option<()> {
                 if idx < $bcount {
                     self.0 ^= 1 << idx;
                     Some(())
@@ -186,22 +366,1039 @@ macro_rules! packed_bools_type {
                     None
                 }
             }
+
+            /// Gets the boolean at index `IDX`.
+            ///
+            #[doc = concat!("`IDX` must be less than ", $bcount, ", which is checked at compile time, so this can't panic.")]
+            ///
+            /// ```compile_fail
+            #[doc = concat!("let bits = packed_booleans::", stringify!($pkd), "::new();")]
+            #[doc = concat!("bits.get_const::<", $bcount, ">();")]
+            /// ```
+            pub const fn get_const<const IDX: u8>(&self) -> bool {
+                const { assert!(IDX < $bcount, concat!("The index must be less than ", $bcount)) };
+                ((self.0 >> IDX) & 1) != 0
+            }
+
+            /// Sets the boolean at index `IDX` to val.
+            ///
+            #[doc = concat!("`IDX` must be less than ", $bcount, ", which is checked at compile time, so this can't panic.")]
+            pub const fn set_const<const IDX: u8>(&mut self, val: bool) {
+                const { assert!(IDX < $bcount, concat!("The index must be less than ", $bcount)) };
+                match val {
+                    true => self.0 |= 1 << IDX,
+                    false => self.0 &= !(1 << IDX),
+                };
+            }
+
+            /// Toggles the boolean at index `IDX`.
+            ///
+            #[doc = concat!("`IDX` must be less than ", $bcount, ", which is checked at compile time, so this can't panic.")]
+            pub const fn toggle_const<const IDX: u8>(&mut self) {
+                const { assert!(IDX < $bcount, concat!("The index must be less than ", $bcount)) };
+                self.0 ^= 1 << IDX;
+            }
+
+            /// Returns a copy with the boolean at the given index set to val.
+            ///
+            /// This is the by-value form of [`set`](Self::set), for building values in constants.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub const fn with(mut self, val: bool, idx: u8) -> Self {
+                self.set(val, idx);
+                self
+            }
+
+            /// Sets the boolean at the given index to val, returning its previous value.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn set_replace(&mut self, val: bool, idx: u8) -> bool {
+                match self.try_set_replace(val, idx) {
+                    Some(out) => out,
+                    None => crate::macros::index_out_of_range(idx, $bcount),
+                }
+            }
+
+            /// Sets the boolean at the given index to val, returning its previous value,
+            #[doc = concat!("if the index is less than ", $bcount, ".")]
+            pub fn try_set_replace(&mut self, val: bool, idx: u8) -> Option<bool> {
+                let prev = self.try_get(idx)?;
+                self.try_set(val, idx);
+                Some(prev)
+            }
+
+            /// Toggles the boolean at the given index, returning its previous value.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn toggle_get(&mut self, idx: u8) -> bool {
+                match self.try_toggle_get(idx) {
+                    Some(out) => out,
+                    None => crate::macros::index_out_of_range(idx, $bcount),
+                }
+            }
+
+            /// Toggles the boolean at the given index, returning its previous value,
+            #[doc = concat!("if the index is less than ", $bcount, ".")]
+            pub fn try_toggle_get(&mut self, idx: u8) -> Option<bool> {
+                let prev = self.try_get(idx)?;
+                self.try_toggle(idx);
+                Some(prev)
+            }
+
+            /// Replaces the value with the result of applying `f` to it.
+            pub fn modify(&mut self, f: impl FnOnce(Self) -> Self) {
+                *self = f(*self);
+            }
+
+            /// Returns the index of the first true value at or after `idx`.
+            pub const fn find_true_from(&self, idx: u8) -> Option<u8> {
+                if idx >= $bcount {
+                    return None;
+                }
+                let bits = self.0 & ($repr::MAX << idx);
+                if bits == 0 {
+                    None
+                } else {
+                    Some(bits.trailing_zeros() as u8)
+                }
+            }
+
+            /// Returns the index of the first false value at or after `idx`.
+            pub const fn find_false_from(&self, idx: u8) -> Option<u8> {
+                Self(!self.0).find_true_from(idx)
+            }
+
+            /// Returns the index of the last true value at or before `idx`.
+            #[doc = concat!("Indices greater than ", $bcountdec, " search from the last value.")]
+            pub const fn rfind_true_from(&self, idx: u8) -> Option<u8> {
+                let mask = if idx >= $bcountdec {
+                    $repr::MAX
+                } else {
+                    (2 << idx) - 1
+                };
+                let bits = self.0 & mask;
+                if bits == 0 {
+                    None
+                } else {
+                    Some($bcountdec - bits.leading_zeros() as u8)
+                }
+            }
+
+            /// Returns the index of the last false value at or before `idx`.
+            #[doc = concat!("Indices greater than ", $bcountdec, " search from the last value.")]
+            pub const fn rfind_false_from(&self, idx: u8) -> Option<u8> {
+                Self(!self.0).rfind_true_from(idx)
+            }
+
+            /// Returns the index of the first true value after `idx`.
+            pub const fn next_true_after(&self, idx: u8) -> Option<u8> {
+                if idx >= $bcountdec {
+                    return None;
+                }
+                self.find_true_from(idx + 1)
+            }
+
+            /// Returns the index of the last true value before `idx`.
+            pub const fn prev_true_before(&self, idx: u8) -> Option<u8> {
+                if idx == 0 {
+                    return None;
+                }
+                self.rfind_true_from(idx - 1)
+            }
+
+            /// Gets the booleans at the given indices, writing them to `out` in the same order.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `indices` and `out` have different lengths,
+            #[doc = concat!("or if any index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn gather(&self, indices: &[u8], out: &mut [bool]) {
+                assert_eq!(indices.len(), out.len(), "The indices and output must have the same length");
+                for (b, &idx) in out.iter_mut().zip(indices) {
+                    *b = self.get(idx);
+                }
+            }
+
+            /// Sets the booleans at the given indices to the matching values in `vals`.
+            ///
+            /// If an index appears more than once, the last matching value is used.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `indices` and `vals` have different lengths,
+            #[doc = concat!("or if any index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn scatter(&mut self, indices: &[u8], vals: &[bool]) {
+                assert_eq!(indices.len(), vals.len(), "The indices and values must have the same length");
+                for (&val, &idx) in vals.iter().zip(indices) {
+                    self.set(val, idx);
+                }
+            }
+
+            /// Returns the next combination of values, counting in binary,
+            /// or `None` if all the values are already true.
+            ///
+            /// Starting from all false values, this visits every combination once.
+            pub const fn checked_increment(self) -> Option<Self> {
+                match self.0.checked_add(1) {
+                    Some(bits) => Some(Self(bits)),
+                    None => None,
+                }
+            }
+
+            /// Returns the previous combination of values, counting in binary,
+            /// or `None` if all the values are already false.
+            pub const fn checked_decrement(self) -> Option<Self> {
+                match self.0.checked_sub(1) {
+                    Some(bits) => Some(Self(bits)),
+                    None => None,
+                }
+            }
+
+            /// Converts the bits from binary to their Gray code.
+            ///
+            /// Consecutive values in binary have Gray codes differing in exactly one value.
+            pub const fn to_gray(self) -> Self {
+                Self(self.0 ^ (self.0 >> 1))
+            }
+
+            /// Converts the bits from their Gray code back to binary.
+            ///
+            /// This is the inverse of `to_gray`.
+            pub const fn from_gray(self) -> Self {
+                let mut bits = self.0;
+                let mut shift = 1;
+                while shift < $bcount {
+                    bits ^= bits >> shift;
+                    shift <<= 1;
+                }
+                Self(bits)
+            }
+
+            /// Returns a value where each boolean is the OR of itself
+            /// and all the booleans at lower indices.
+            ///
+            /// Everything from the first true value onwards becomes true.
+            pub const fn prefix_or(self) -> Self {
+                let mut bits = self.0;
+                let mut shift = 1;
+                while shift < $bcount {
+                    bits |= bits << shift;
+                    shift <<= 1;
+                }
+                Self(bits)
+            }
+
+            /// Returns a value where each boolean is the XOR of itself
+            /// and all the booleans at lower indices.
+            pub const fn prefix_xor(self) -> Self {
+                let mut bits = self.0;
+                let mut shift = 1;
+                while shift < $bcount {
+                    bits ^= bits << shift;
+                    shift <<= 1;
+                }
+                Self(bits)
+            }
+
+            /// Returns a value that is true where `self` and `other` are equal.
+            pub const fn eq_mask(self, other: Self) -> Self {
+                Self(!(self.0 ^ other.0))
+            }
+
+            /// Returns a value that is true where `self` and `other` are different.
+            pub const fn ne_mask(self, other: Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+
+            /// Returns the NAND of `self` and `other`, which is true where either is false.
+            pub const fn nand(self, other: Self) -> Self {
+                Self(!(self.0 & other.0))
+            }
+
+            /// Returns the NOR of `self` and `other`, which is true where both are false.
+            pub const fn nor(self, other: Self) -> Self {
+                Self(!(self.0 | other.0))
+            }
+
+            /// Returns the XNOR of `self` and `other`, which is the same as [`eq_mask`](Self::eq_mask).
+            pub const fn xnor(self, other: Self) -> Self {
+                self.eq_mask(other)
+            }
+
+            /// Returns the values of `self` with those set in `other` cleared, which is `self & !other`.
+            pub const fn and_not(self, other: Self) -> Self {
+                Self(self.0 & !other.0)
+            }
+
+            /// Returns a value that is true where at least two of `a`, `b` and `c` are true.
+            pub const fn majority(a: Self, b: Self, c: Self) -> Self {
+                Self((a.0 & b.0) | (a.0 & c.0) | (b.0 & c.0))
+            }
+
+            /// Combines three values with a boolean function, applied to each index.
+            pub fn ternary(a: Self, b: Self, c: Self, f: impl Fn(bool, bool, bool) -> bool) -> Self {
+                let mut table = 0;
+                for i in 0..8 {
+                    if f(i & 0b100 != 0, i & 0b010 != 0, i & 0b001 != 0) {
+                        table |= 1 << i;
+                    }
+                }
+                Self::ternary_table(a, b, c, table)
+            }
+
+            /// Combines three values with a boolean function given as a truth table.
+            ///
+            /// The result at each index is bit `(a << 2) | (b << 1) | c` of `table`,
+            /// where `a`, `b` and `c` are the values at that index.
+            /// For example, `0b1110_1000` is the majority function.
+            pub const fn ternary_table(a: Self, b: Self, c: Self, table: u8) -> Self {
+                let mut bits = 0;
+                let mut i = 0;
+                while i < 8 {
+                    if table & (1 << i) != 0 {
+                        let a = if i & 0b100 != 0 { a.0 } else { !a.0 };
+                        let b = if i & 0b010 != 0 { b.0 } else { !b.0 };
+                        let c = if i & 0b001 != 0 { c.0 } else { !c.0 };
+                        bits |= a & b & c;
+                    }
+                    i += 1;
+                }
+                Self(bits)
+            }
+
+            /// Combines `self` and `other` with a two-input boolean function, applied to each index.
+            pub const fn apply_binary_op(self, other: Self, op: crate::BinaryBoolOp) -> Self {
+                let table = op.table();
+                let mut bits = 0;
+                let mut i = 0;
+                while i < 4 {
+                    if table & (1 << i) != 0 {
+                        let a = if i & 0b10 != 0 { self.0 } else { !self.0 };
+                        let b = if i & 0b01 != 0 { other.0 } else { !other.0 };
+                        bits |= a & b;
+                    }
+                    i += 1;
+                }
+                Self(bits)
+            }
+
+            /// Parses a value from ASCII binary digits, with the last index first,
+            /// in the same order as the `Binary` output.
+            ///
+            #[doc = concat!("The input must have exactly ", $bcount, " bytes, each `b'0'` or `b'1'`.")]
+            pub const fn from_ascii_bits(digits: &[u8]) -> Result<Self, crate::ParseBitsError> {
+                if digits.len() != $bcount {
+                    return Err(crate::ParseBitsError::WrongLength { expected: $bcount, found: digits.len() });
+                }
+                let mut bits: $repr = 0;
+                let mut i = 0;
+                while i < digits.len() {
+                    bits = match digits[i] {
+                        b'0' => bits << 1,
+                        b'1' => (bits << 1) | 1,
+                        byte => return Err(crate::ParseBitsError::InvalidDigit { index: i, byte }),
+                    };
+                    i += 1;
+                }
+                Ok(Self(bits))
+            }
+
+            /// Returns an adapter that displays the value in binary, in groups of 4 bits.
+            pub const fn grouped(self) -> crate::GroupedBits<Self> {
+                crate::GroupedBits { value: self }
+            }
+
+            /// Returns the changes from `self` to `after`.
+            pub const fn changes_to(self, after: Self) -> crate::ChangeSet<Self> {
+                crate::ChangeSet::new(self, after)
+            }
+
+            /// Returns an iterator over the indices of the true values.
+            pub const fn ones(self) -> crate::Ones<Self> {
+                crate::Ones::new(self)
+            }
+
+            /// Returns an iterator over the indices where `self` and `other` are different.
+            pub const fn differences(&self, other: Self) -> crate::Ones<Self> {
+                crate::Ones::new(self.ne_mask(other))
+            }
+
+            /// Returns an iterator over the booleans at each index of `self` and `other`, as pairs.
+            pub const fn pairs(self, other: Self) -> crate::Pairs<Self> {
+                crate::Pairs::new(self, other, $bcount)
+            }
+
+            /// Returns an iterator over every value with exactly `k` true values,
+            /// in increasing order of their bits.
+            #[doc = concat!("This yields nothing if `k` is greater than ", $bcount, ".")]
+            pub const fn combinations(k: u8) -> crate::Combinations<Self> {
+                let first = if k > $bcount {
+                    None
+                } else if k == $bcount {
+                    Some(Self($repr::MAX))
+                } else {
+                    Some(Self((1 << k) - 1))
+                };
+                crate::Combinations::new(first)
+            }
+
+            /// Returns an iterator over every combination of values in Gray code order,
+            /// starting from all false values.
+            ///
+            /// Each step toggles exactly one value.
+            pub const fn gray_codes() -> crate::GrayCodes<Self> {
+                crate::GrayCodes::new(Self(0))
+            }
+
+            /// Writes the booleans as ASCII binary digits into the start of `buf`,
+            /// returning how many bytes were written.
+            ///
+            #[doc = concat!("This writes the same ", $bcount, " digits as the `Binary` impl, without going through `core::fmt`.")]
+            pub const fn write_binary(&self, buf: &mut [u8]) -> Result<usize, crate::BufTooSmall> {
+                if buf.len() < $bcount {
+                    return Err(crate::BufTooSmall::new($bcount, buf.len()));
+                }
+                let mut i = 0;
+                while i < $bcount {
+                    buf[i] = b'0' + ((self.0 >> ($bcount - 1 - i)) & 1) as u8;
+                    i += 1;
+                }
+                Ok($bcount)
+            }
+
+            /// Writes the booleans as ASCII lowercase hexadecimal digits into the start of `buf`,
+            /// returning how many bytes were written.
+            ///
+            /// This writes the same digits as the `LowerHex` impl, without going through `core::fmt`.
+            pub const fn write_lower_hex(&self, buf: &mut [u8]) -> Result<usize, crate::BufTooSmall> {
+                self.write_hex(buf, crate::slices::HEX_DIGITS)
+            }
+
+            /// Writes the booleans as ASCII uppercase hexadecimal digits into the start of `buf`,
+            /// returning how many bytes were written.
+            ///
+            /// This writes the same digits as the `UpperHex` impl, without going through `core::fmt`.
+            pub const fn write_upper_hex(&self, buf: &mut [u8]) -> Result<usize, crate::BufTooSmall> {
+                self.write_hex(buf, b"0123456789ABCDEF")
+            }
+
+            /// Returns the booleans as a string of `0`s and `1`s, in index order.
+            ///
+            /// Unlike the `Binary` impl, the first character is the boolean at index 0.
+            #[cfg(feature = "alloc")]
+            pub fn to_binary_string(&self) -> alloc::string::String {
+                (0..$bcount).map(|idx| if self.get(idx) { '1' } else { '0' }).collect()
+            }
+
+            /// Returns the booleans as lowercase hexadecimal digits, 4 booleans at a time in index order.
+            ///
+            /// Digit `k` holds the booleans at indices `4k` to `4k + 3`, with index `4k` as its lowest bit.
+            /// Unlike the `LowerHex` impl, the first digit holds index 0.
+            #[cfg(feature = "alloc")]
+            pub fn to_hex_string(&self) -> alloc::string::String {
+                (0..$bcount / 4)
+                    .map(|k| char::from(crate::slices::HEX_DIGITS[((self.0 >> (4 * k)) & 0xf) as usize]))
+                    .collect()
+            }
+
+            const fn write_hex(&self, buf: &mut [u8], digits: &[u8; 16]) -> Result<usize, crate::BufTooSmall> {
+                const LEN: usize = $bcount / 4;
+                if buf.len() < LEN {
+                    return Err(crate::BufTooSmall::new(LEN, buf.len()));
+                }
+                let mut i = 0;
+                while i < LEN {
+                    buf[i] = digits[((self.0 >> (4 * (LEN - 1 - i))) & 0xf) as usize];
+                    i += 1;
+                }
+                Ok(LEN)
+            }
+        }
+
+        impl $pkd {
+            #[doc = concat!("Creates a new `", stringify!($builder), "` with all false values.")]
+            pub const fn builder() -> $builder { $builder::new() }
+        }
+
+        #[doc = concat!("A builder for `", stringify!($pkd), "` values that can be used in const contexts.")]
+        ///
+        /// # Panics
+        ///
+        /// The methods taking an index panic if the index is out of range,
+        /// which is a compile error in const contexts.
+        #[derive(Clone, Copy, PartialEq, Eq, Default, Hash, Debug)]
+        pub struct $builder($repr);
+
+        impl $builder {
+            /// Creates a new builder with all false values.
+            pub const fn new() -> Self { Self(0) }
+
+            /// Sets the boolean at the given index to true.
+            #[track_caller]
+            pub const fn set(self, idx: u8) -> Self {
+                Self(self.0 | Self::bit(idx))
+            }
+
+            /// Sets the boolean at the given index to false.
+            #[track_caller]
+            pub const fn clear(self, idx: u8) -> Self {
+                Self(self.0 & !Self::bit(idx))
+            }
+
+            /// Toggles the boolean at the given index.
+            #[track_caller]
+            pub const fn toggle(self, idx: u8) -> Self {
+                Self(self.0 ^ Self::bit(idx))
+            }
+
+            /// Sets the booleans in the given range to true.
+            pub const fn set_range(self, range: core::ops::Range<u8>) -> Self {
+                Self(self.0 | Self::range_mask(range))
+            }
+
+            /// Sets the booleans in the given range to false.
+            pub const fn clear_range(self, range: core::ops::Range<u8>) -> Self {
+                Self(self.0 & !Self::range_mask(range))
+            }
+
+            #[doc = concat!("Builds the `", stringify!($pkd), "`.")]
+            pub const fn build(self) -> $pkd { $pkd(self.0) }
+
+            #[track_caller]
+            const fn bit(idx: u8) -> $repr {
+                if idx >= $bcount {
+                    crate::macros::index_out_of_range(idx, $bcount);
+                }
+                1 << idx
+            }
+
+            const fn range_mask(range: core::ops::Range<u8>) -> $repr {
+                assert!(range.start <= range.end, "The range start cannot be greater than its end");
+                assert!(range.end <= $bcount, concat!("The range end cannot be greater than ", $bcount));
+                let len = range.end - range.start;
+                if len == 0 {
+                    0
+                } else {
+                    ($repr::MAX >> ($bcount - len)) << range.start
+                }
+            }
+        }
+
+        impl crate::typed_flags::private::Sealed for $pkd {}
+
+        impl crate::FlagStorage for $pkd {
+            const CAPACITY: u8 = $bcount;
+
+            fn flag(&self, idx: u8) -> bool {
+                self.get(idx)
+            }
+
+            fn set_flag(&mut self, idx: u8, val: bool) {
+                self.set(val, idx);
+            }
+        }
+
+        #[doc = concat!("An index into a `", stringify!($pkd), "`, which is always less than ", $bcount, ".")]
+        ///
+        /// The index is checked once when it is created,
+        /// so the methods taking it never panic.
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        pub struct $index(u8);
+
+        impl $index {
+            /// The first index.
+            pub const MIN: Self = Self(0);
+            /// The last index.
+            pub const MAX: Self = Self($bcountdec);
+
+            #[doc = concat!("Creates a new index, if it is less than ", $bcount, ".")]
+            pub const fn new(idx: u8) -> Option<Self> {
+                if idx < $bcount {
+                    Some(Self(idx))
+                } else {
+                    None
+                }
+            }
+
+            /// Returns the index as a `u8`.
+            pub const fn get(self) -> u8 {
+                self.0
+            }
+        }
+
+        impl From<$index> for u8 {
+            fn from(idx: $index) -> u8 { idx.0 }
+        }
+
+        impl $pkd {
+            /// Gets the boolean at the given checked index.
+            pub const fn get_at(&self, idx: $index) -> bool {
+                ((self.0 >> idx.0) & 1) != 0
+            }
+
+            /// Sets the boolean at the given checked index to val.
+            pub const fn set_at(&mut self, val: bool, idx: $index) {
+                self.0 = (self.0 & !(1 << idx.0)) | ((val as $repr) << idx.0);
+            }
+
+            /// Toggles the boolean at the given checked index.
+            pub const fn toggle_at(&mut self, idx: $index) {
+                self.0 ^= 1 << idx.0;
+            }
+        }
+
+        impl $pkd {
+            #[doc = concat!("Creates a new `", stringify!($pkd), "` from bits in the given order.")]
+            ///
+            /// With `BitOrder::Msb0`, the most significant bit becomes index 0.
+            pub const fn from_bits_ordered(bits: $repr, order: crate::BitOrder) -> Self {
+                match order {
+                    crate::BitOrder::Lsb0 => Self(bits),
+                    crate::BitOrder::Msb0 => Self(bits.reverse_bits()),
+                }
+            }
+
+            #[doc = concat!("Returns the bits of this `", stringify!($pkd), "` in the given order.")]
+            ///
+            /// With `BitOrder::Msb0`, index 0 becomes the most significant bit.
+            pub const fn to_bits_ordered(self, order: crate::BitOrder) -> $repr {
+                match order {
+                    crate::BitOrder::Lsb0 => self.0,
+                    crate::BitOrder::Msb0 => self.0.reverse_bits(),
+                }
+            }
+
+            /// Returns an adapter that formats the bits in the given order.
+            pub const fn ordered(self, order: crate::BitOrder) -> crate::OrderedBits<Self> {
+                crate::OrderedBits::new(self, order)
+            }
+        }
+
+        #[doc = concat!("Displays the ", stringify!($pkd), " in binary, with its bits in the chosen order.")]
+        impl core::fmt::Binary for crate::OrderedBits<$pkd> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let bits = self.value().to_bits_ordered(self.order());
+                core::fmt::Binary::fmt(&$pkd(bits), f)
+            }
+        }
+
+        #[doc = concat!("Displays the ", stringify!($pkd), " in lowercase hexadecimal, with its bits in the chosen order.")]
+        impl core::fmt::LowerHex for crate::OrderedBits<$pkd> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let bits = self.value().to_bits_ordered(self.order());
+                core::fmt::LowerHex::fmt(&$pkd(bits), f)
+            }
+        }
+
+        #[doc = concat!("Displays the ", stringify!($pkd), " in uppercase hexadecimal, with its bits in the chosen order.")]
+        impl core::fmt::UpperHex for crate::OrderedBits<$pkd> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let bits = self.value().to_bits_ordered(self.order());
+                core::fmt::UpperHex::fmt(&$pkd(bits), f)
+            }
+        }
+
+        impl Iterator for crate::GrayCodes<$pkd> {
+            type Item = $pkd;
+
+            fn next(&mut self) -> Option<$pkd> {
+                let counter = self.counter?;
+                self.counter = counter.checked_increment();
+                Some(counter.to_gray())
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                // this is only None if every one of the 2^128 values of a u128 remains
+                let remaining = match self.counter {
+                    Some(counter) => u128::from($repr::MAX - counter.0).checked_add(1),
+                    None => Some(0),
+                };
+                match remaining.and_then(|len| usize::try_from(len).ok()) {
+                    Some(len) => (len, Some(len)),
+                    None => (usize::MAX, None),
+                }
+            }
+        }
+
+        impl core::iter::FusedIterator for crate::GrayCodes<$pkd> {}
+
+        impl Iterator for crate::Ones<$pkd> {
+            type Item = u8;
+
+            fn next(&mut self) -> Option<u8> {
+                let bits = self.remaining.0;
+                if bits == 0 {
+                    return None;
+                }
+                // clear the lowest true value
+                self.remaining.0 = bits & (bits - 1);
+                Some(bits.trailing_zeros() as u8)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.len();
+                (len, Some(len))
+            }
+        }
+
+        impl DoubleEndedIterator for crate::Ones<$pkd> {
+            fn next_back(&mut self) -> Option<u8> {
+                let bits = self.remaining.0;
+                if bits == 0 {
+                    return None;
+                }
+                let idx = $bcountdec - bits.leading_zeros() as u8;
+                self.remaining.0 = bits ^ (1 << idx);
+                Some(idx)
+            }
+        }
+
+        impl ExactSizeIterator for crate::Ones<$pkd> {
+            fn len(&self) -> usize {
+                self.remaining.0.count_ones() as usize
+            }
+        }
+
+        impl core::iter::FusedIterator for crate::Ones<$pkd> {}
+
+        impl $pkd {
+            /// Reads a value from a pointer with a volatile read, such as from a memory-mapped register.
+            ///
+            /// # Safety
+            ///
+            /// This has the same requirements as [`core::ptr::read_volatile`].
+            pub unsafe fn read_volatile(src: *const $repr) -> Self {
+                // SAFETY: the caller upholds the requirements
+                Self(unsafe { core::ptr::read_volatile(src) })
+            }
+
+            /// Writes a value to a pointer with a volatile write, such as to a memory-mapped register.
+            ///
+            /// # Safety
+            ///
+            /// This has the same requirements as [`core::ptr::write_volatile`].
+            pub unsafe fn write_volatile(dst: *mut $repr, val: Self) {
+                // SAFETY: the caller upholds the requirements
+                unsafe { core::ptr::write_volatile(dst, val.0) }
+            }
+        }
+
+        impl<'a> crate::RegisterBits<'a, $pkd> {
+            /// Creates a handle to the register at the given address.
+            ///
+            /// # Safety
+            ///
+            /// The pointer must be valid for volatile reads and writes, and aligned,
+            /// for as long as the handle is used.
+            pub unsafe fn new(ptr: *mut $repr) -> Self {
+                Self::from_ptr(ptr.cast())
+            }
+
+            /// Reads the whole register.
+            pub fn read(&self) -> $pkd {
+                // SAFETY: the pointer is valid, as required by `new`,
+                // and the packed type is `repr(transparent)` over the register's type
+                unsafe { $pkd::read_volatile(self.ptr.cast()) }
+            }
+
+            /// Writes the whole register.
+            pub fn write(&self, val: $pkd) {
+                // SAFETY: the pointer is valid, as required by `new`
+                unsafe { $pkd::write_volatile(self.ptr.cast(), val) }
+            }
+
+            /// Reads the register, applies `f` to its value, and writes the result back.
+            pub fn modify(&self, f: impl FnOnce($pkd) -> $pkd) {
+                self.write(f(self.read()));
+            }
+
+            /// Reads the boolean at the given index.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn get(&self, idx: u8) -> bool {
+                self.read().get(idx)
+            }
+
+            /// Sets the boolean at the given index to val, by reading the register and writing it back.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn set(&self, val: bool, idx: u8) {
+                let mut bits = self.read();
+                bits.set(val, idx);
+                self.write(bits);
+            }
+
+            /// Toggles the boolean at the given index, by reading the register and writing it back.
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the given index is greater than ", $bcountdec, ".")]
+            #[track_caller]
+            pub fn toggle(&self, idx: u8) {
+                let mut bits = self.read();
+                bits.toggle(idx);
+                self.write(bits);
+            }
+        }
+
+        impl crate::ChangeSet<$pkd> {
+            /// Returns the values that changed from false to true.
+            pub const fn raised(&self) -> $pkd {
+                $pkd(self.after().0 & !self.before().0)
+            }
+
+            /// Returns the values that changed from true to false.
+            pub const fn cleared(&self) -> $pkd {
+                $pkd(self.before().0 & !self.after().0)
+            }
+
+            /// Returns the values that changed.
+            pub const fn changed(&self) -> $pkd {
+                $pkd(self.before().0 ^ self.after().0)
+            }
+
+            /// Returns true if no values changed.
+            pub const fn is_empty(&self) -> bool {
+                self.changed().0 == 0
+            }
+
+            /// Returns an iterator over the indices of the values that changed from false to true.
+            pub const fn raised_indices(&self) -> crate::Ones<$pkd> {
+                self.raised().ones()
+            }
+
+            /// Returns an iterator over the indices of the values that changed from true to false.
+            pub const fn cleared_indices(&self) -> crate::Ones<$pkd> {
+                self.cleared().ones()
+            }
+
+            /// Returns an iterator over the indices of the values that changed.
+            pub const fn changed_indices(&self) -> crate::Ones<$pkd> {
+                self.changed().ones()
+            }
+
+            /// Applies the same changes to another value,
+            /// setting the raised values and clearing the cleared ones.
+            pub const fn apply(&self, value: $pkd) -> $pkd {
+                $pkd((value.0 & !self.cleared().0) | self.raised().0)
+            }
+        }
+
+        impl crate::Pairs<$pkd> {
+            fn pair(&self, idx: u8) -> (bool, bool) {
+                (((self.a.0 >> idx) & 1) != 0, ((self.b.0 >> idx) & 1) != 0)
+            }
+        }
+
+        impl Iterator for crate::Pairs<$pkd> {
+            type Item = (bool, bool);
+
+            fn next(&mut self) -> Option<(bool, bool)> {
+                self.range.next().map(|idx| self.pair(idx))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.range.size_hint()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<(bool, bool)> {
+                self.range.nth(n).map(|idx| self.pair(idx))
+            }
+        }
+
+        impl DoubleEndedIterator for crate::Pairs<$pkd> {
+            fn next_back(&mut self) -> Option<(bool, bool)> {
+                self.range.next_back().map(|idx| self.pair(idx))
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<(bool, bool)> {
+                self.range.nth_back(n).map(|idx| self.pair(idx))
+            }
+        }
+
+        impl ExactSizeIterator for crate::Pairs<$pkd> {
+            fn len(&self) -> usize {
+                self.range.len()
+            }
+        }
+
+        impl core::iter::FusedIterator for crate::Pairs<$pkd> {}
+
+        impl Iterator for crate::Combinations<$pkd> {
+            type Item = $pkd;
+
+            fn next(&mut self) -> Option<$pkd> {
+                let current = self.next?;
+                let x = current.0;
+                // Gosper's hack: move the lowest block of ones up by one,
+                // and pack the rest of that block down at the bottom
+                self.next = if x == 0 {
+                    None
+                } else {
+                    let lowest = x & x.wrapping_neg();
+                    x.checked_add(lowest).map(|ripple| $pkd((((ripple ^ x) >> 2) / lowest) | ripple))
+                };
+                Some(current)
+            }
+        }
+
+        impl core::iter::FusedIterator for crate::Combinations<$pkd> {}
+
+        impl FromIterator<$pkd> for crate::Union<$pkd> {
+            fn from_iter<I: IntoIterator<Item = $pkd>>(iter: I) -> Self {
+                crate::Union(iter.into_iter().fold($pkd::new(), |acc, val| acc | val))
+            }
+        }
+
+        impl<'a> FromIterator<&'a $pkd> for crate::Union<$pkd> {
+            fn from_iter<I: IntoIterator<Item = &'a $pkd>>(iter: I) -> Self {
+                iter.into_iter().copied().collect()
+            }
+        }
+
+        impl core::iter::Sum<$pkd> for crate::Union<$pkd> {
+            fn sum<I: Iterator<Item = $pkd>>(iter: I) -> Self {
+                iter.collect()
+            }
+        }
+
+        impl Extend<$pkd> for crate::Union<$pkd> {
+            fn extend<I: IntoIterator<Item = $pkd>>(&mut self, iter: I) {
+                self.0 |= iter.into_iter().collect::<Self>().0;
+            }
+        }
+
+        impl FromIterator<$pkd> for crate::Intersection<$pkd> {
+            fn from_iter<I: IntoIterator<Item = $pkd>>(iter: I) -> Self {
+                crate::Intersection(iter.into_iter().fold(!$pkd::new(), |acc, val| acc & val))
+            }
+        }
+
+        impl<'a> FromIterator<&'a $pkd> for crate::Intersection<$pkd> {
+            fn from_iter<I: IntoIterator<Item = &'a $pkd>>(iter: I) -> Self {
+                iter.into_iter().copied().collect()
+            }
+        }
+
+        impl core::iter::Sum<$pkd> for crate::Intersection<$pkd> {
+            fn sum<I: Iterator<Item = $pkd>>(iter: I) -> Self {
+                iter.collect()
+            }
+        }
+
+        impl Extend<$pkd> for crate::Intersection<$pkd> {
+            fn extend<I: IntoIterator<Item = $pkd>>(&mut self, iter: I) {
+                self.0 &= iter.into_iter().collect::<Self>().0;
+            }
+        }
+
+        $(
+            impl From<$pkd> for $wider {
+                fn from(pkd: $pkd) -> $wider { <$wider>::from(pkd.0) }
+            }
+
+            impl TryFrom<$wider> for $pkd {
+                type Error = crate::StrayBitsError;
+
+                fn try_from(bits: $wider) -> Result<Self, crate::StrayBitsError> {
+                    $repr::try_from(bits).map(Self).map_err(|_| {
+                        crate::StrayBitsError::new(u128::from(bits) & !u128::from($repr::MAX), $bcount)
+                    })
+                }
+            }
+        )*
+
+        $(
+            impl From<$pkd> for crate::$wider_pkd {
+                fn from(pkd: $pkd) -> crate::$wider_pkd { crate::$wider_pkd::from_bits(pkd.0.into()) }
+            }
+
+            impl TryFrom<crate::$wider_pkd> for $pkd {
+                type Error = crate::StrayBitsError;
+
+                fn try_from(pkd: crate::$wider_pkd) -> Result<Self, crate::StrayBitsError> {
+                    Self::try_from(pkd.to_bits())
+                }
+            }
+        )*
+
+        impl core::str::FromStr for $pkd {
+            type Err = crate::ParseBitsError;
+
+            /// Parses a value from binary digits, in the same way as `from_ascii_bits`.
+            fn from_str(s: &str) -> Result<Self, crate::ParseBitsError> {
+                Self::from_ascii_bits(s.as_bytes())
+            }
         }
 
         impl From<[bool; $bcount]> for $pkd {
             fn from(bools: [bool; $bcount]) -> Self { Self::new_vals(bools) }
         }
 
+        impl PartialEq<[bool; $bcount]> for $pkd {
+            fn eq(&self, other: &[bool; $bcount]) -> bool { *self == Self::new_vals(*other) }
+        }
+
+        impl PartialEq<$pkd> for [bool; $bcount] {
+            fn eq(&self, other: &$pkd) -> bool { other == self }
+        }
+
+        impl PartialEq<$repr> for $pkd {
+            fn eq(&self, other: &$repr) -> bool { self.0 == *other }
+        }
+
+        impl PartialEq<$pkd> for $repr {
+            fn eq(&self, other: &$pkd) -> bool { other == self }
+        }
+
         crate::macros::impl_binops!{ impl & | ^ for $pkd }
+        crate::macros::impl_binops!{ impl & | ^ for $pkd, $repr => $pkd::from_bits }
+        crate::macros::impl_binops!{ impl & | ^ for $pkd, [bool; $bcount] => $pkd::new_vals }
+
+        crate::macros::const_impl! {
+            impl core::ops::Not for $pkd {
+                type Output = $pkd;
+                fn not(self) -> Self { Self(!self.0) }
+            }
+        }
 
-        impl core::ops::Not for $pkd {
-            type Output = $pkd;
-            fn not(self) -> Self { Self(!self.0) }
+        crate::macros::const_impl! {
+            impl core::ops::Not for &$pkd {
+                type Output = $pkd;
+                fn not(self) -> $pkd { $pkd(!self.0) }
+            }
         }
 
-        impl core::ops::Not for &$pkd {
-            type Output = $pkd;
-            fn not(self) -> $pkd { $pkd(!self.0) }
+        impl core::fmt::Display for crate::GroupedBits<$pkd> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if f.alternate() {
+                    f.write_str("0b")?;
+                }
+                for group in (0..$bcount / 4).rev() {
+                    write!(f, "{:04b}", (self.value.0 >> (group * 4)) & 0b1111)?;
+                    if group != 0 {
+                        f.write_str("_")?;
+                    }
+                }
+                Ok(())
+            }
         }
 
         impl core::fmt::Debug for $pkd {
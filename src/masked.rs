@@ -0,0 +1,212 @@
+//! Matching packed values against patterns with wildcards.
+
+use core::{fmt, ops};
+
+use crate::{PackedBools8, ParseBitsError};
+
+/// A pattern for `PackedBools8` values, made of a value and a mask of which indices to compare.
+///
+/// Indices where the mask is false are wildcards, which match anything.
+/// This is how acceptance filters are specified for CAN and Ethernet hardware.
+///
+/// ```
+/// use packed_booleans::{MaskedBools8, PackedBools8};
+///
+/// let filter = MaskedBools8::from_pattern(b"1x0x_xx1x").unwrap();
+/// assert!(filter.matches(PackedBools8::from_bits(0b1100_0010)));
+/// assert!(!filter.matches(PackedBools8::from_bits(0b1110_0010)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct MaskedBools8 {
+    /// The values to compare against, which are always false where `mask` is false.
+    value: u8,
+    mask: u8,
+}
+
+impl MaskedBools8 {
+    /// A pattern that matches every value.
+    pub const ANY: Self = Self { value: 0, mask: 0 };
+
+    /// Creates a new pattern from a value and a mask of which indices to compare.
+    ///
+    /// The values where `mask` is false are ignored.
+    pub const fn new(value: PackedBools8, mask: PackedBools8) -> Self {
+        Self { value: value.to_bits() & mask.to_bits(), mask: mask.to_bits() }
+    }
+
+    /// Creates a pattern that only matches the given value.
+    pub const fn exact(value: PackedBools8) -> Self {
+        Self { value: value.to_bits(), mask: u8::MAX }
+    }
+
+    /// Parses a pattern of ASCII `0`, `1` and `x` digits, with the last index first,
+    /// in the same order as the `Binary` output of `PackedBools8`.
+    ///
+    /// An `x` (or `X`) is a wildcard, and underscores between the digits are ignored.
+    /// There must be exactly 8 digits.
+    pub const fn from_pattern(pattern: &[u8]) -> Result<Self, ParseBitsError> {
+        let mut value = 0u8;
+        let mut mask = 0u8;
+        let mut digits = 0;
+        let mut i = 0;
+        while i < pattern.len() {
+            let (v, m) = match pattern[i] {
+                b'_' => {
+                    i += 1;
+                    continue;
+                }
+                b'0' => (0, 1),
+                b'1' => (1, 1),
+                b'x' | b'X' => (0, 0),
+                byte => return Err(ParseBitsError::InvalidDigit { index: i, byte }),
+            };
+            if digits < 8 {
+                value = (value << 1) | v;
+                mask = (mask << 1) | m;
+            }
+            digits += 1;
+            i += 1;
+        }
+        if digits != 8 {
+            return Err(ParseBitsError::WrongLength { expected: 8, found: digits });
+        }
+        Ok(Self { value, mask })
+    }
+
+    /// Returns the values being compared against, with the wildcards false.
+    pub const fn value(self) -> PackedBools8 {
+        PackedBools8::from_bits(self.value)
+    }
+
+    /// Returns which indices are compared.
+    pub const fn mask(self) -> PackedBools8 {
+        PackedBools8::from_bits(self.mask)
+    }
+
+    /// Returns true if `val` matches the pattern at every index that isn't a wildcard.
+    pub const fn matches(self, val: PackedBools8) -> bool {
+        (val.to_bits() ^ self.value) & self.mask == 0
+    }
+
+    /// Returns the pattern matching only the values that match both `self` and `other`,
+    /// or `None` if no value matches both.
+    pub const fn intersect(self, other: Self) -> Option<Self> {
+        if (self.value ^ other.value) & self.mask & other.mask != 0 {
+            None
+        } else {
+            Some(Self { value: self.value | other.value, mask: self.mask | other.mask })
+        }
+    }
+
+    /// Returns the narrowest pattern matching every value that matches `self` or `other`.
+    ///
+    /// This may also match values that match neither.
+    pub const fn cover(self, other: Self) -> Self {
+        let mask = self.mask & other.mask & !(self.value ^ other.value);
+        Self { value: self.value & mask, mask }
+    }
+}
+
+/// Combines two patterns with [`MaskedBools8::cover`].
+impl ops::BitOr for MaskedBools8 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.cover(rhs)
+    }
+}
+
+impl ops::BitOrAssign for MaskedBools8 {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.cover(rhs);
+    }
+}
+
+impl core::str::FromStr for MaskedBools8 {
+    type Err = ParseBitsError;
+
+    /// Parses a pattern in the same way as `from_pattern`.
+    fn from_str(s: &str) -> Result<Self, ParseBitsError> {
+        Self::from_pattern(s.as_bytes())
+    }
+}
+
+impl From<PackedBools8> for MaskedBools8 {
+    fn from(value: PackedBools8) -> Self {
+        Self::exact(value)
+    }
+}
+
+/// Displays the pattern in the format that `from_pattern` parses, without underscores.
+impl fmt::Display for MaskedBools8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for idx in (0..8).rev() {
+            let c = match ((self.mask >> idx) & 1, (self.value >> idx) & 1) {
+                (0, _) => 'x',
+                (_, 0) => '0',
+                _ => '1',
+            };
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for MaskedBools8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MaskedBools8({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::MaskedBools8;
+    use crate::{PackedBools8, ParseBitsError};
+
+    fn pat(s: &str) -> MaskedBools8 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let filter = pat("1x0x_xx1x");
+        assert_eq!(filter.value(), 0b1000_0010);
+        assert_eq!(filter.mask(), 0b1010_0010);
+        assert_eq!(format!("{filter:?}"), "MaskedBools8(1x0xxx1x)");
+        assert_eq!(pat("XXXXXXXX"), MaskedBools8::ANY);
+        assert_eq!(
+            MaskedBools8::from_pattern(b"1x0x_xx1"),
+            Err(ParseBitsError::WrongLength { expected: 8, found: 7 })
+        );
+        assert_eq!(
+            MaskedBools8::from_pattern(b"1x0x_xx1?"),
+            Err(ParseBitsError::InvalidDigit { index: 8, byte: b'?' })
+        );
+    }
+
+    #[test]
+    fn matching() {
+        let filter = MaskedBools8::new(PackedBools8::from_bits(0xff), PackedBools8::from_bits(0x0f));
+        assert!(filter.matches(PackedBools8::from_bits(0x0f)));
+        assert!(filter.matches(PackedBools8::from_bits(0xaf)));
+        assert!(!filter.matches(PackedBools8::from_bits(0xae)));
+        assert!(MaskedBools8::ANY.matches(PackedBools8::from_bits(0x12)));
+    }
+
+    #[test]
+    fn combining() {
+        let a = pat("1xxx_0000");
+        let b = pat("1xx1_xxx1");
+        assert_eq!(a.intersect(b), None);
+        assert_eq!(a.intersect(pat("x1xx_xxxx")), Some(pat("11xx_0000")));
+
+        let cover = pat("1100_0001") | pat("1100_0011");
+        assert_eq!(cover, pat("1100_00x1"));
+        assert!(cover.matches(PackedBools8::from_bits(0b1100_0001)));
+        assert!(cover.matches(PackedBools8::from_bits(0b1100_0011)));
+        assert_eq!(MaskedBools8::exact(PackedBools8::from_bits(0x0f)) | MaskedBools8::ANY, MaskedBools8::ANY);
+    }
+}
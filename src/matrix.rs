@@ -0,0 +1,182 @@
+//! An 8x8 matrix of booleans packed into a `PackedBools64`.
+
+use core::fmt;
+
+use crate::{bitboard, PackedBools64};
+
+/// An 8x8 matrix of booleans, stored row by row in a `PackedBools64`.
+///
+/// The value at `(row, col)` is at index `row * 8 + col`, so row `r` is byte `r` of the bits.
+/// Row 0 is the top row and column 0 is the left column.
+///
+/// The flips and rotations move every value at once with a few shifts and masks.
+///
+/// ```
+/// use packed_booleans::BitMatrix8x8;
+///
+/// let mut m = BitMatrix8x8::new();
+/// m.set(true, 0, 1);
+/// let r = m.rotate90();
+/// assert!(r.get(1, 7));
+/// assert_eq!(r.rotate270(), m);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(transparent)]
+pub struct BitMatrix8x8(PackedBools64);
+
+impl BitMatrix8x8 {
+    /// Creates a new matrix with all false values.
+    pub const fn new() -> Self {
+        Self(PackedBools64::new())
+    }
+
+    /// Creates a matrix from its packed values, with `(row, col)` at index `row * 8 + col`.
+    pub const fn from_packed(bits: PackedBools64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the packed values, with `(row, col)` at index `row * 8 + col`.
+    pub const fn to_packed(self) -> PackedBools64 {
+        self.0
+    }
+
+    /// Creates a matrix from its rows, where bit `col` of each row is the value in that column.
+    pub const fn from_rows(rows: [u8; 8]) -> Self {
+        Self(PackedBools64::from_le_bytes(rows))
+    }
+
+    /// Returns the rows of the matrix, where bit `col` of each row is the value in that column.
+    pub const fn to_rows(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+
+    /// Gets the value at the given row and column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or column is greater than 7.
+    #[track_caller]
+    pub const fn get(&self, row: u8, col: u8) -> bool {
+        self.0.get(Self::index(row, col))
+    }
+
+    /// Sets the value at the given row and column to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or column is greater than 7.
+    #[track_caller]
+    pub const fn set(&mut self, val: bool, row: u8, col: u8) {
+        self.0.set(val, Self::index(row, col));
+    }
+
+    #[track_caller]
+    const fn index(row: u8, col: u8) -> u8 {
+        assert!(row < 8 && col < 8, "The row and column cannot be greater than 7");
+        row * 8 + col
+    }
+
+    /// Mirrors the matrix left to right, reversing the order of the columns.
+    pub const fn flip_horizontal(self) -> Self {
+        Self(bitboard::mirror_horizontal(self.0))
+    }
+
+    /// Mirrors the matrix top to bottom, reversing the order of the rows.
+    pub const fn flip_vertical(self) -> Self {
+        Self(bitboard::flip_vertical(self.0))
+    }
+
+    /// Swaps the rows and columns, so `(row, col)` moves to `(col, row)`.
+    pub const fn transpose(self) -> Self {
+        Self(bitboard::flip_diagonal(self.0))
+    }
+
+    /// Rotates the matrix a quarter turn clockwise, so `(row, col)` moves to `(col, 7 - row)`.
+    pub const fn rotate90(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Rotates the matrix a half turn, so `(row, col)` moves to `(7 - row, 7 - col)`.
+    pub const fn rotate180(self) -> Self {
+        Self(PackedBools64::from_bits(self.0.to_bits().reverse_bits()))
+    }
+
+    /// Rotates the matrix a quarter turn counterclockwise, so `(row, col)` moves to `(7 - col, row)`.
+    pub const fn rotate270(self) -> Self {
+        self.transpose().flip_vertical()
+    }
+}
+
+impl From<PackedBools64> for BitMatrix8x8 {
+    fn from(bits: PackedBools64) -> Self {
+        Self(bits)
+    }
+}
+
+impl From<BitMatrix8x8> for PackedBools64 {
+    fn from(matrix: BitMatrix8x8) -> Self {
+        matrix.0
+    }
+}
+
+/// Shows the matrix as rows of `0`s and `1`s, with the top row first and column 0 on the left.
+impl fmt::Debug for BitMatrix8x8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitMatrix8x8(")?;
+        for (idx, row) in self.to_rows().into_iter().enumerate() {
+            if idx != 0 {
+                f.write_str(" ")?;
+            }
+            (0..8).try_for_each(|col| f.write_str(if row & (1 << col) != 0 { "1" } else { "0" }))?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::BitMatrix8x8;
+    use crate::PackedBools64;
+
+    /// Builds a matrix by mapping every cell to the cell it comes from.
+    fn remap(m: BitMatrix8x8, from: impl Fn(u8, u8) -> (u8, u8)) -> BitMatrix8x8 {
+        let mut out = BitMatrix8x8::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                let (r, c) = from(row, col);
+                out.set(m.get(r, c), row, col);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn flips_and_rotations() {
+        for bits in [0, u64::MAX, 0x0123_4567_89ab_cdef, 0x8000_0000_0000_0001, 1 << 10 | 1 << 47] {
+            let m = BitMatrix8x8::from_packed(PackedBools64::from_bits(bits));
+            assert_eq!(m.flip_horizontal(), remap(m, |r, c| (r, 7 - c)));
+            assert_eq!(m.flip_vertical(), remap(m, |r, c| (7 - r, c)));
+            assert_eq!(m.transpose(), remap(m, |r, c| (c, r)));
+            assert_eq!(m.rotate90(), remap(m, |r, c| (7 - c, r)));
+            assert_eq!(m.rotate180(), remap(m, |r, c| (7 - r, 7 - c)));
+            assert_eq!(m.rotate270(), remap(m, |r, c| (c, 7 - r)));
+            assert_eq!(m.rotate90().rotate90(), m.rotate180());
+            assert_eq!(m.rotate90().rotate270(), m);
+        }
+    }
+
+    #[test]
+    fn rows() {
+        let m = BitMatrix8x8::from_rows([0b1, 0, 0, 0, 0, 0, 0, 0b1000_0000]);
+        assert!(m.get(0, 0) && m.get(7, 7));
+        assert_eq!(m.to_rows()[7], 0x80);
+        assert_eq!(
+            format!("{m:?}"),
+            "BitMatrix8x8(10000000 00000000 00000000 00000000 00000000 00000000 00000000 00000001)"
+        );
+    }
+}
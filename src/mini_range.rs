@@ -0,0 +1,226 @@
+//! A range of small integers packed into a single byte.
+
+use core::{fmt, iter::FusedIterator, ops::Range};
+
+/// A half-open range `start..end` of integers up to 15, stored in one byte.
+///
+/// The start is kept in the high 4 bits and the end in the low 4 bits.
+/// Like `Range<u8>`, the range is empty when the start isn't less than the end,
+/// and iterating it yields every integer from the start up to but not including the end.
+///
+/// ```
+/// use packed_booleans::MiniRange8;
+///
+/// let mut range = MiniRange8::new(2, 6);
+/// assert_eq!(core::mem::size_of_val(&range), 1);
+/// assert!(range.contains(5));
+/// assert_eq!(range.next(), Some(2));
+/// assert_eq!(range.next_back(), Some(5));
+/// assert_eq!(range.len(), 2);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MiniRange8(u8);
+
+impl MiniRange8 {
+    /// The greatest start or end a range can have.
+    pub const MAX: u8 = 15;
+
+    /// Creates the range `start..end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `end` is greater than [`MAX`](Self::MAX).
+    pub const fn new(start: u8, end: u8) -> Self {
+        match Self::try_new(start, end) {
+            Some(range) => range,
+            None => panic!("The start and end of a MiniRange8 cannot be greater than 15"),
+        }
+    }
+
+    /// Creates the range `start..end`,
+    /// or returns `None` if `start` or `end` is greater than [`MAX`](Self::MAX).
+    pub const fn try_new(start: u8, end: u8) -> Option<Self> {
+        if start > Self::MAX || end > Self::MAX {
+            return None;
+        }
+        Some(Self((start << 4) | end))
+    }
+
+    /// Returns the start of the range.
+    pub const fn start(&self) -> u8 {
+        self.0 >> 4
+    }
+
+    /// Returns the end of the range, which is not included in it.
+    pub const fn end(&self) -> u8 {
+        self.0 & 0x0f
+    }
+
+    /// Returns the number of integers in the range.
+    pub const fn len(&self) -> u8 {
+        self.end().saturating_sub(self.start())
+    }
+
+    /// Returns true if the range contains no integers.
+    pub const fn is_empty(&self) -> bool {
+        self.start() >= self.end()
+    }
+
+    /// Returns true if the range contains the given integer.
+    pub const fn contains(&self, val: u8) -> bool {
+        self.start() <= val && val < self.end()
+    }
+
+    /// Converts the range into a `Range<u8>`.
+    pub const fn to_range(self) -> Range<u8> {
+        self.start()..self.end()
+    }
+
+    /// Replaces the start, which must be at most `MAX`.
+    const fn set_start(&mut self, start: u8) {
+        self.0 = (start << 4) | self.end();
+    }
+
+    /// Replaces the end, which must be at most `MAX`.
+    const fn set_end(&mut self, end: u8) {
+        self.0 = (self.0 & 0xf0) | end;
+    }
+}
+
+impl Default for MiniRange8 {
+    /// Returns the empty range `0..0`.
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+impl fmt::Debug for MiniRange8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiniRange8({}..{})", self.start(), self.end())
+    }
+}
+
+impl From<MiniRange8> for Range<u8> {
+    fn from(range: MiniRange8) -> Self {
+        range.to_range()
+    }
+}
+
+impl TryFrom<Range<u8>> for MiniRange8 {
+    type Error = Range<u8>;
+
+    /// Converts a `Range<u8>`, or returns it back if its start or end is greater than `MAX`.
+    fn try_from(range: Range<u8>) -> Result<Self, Range<u8>> {
+        Self::try_new(range.start, range.end).ok_or(range)
+    }
+}
+
+impl Iterator for MiniRange8 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if MiniRange8::is_empty(self) {
+            return None;
+        }
+        let start = self.start();
+        // start < end <= MAX, so this can't overflow the nibble
+        self.set_start(start + 1);
+        Some(start)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(MiniRange8::len(self));
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        match u8::try_from(n).ok().filter(|&n| n < MiniRange8::len(self)) {
+            Some(n) => {
+                self.set_start(self.start() + n);
+                self.next()
+            }
+            None => {
+                self.set_start(self.end().max(self.start()));
+                None
+            }
+        }
+    }
+
+    fn last(mut self) -> Option<u8> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for MiniRange8 {
+    fn next_back(&mut self) -> Option<u8> {
+        if MiniRange8::is_empty(self) {
+            return None;
+        }
+        let end = self.end() - 1;
+        self.set_end(end);
+        Some(end)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<u8> {
+        match u8::try_from(n).ok().filter(|&n| n < MiniRange8::len(self)) {
+            Some(n) => {
+                self.set_end(self.end() - n);
+                self.next_back()
+            }
+            None => {
+                self.set_end(self.start().min(self.end()));
+                None
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for MiniRange8 {}
+
+impl FusedIterator for MiniRange8 {}
+
+#[cfg(test)]
+mod tests {
+    use super::MiniRange8;
+
+    #[test]
+    fn matches_range() {
+        for start in 0..=15 {
+            for end in 0..=15 {
+                let range = MiniRange8::new(start, end);
+                assert_eq!(range.len() as usize, (start..end).len());
+                assert!(range.eq(start..end));
+                assert!(range.rev().eq((start..end).rev()));
+                assert_eq!(range.contains(start), (start..end).contains(&start));
+                for n in 0..17 {
+                    let (mut mini, mut std) = (range, start..end);
+                    assert_eq!(mini.nth(n), std.nth(n));
+                    assert!(mini.eq(std));
+                    let (mut mini, mut std) = (range, start..end);
+                    assert_eq!(mini.nth_back(n), std.nth_back(n));
+                    assert!(mini.rev().eq(std.rev()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(MiniRange8::try_new(3, 16), None);
+        assert_eq!(MiniRange8::try_from(0..15), Ok(MiniRange8::new(0, 15)));
+        assert_eq!(MiniRange8::try_from(16..20), Err(16..20));
+        let mut full = MiniRange8::new(0, 15);
+        assert_eq!(full.next_back(), Some(14));
+        assert_eq!(full.by_ref().count(), 14);
+        assert_eq!(full.next(), None);
+        assert!(MiniRange8::default().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_large() {
+        MiniRange8::new(16, 2);
+    }
+}
@@ -0,0 +1,65 @@
+//! Morton (Z-order) codes for two-dimensional coordinates.
+//!
+//! A Morton code interleaves the bits of the coordinates, with bit `i` of `x` at bit `2 * i`
+//! and bit `i` of `y` at bit `2 * i + 1` of the code.
+//! Sorting cells by their codes keeps nearby cells close together,
+//! and every aligned square with a power of two side is a single run of codes.
+//!
+//! ```
+//! use packed_booleans::morton;
+//!
+//! let code = morton::interleave_coords(0b11, 0b10);
+//! assert_eq!(code, 0b1101);
+//! assert_eq!(morton::deinterleave(code), (0b11, 0b10));
+//! ```
+
+/// Returns the Morton code of `(x, y)`, with the bits of `x` in the even bits of the code.
+pub const fn interleave_coords(x: u32, y: u32) -> u64 {
+    spread(x) | (spread(y) << 1)
+}
+
+/// Returns the coordinates `(x, y)` of a Morton code, undoing [`interleave_coords`].
+pub const fn deinterleave(code: u64) -> (u32, u32) {
+    (compact(code), compact(code >> 1))
+}
+
+/// Moves bit `i` of `val` to bit `2 * i`, leaving the odd bits false.
+const fn spread(val: u32) -> u64 {
+    let mut val = val as u64;
+    val = (val | (val << 16)) & 0x0000_ffff_0000_ffff;
+    val = (val | (val << 8)) & 0x00ff_00ff_00ff_00ff;
+    val = (val | (val << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    val = (val | (val << 2)) & 0x3333_3333_3333_3333;
+    (val | (val << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Moves bit `2 * i` of `val` to bit `i`, ignoring the odd bits.
+const fn compact(val: u64) -> u32 {
+    let mut val = val & 0x5555_5555_5555_5555;
+    val = (val | (val >> 1)) & 0x3333_3333_3333_3333;
+    val = (val | (val >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    val = (val | (val >> 4)) & 0x00ff_00ff_00ff_00ff;
+    val = (val | (val >> 8)) & 0x0000_ffff_0000_ffff;
+    (val | (val >> 16)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deinterleave, interleave_coords};
+
+    fn naive(x: u32, y: u32) -> u64 {
+        (0..32).fold(0, |acc, i| acc | (u64::from(x >> i & 1) << (2 * i)) | (u64::from(y >> i & 1) << (2 * i + 1)))
+    }
+
+    #[test]
+    fn round_trip() {
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (5, 9), (0xdead_beef, 0x1234_5678), (u32::MAX, 0), (u32::MAX, u32::MAX)] {
+            let code = interleave_coords(x, y);
+            assert_eq!(code, naive(x, y));
+            assert_eq!(deinterleave(code), (x, y));
+        }
+        assert_eq!(deinterleave(u64::MAX), (u32::MAX, u32::MAX));
+        // the codes of a 2x2 square follow a Z
+        assert_eq!([(0, 0), (1, 0), (0, 1), (1, 1)].map(|(x, y)| interleave_coords(x, y)), [0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,146 @@
+//! Packing 128 booleans together into 16 bytes.
+
+use core::{iter::FusedIterator, ops};
+
+crate::macros::packed_bools_type!{
+    NAME = PackedBools128,
+    BUILDER = PackedBools128Builder,
+    INDEX = BitIndex128,
+    REPR = u128,
+    BOOL_COUNT = 128,
+    BCOUNT_MINUS1 = 127,
+    BYTE_DESCRIPTION = "sixteen bytes",
+    PRETTY_DEBUG = "PackedBools128(\n    {:#0130b},\n)",
+    DEBUG = "PackedBools128({:#0130b})",
+    BINARY = "{:0128b}",
+    LOW_HEX = "{:032x}",
+    UPPER_HEX = "{:032X}",
+    WIDER_INTS = [],
+    WIDER_TYPES = []
+}
+
+impl IntoIterator for PackedBools128 {
+    type Item = bool;
+    type IntoIter = IntoIter128;
+
+    fn into_iter(self) -> IntoIter128 {
+        IntoIter128::new(self)
+    }
+}
+
+/// An iterator over the booleans in a [`PackedBools128`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(not(doc), repr(C))]
+pub struct IntoIter128 {
+    bools: PackedBools128,
+    range: ops::Range<u8>
+}
+
+impl IntoIter128 {
+    fn new(bools: PackedBools128) -> Self {
+        Self { bools, range: 0..128 }
+    }
+
+    /// Returns the next boolean without advancing the iterator.
+    pub fn peek(&self) -> Option<bool> {
+        self.range.clone().next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    /// Returns the next boolean from the back without advancing the iterator.
+    pub fn peek_back(&self) -> Option<bool> {
+        self.range.clone().next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl Iterator for IntoIter128 {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        // try_get here because it strips the panicking path entirely
+        // None should never be returned
+        // but it should hopefully optimize the unreachable paths out
+        self.range.next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<bool> {
+        self.range.nth(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter128 {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<bool> {
+        self.range.nth_back(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl ExactSizeIterator for IntoIter128 {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+impl FusedIterator for IntoIter128 {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::PackedBools128;
+
+    #[test]
+    fn set_get() {
+        let mut pkd = PackedBools128::new();
+        pkd.set(true, 0);
+        pkd.set(true, 77);
+        pkd.set(true, 127);
+        assert!(pkd.get(77));
+        pkd.toggle(77);
+        assert!(!pkd.get(77));
+        assert_eq!(pkd.try_get(128), None);
+        assert_eq!(pkd.to_bits(), 1 << 127 | 1);
+        assert_eq!(pkd.count_true(), 2);
+        assert_eq!(pkd.rfind_true_from(126), Some(0));
+    }
+
+    #[test]
+    fn formatting() {
+        let pkd = PackedBools128::from_bits(0xd4);
+        assert_eq!(format!("{pkd:x}"), "000000000000000000000000000000d4");
+        assert_eq!(format!("{pkd:?}"), format!("PackedBools128({:#0130b})", 0xd4));
+    }
+
+    #[test]
+    fn iter() {
+        let bits = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        let pkd = PackedBools128::from_bits(bits);
+        assert_eq!(pkd.into_iter().len(), 128);
+        pkd.into_iter()
+            .enumerate()
+            .for_each(|(idx, b)| assert_eq!(b, (bits >> idx) & 1 != 0));
+        assert_eq!(pkd.ones().count(), 64);
+    }
+
+    #[test]
+    fn gray_codes_len() {
+        let mut codes = PackedBools128::gray_codes();
+        assert_eq!(codes.size_hint(), (usize::MAX, None));
+        assert_eq!(codes.next(), Some(PackedBools128::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 200 but the capacity is 128")]
+    fn get_out_of_range() {
+        PackedBools128::new().get(200);
+    }
+}
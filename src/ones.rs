@@ -0,0 +1,41 @@
+//! Iterating over the indices of the true values in a packed value.
+
+/// An iterator over the indices of the true values in a packed value, in increasing order.
+///
+/// This is created by the `ones` and `differences` methods on the packed types.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ones<T> {
+    /// The true values that haven't been yielded yet.
+    pub(crate) remaining: T,
+}
+
+impl<T> Ones<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self { remaining: value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn ones() {
+        let pkd = PackedBools8::from_bits(0b1001_0110);
+        assert_eq!(pkd.ones().collect::<Vec<_>>(), [1, 2, 4, 7]);
+        assert_eq!(pkd.ones().rev().collect::<Vec<_>>(), [7, 4, 2, 1]);
+        assert_eq!(pkd.ones().len(), 4);
+        assert_eq!(PackedBools8::new().ones().next(), None);
+    }
+
+    #[test]
+    fn differences() {
+        let a = PackedBools16::from_bits(0x8421);
+        let b = PackedBools16::from_bits(0x8811);
+        assert_eq!(a.differences(b).collect::<Vec<_>>(), [4, 5, 10, 11]);
+        assert_eq!(a.differences(a).len(), 0);
+    }
+}
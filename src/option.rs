@@ -0,0 +1,193 @@
+//! Packing optional booleans together.
+
+use crate::PackedBools8;
+
+/// A type containing 8 `Option<bool>` values, while only being two bytes.
+///
+/// Each value is either known to be true or false, or unknown,
+/// which is useful for comparing a partially specified state against a full one.
+///
+/// ```
+/// use packed_booleans::{PackedBools8, PackedOptionBools8};
+///
+/// let mut desired = PackedOptionBools8::new();
+/// desired.set(Some(true), 0);
+/// desired.set(Some(false), 3);
+///
+/// let actual = PackedBools8::from_bits(0b1000);
+/// // index 0 should be true and index 3 should be false
+/// assert_eq!(desired.mismatches(actual).to_bits(), 0b1001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct PackedOptionBools8 {
+    /// The known values, which are always false where `known` is false.
+    value: u8,
+    known: u8,
+}
+
+impl PackedOptionBools8 {
+    /// Creates a new `PackedOptionBools8` with every value unknown.
+    pub const fn new() -> Self {
+        Self { value: 0, known: 0 }
+    }
+
+    /// Creates a new `PackedOptionBools8` with every value known.
+    pub const fn from_packed(values: PackedBools8) -> Self {
+        Self { value: values.to_bits(), known: u8::MAX }
+    }
+
+    /// Creates a new `PackedOptionBools8` from its values and which of them are known.
+    ///
+    /// The values where `known` is false are ignored.
+    pub const fn from_parts(values: PackedBools8, known: PackedBools8) -> Self {
+        Self { value: values.to_bits() & known.to_bits(), known: known.to_bits() }
+    }
+
+    /// Returns the known values, with the unknown values false.
+    pub const fn values(self) -> PackedBools8 {
+        PackedBools8::from_bits(self.value)
+    }
+
+    /// Returns which values are known.
+    pub const fn known(self) -> PackedBools8 {
+        PackedBools8::from_bits(self.known)
+    }
+
+    /// Returns the values, if they are all known.
+    pub const fn to_packed(self) -> Option<PackedBools8> {
+        if self.known == u8::MAX {
+            Some(self.values())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the values, using the matching values of `default` where they are unknown.
+    pub const fn unwrap_or(self, default: PackedBools8) -> PackedBools8 {
+        PackedBools8::from_bits(self.value | (default.to_bits() & !self.known))
+    }
+
+    /// Gets the value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given index is greater than 7.
+    #[track_caller]
+    pub fn get(&self, idx: u8) -> Option<bool> {
+        self.known().get(idx).then(|| self.values().get(idx))
+    }
+
+    /// Sets the value at the given index to val.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given index is greater than 7.
+    #[track_caller]
+    pub fn set(&mut self, val: Option<bool>, idx: u8) {
+        if idx >= 8 {
+            crate::macros::index_out_of_range(idx, 8);
+        }
+        let bit = 1 << idx;
+        match val {
+            Some(val) => {
+                self.known |= bit;
+                self.value = (self.value & !bit) | (u8::from(val) << idx);
+            }
+            None => {
+                self.known &= !bit;
+                self.value &= !bit;
+            }
+        }
+    }
+
+    /// Gets all the values.
+    pub fn get_all(&self) -> [Option<bool>; 8] {
+        core::array::from_fn(|idx| self.get(idx as u8))
+    }
+
+    /// Returns `self`, with its unknown values filled in from `other`.
+    pub const fn or(self, other: Self) -> Self {
+        let fill = other.known & !self.known;
+        Self {
+            value: self.value | (other.value & fill),
+            known: self.known | fill,
+        }
+    }
+
+    /// Returns the indices where `self` and `other` both have known values that are different.
+    pub const fn conflicts(self, other: Self) -> PackedBools8 {
+        PackedBools8::from_bits((self.value ^ other.value) & self.known & other.known)
+    }
+
+    /// Returns the indices where `self` has a known value that is different from `actual`.
+    pub const fn mismatches(self, actual: PackedBools8) -> PackedBools8 {
+        PackedBools8::from_bits((self.value ^ actual.to_bits()) & self.known)
+    }
+
+    /// Returns true if every known value matches `actual`.
+    pub const fn matches(self, actual: PackedBools8) -> bool {
+        self.mismatches(actual).to_bits() == 0
+    }
+}
+
+impl From<[Option<bool>; 8]> for PackedOptionBools8 {
+    fn from(vals: [Option<bool>; 8]) -> Self {
+        let mut out = Self::new();
+        for (idx, val) in vals.into_iter().enumerate() {
+            out.set(val, idx as u8);
+        }
+        out
+    }
+}
+
+impl From<PackedBools8> for PackedOptionBools8 {
+    fn from(values: PackedBools8) -> Self {
+        Self::from_packed(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedOptionBools8;
+    use crate::PackedBools8;
+
+    const T: Option<bool> = Some(true);
+    const F: Option<bool> = Some(false);
+    const N: Option<bool> = None;
+
+    #[test]
+    fn set_get() {
+        let mut opt = PackedOptionBools8::new();
+        assert_eq!(opt.get_all(), [N; 8]);
+        opt.set(T, 1);
+        opt.set(F, 2);
+        opt.set(T, 7);
+        opt.set(N, 7);
+        assert_eq!(opt.get_all(), [N, T, F, N, N, N, N, N]);
+        assert_eq!(opt, PackedOptionBools8::from([N, T, F, N, N, N, N, N]));
+        assert_eq!(opt.to_packed(), None);
+        assert_eq!(opt.unwrap_or(PackedBools8::from_bits(0xff)), 0b1111_1011);
+    }
+
+    #[test]
+    fn merging() {
+        let a = PackedOptionBools8::from([T, F, N, N, T, N, N, N]);
+        let b = PackedOptionBools8::from([F, F, T, N, N, N, N, F]);
+        assert_eq!(a.or(b).get_all(), [T, F, T, N, T, N, N, F]);
+        assert_eq!(a.conflicts(b), 0b0000_0001);
+        assert!(a.matches(PackedBools8::from_bits(0b0001_0001)));
+        assert_eq!(a.mismatches(PackedBools8::new()), 0b0001_0001);
+        let full = PackedOptionBools8::from_packed(PackedBools8::from_bits(0x5a));
+        assert_eq!(full.to_packed(), Some(PackedBools8::from_bits(0x5a)));
+        assert_eq!(
+            PackedOptionBools8::from_parts(PackedBools8::from_bits(0xff), PackedBools8::from_bits(0x0f)).values(),
+            0x0f
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range: the index is 8 but the capacity is 8")]
+    fn set_out_of_range() {
+        PackedOptionBools8::new().set(T, 8);
+    }
+}
@@ -0,0 +1,78 @@
+//! Choosing which end of the bits index 0 is at.
+
+/// The order of the bits in a packed value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum BitOrder {
+    /// Index 0 is the least significant bit.
+    ///
+    /// This is the order the packed types use internally.
+    #[default]
+    Lsb0,
+    /// Index 0 is the most significant bit.
+    ///
+    /// This is the order used by most MSB-first protocols,
+    /// and the order that binary numbers are written in.
+    Msb0,
+}
+
+/// A formatting adapter that displays the bits of a packed value in a chosen order.
+///
+/// With `BitOrder::Msb0`, this formats index 0 first,
+/// so the output reads in index order.
+///
+/// This is created by the `ordered` method on the packed types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderedBits<T> {
+    value: T,
+    order: BitOrder,
+}
+
+impl<T: Copy> OrderedBits<T> {
+    pub(crate) const fn new(value: T, order: BitOrder) -> Self {
+        Self { value, order }
+    }
+
+    /// Returns the value being formatted.
+    pub const fn value(&self) -> T {
+        self.value
+    }
+
+    /// Returns the order the bits are formatted in.
+    pub const fn order(&self) -> BitOrder {
+        self.order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::BitOrder;
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn ordered_bits() {
+        let pkd = PackedBools8::from_bits_ordered(0b1000_0001, BitOrder::Msb0);
+        assert!(pkd.get(0));
+        assert!(pkd.get(7));
+        let pkd = PackedBools8::from_bits_ordered(0b1100_0000, BitOrder::Msb0);
+        assert_eq!(pkd.get_all(), [true, true, false, false, false, false, false, false]);
+        assert_eq!(pkd.to_bits_ordered(BitOrder::Msb0), 0b1100_0000);
+        assert_eq!(pkd.to_bits_ordered(BitOrder::Lsb0), 0b0000_0011);
+        assert_eq!(PackedBools8::from_bits_ordered(0x5a, BitOrder::Lsb0), PackedBools8::from_bits(0x5a));
+
+        let pkd = PackedBools16::from_bits_ordered(0x8000, BitOrder::Msb0);
+        assert_eq!(pkd, PackedBools16::from_bits(1));
+    }
+
+    #[test]
+    fn formatting() {
+        let pkd = PackedBools8::from([true, true, false, true, false, false, false, false]);
+        assert_eq!(format!("{:b}", pkd.ordered(BitOrder::Lsb0)), "00001011");
+        assert_eq!(format!("{:b}", pkd.ordered(BitOrder::Msb0)), "11010000");
+        assert_eq!(format!("{:#x}", pkd.ordered(BitOrder::Msb0)), "0xd0");
+        assert_eq!(format!("{:X}", PackedBools16::from_bits(1).ordered(BitOrder::Msb0)), "8000");
+    }
+}
@@ -0,0 +1,49 @@
+//! Iterating over two packed values side by side.
+
+use core::ops::Range;
+
+/// An iterator over the booleans at each index of two packed values, as pairs.
+///
+/// This is created by the `pairs` method on the packed types.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pairs<T> {
+    pub(crate) a: T,
+    pub(crate) b: T,
+    pub(crate) range: Range<u8>,
+}
+
+impl<T> Pairs<T> {
+    pub(crate) const fn new(a: T, b: T, len: u8) -> Self {
+        Self { a, b, range: 0..len }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn pairs() {
+        let a = PackedBools8::from_bits(0b0000_0011);
+        let b = PackedBools8::from_bits(0b0000_0101);
+        let pairs: Vec<_> = a.pairs(b).take(4).collect();
+        assert_eq!(pairs, [(true, true), (true, false), (false, true), (false, false)]);
+        assert_eq!(a.pairs(b).len(), 8);
+        assert_eq!(a.pairs(b).nth(2), Some((false, true)));
+    }
+
+    #[test]
+    fn pairs_back() {
+        let a = PackedBools16::from_bits(0x8000);
+        let b = PackedBools16::from_bits(0x4000);
+        let mut pairs = a.pairs(b);
+        assert_eq!(pairs.next_back(), Some((true, false)));
+        assert_eq!(pairs.next_back(), Some((false, true)));
+        assert_eq!(pairs.len(), 14);
+        let mismatched: Vec<_> = a.pairs(b).enumerate().rev().filter(|(_, (x, y))| x != y).map(|(i, _)| i).collect();
+        assert_eq!(mismatched, [15, 14]);
+    }
+}
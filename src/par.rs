@@ -0,0 +1,200 @@
+//! Parallel operations over large packed buffers, using `rayon`.
+//!
+//! These functions work on byte slices, where bit `i` of the buffer
+//! is bit `i % 8` of byte `i / 8`.
+//! Slices of `PackedBools8` can be viewed as bytes with
+//! [`PackedBools8::slice_as_bytes`](crate::PackedBools8::slice_as_bytes).
+//!
+//! With the `alloc` feature, `&PackedBoolVec` is also a parallel iterator over its booleans.
+
+use rayon::prelude::*;
+
+/// How many bytes each parallel task works on.
+const CHUNK_SIZE: usize = 1 << 14;
+
+/// Counts how many true values there are in the buffer.
+pub fn count_true(bytes: &[u8]) -> u64 {
+    bytes.par_chunks(CHUNK_SIZE)
+        .map(crate::slices::count_true)
+        .sum()
+}
+
+/// Counts how many false values there are in the buffer.
+pub fn count_false(bytes: &[u8]) -> u64 {
+    bytes.len() as u64 * 8 - count_true(bytes)
+}
+
+/// Returns the index of the first true value in the buffer.
+pub fn find_first_true(bytes: &[u8]) -> Option<u64> {
+    find_first_byte(bytes, |b| b)
+}
+
+/// Returns the index of the first false value in the buffer.
+pub fn find_first_false(bytes: &[u8]) -> Option<u64> {
+    find_first_byte(bytes, |b| !b)
+}
+
+/// Finds the first bit set in `map(byte)`.
+fn find_first_byte(bytes: &[u8], map: impl Fn(u8) -> u8 + Sync) -> Option<u64> {
+    let chunk_idx = bytes.par_chunks(CHUNK_SIZE)
+        .position_first(|chunk| chunk.iter().any(|&b| map(b) != 0))?;
+    let chunk = &bytes[chunk_idx * CHUNK_SIZE..];
+    chunk.iter()
+        .position(|&b| map(b) != 0)
+        .map(|byte_idx| {
+            let bit = map(chunk[byte_idx]).trailing_zeros();
+            ((chunk_idx * CHUNK_SIZE + byte_idx) as u64) * 8 + u64::from(bit)
+        })
+}
+
+/// Sets `dst` to the bitwise AND of `dst` and `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn and_assign(dst: &mut [u8], src: &[u8]) {
+    combine(dst, src, |a, b| a & b)
+}
+
+/// Sets `dst` to the bitwise OR of `dst` and `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn or_assign(dst: &mut [u8], src: &[u8]) {
+    combine(dst, src, |a, b| a | b)
+}
+
+/// Sets `dst` to the bitwise XOR of `dst` and `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn xor_assign(dst: &mut [u8], src: &[u8]) {
+    combine(dst, src, |a, b| a ^ b)
+}
+
+fn combine(dst: &mut [u8], src: &[u8], op: impl Fn(u8, u8) -> u8 + Sync) {
+    assert_eq!(dst.len(), src.len(), "The slices must have the same length");
+    dst.par_chunks_mut(CHUNK_SIZE)
+        .zip(src.par_chunks(CHUNK_SIZE))
+        .for_each(|(dst, src)| {
+            dst.iter_mut().zip(src).for_each(|(d, &s)| *d = op(*d, s));
+        });
+}
+
+/// A parallel iterator over the booleans in a [`PackedBoolVec`](crate::PackedBoolVec).
+///
+/// Each task reads a chunk of the bytes, like the other functions in this module.
+///
+/// ```
+/// use packed_booleans::PackedBoolVec;
+/// use rayon::prelude::*;
+///
+/// let vec: PackedBoolVec = (0..1000).map(|i| i % 3 == 0).collect();
+/// assert_eq!(vec.par_iter().filter(|&b| b).count(), 334);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct ParBoolVecIter<'a, W: crate::Word = u8> {
+    vec: &'a crate::PackedBoolVec<W>,
+}
+
+#[cfg(feature = "alloc")]
+impl<W: crate::Word> ParallelIterator for ParBoolVecIter<'_, W> {
+    type Item = bool;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<bool>,
+    {
+        let len = self.vec.len();
+        self.vec.as_bytes()
+            .par_chunks(CHUNK_SIZE)
+            .enumerate()
+            .flat_map_iter(move |(i, chunk)| {
+                let n = (len - i * CHUNK_SIZE * 8).min(chunk.len() * 8);
+                (0..n).map(move |idx| (chunk[idx / 8] >> (idx % 8)) & 1 != 0)
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, W: crate::Word> IntoParallelIterator for &'a crate::PackedBoolVec<W> {
+    type Item = bool;
+    type Iter = ParBoolVecIter<'a, W>;
+
+    fn into_par_iter(self) -> ParBoolVecIter<'a, W> {
+        ParBoolVecIter { vec: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn count() {
+        let mut bytes = vec![0u8; CHUNK_SIZE * 3 + 5];
+        assert_eq!(count_true(&bytes), 0);
+        bytes[0] = 0b101;
+        bytes[CHUNK_SIZE + 1] = 0xff;
+        *bytes.last_mut().unwrap() = 0x80;
+        assert_eq!(count_true(&bytes), 11);
+        assert_eq!(count_false(&bytes), bytes.len() as u64 * 8 - 11);
+    }
+
+    #[test]
+    fn find_first() {
+        let mut bytes = vec![0u8; CHUNK_SIZE * 2 + 3];
+        assert_eq!(find_first_true(&bytes), None);
+        assert_eq!(find_first_false(&bytes), Some(0));
+        bytes[CHUNK_SIZE + 2] = 0b0100;
+        bytes[CHUNK_SIZE * 2] = 0b1;
+        assert_eq!(find_first_true(&bytes), Some((CHUNK_SIZE as u64 + 2) * 8 + 2));
+
+        bytes.fill(0xff);
+        assert_eq!(find_first_false(&bytes), None);
+        bytes[5] = 0b1110_1111;
+        assert_eq!(find_first_false(&bytes), Some(5 * 8 + 4));
+    }
+
+    #[test]
+    fn combine() {
+        let a: Vec<u8> = (0..CHUNK_SIZE * 2).map(|i| i as u8).collect();
+        let b: Vec<u8> = (0..CHUNK_SIZE * 2).map(|i| (i / 3) as u8).collect();
+
+        let mut and = a.clone();
+        and_assign(&mut and, &b);
+        let mut or = a.clone();
+        or_assign(&mut or, &b);
+        let mut xor = a.clone();
+        xor_assign(&mut xor, &b);
+
+        for i in 0..a.len() {
+            assert_eq!(and[i], a[i] & b[i]);
+            assert_eq!(or[i], a[i] | b[i]);
+            assert_eq!(xor[i], a[i] ^ b[i]);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bool_vec() {
+        let vec: crate::PackedBoolVec = (0..CHUNK_SIZE * 8 * 2 + 13).map(|i| i % 7 < 3).collect();
+        let bools: Vec<bool> = vec.par_iter().collect();
+        assert!(bools.into_iter().eq(vec.iter()));
+        assert_eq!(vec.par_iter().filter(|&b| b).count(), vec.count_true());
+        assert_eq!(crate::PackedBoolVec::new().par_iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn combine_length_mismatch() {
+        and_assign(&mut [0; 4], &[0; 3]);
+    }
+}
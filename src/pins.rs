@@ -0,0 +1,256 @@
+//! Reading and writing banks of `embedded-hal` GPIO pins as packed values.
+//!
+//! Pin `i` of a bank is index `i` of the packed value.
+//! A bank can hold pins of different types by using `&mut dyn InputPin<Error = E>`
+//! or `&mut dyn OutputPin<Error = E>` as the pin type.
+//!
+//! Every pin of a bank is read or written even if some of them fail,
+//! and the failures are collected into a single [`PinBankError`].
+//!
+//! ```
+//! # use core::convert::Infallible;
+//! # use embedded_hal::digital::{ErrorType, InputPin};
+//! # struct Pin(bool);
+//! # impl ErrorType for Pin { type Error = Infallible; }
+//! # impl InputPin for Pin {
+//! #     fn is_high(&mut self) -> Result<bool, Infallible> { Ok(self.0) }
+//! #     fn is_low(&mut self) -> Result<bool, Infallible> { Ok(!self.0) }
+//! # }
+//! use packed_booleans::pins;
+//!
+//! let mut bank = [Pin(true), Pin(false), Pin(true)];
+//! assert_eq!(pins::read(&mut bank).unwrap().to_bits(), 0b101);
+//! ```
+
+use core::fmt;
+
+use embedded_hal::digital::{Error, ErrorKind, InputPin, OutputPin, PinState};
+
+use crate::PackedBools8;
+
+/// The error returned when some of the pins in a bank fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PinBankError<E> {
+    failed: PackedBools8,
+    first: E,
+}
+
+impl<E> PinBankError<E> {
+    /// Returns which pins failed.
+    pub const fn failed(&self) -> PackedBools8 {
+        self.failed
+    }
+
+    /// Returns the error from the lowest-numbered pin that failed.
+    pub const fn first_error(&self) -> &E {
+        &self.first
+    }
+
+    /// Returns the error from the lowest-numbered pin that failed.
+    pub fn into_first_error(self) -> E {
+        self.first
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for PinBankError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} pins failed, first with {:?}", self.failed.count_true(), self.first)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for PinBankError<E> {}
+
+/// Uses the kind of the first error, so a bank can be used where a single pin's errors are expected.
+impl<E: Error> Error for PinBankError<E> {
+    fn kind(&self) -> ErrorKind {
+        self.first.kind()
+    }
+}
+
+/// Collects the errors from every pin of a bank.
+struct Failures<E> {
+    failed: PackedBools8,
+    first: Option<E>,
+}
+
+impl<E> Failures<E> {
+    fn new() -> Self {
+        Self { failed: PackedBools8::new(), first: None }
+    }
+
+    fn record<T>(&mut self, idx: u8, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(val) => Some(val),
+            Err(err) => {
+                self.failed.set(true, idx);
+                self.first.get_or_insert(err);
+                None
+            }
+        }
+    }
+
+    fn finish<T>(self, val: T) -> Result<T, PinBankError<E>> {
+        match self.first {
+            None => Ok(val),
+            Some(first) => Err(PinBankError { failed: self.failed, first }),
+        }
+    }
+}
+
+/// Reads whether each pin in the bank is high.
+///
+/// The indices past the end of the bank are false.
+///
+/// # Panics
+///
+/// Panics if there are more than 8 pins.
+pub fn read<P: InputPin>(pins: &mut [P]) -> Result<PackedBools8, PinBankError<P::Error>> {
+    assert!(pins.len() <= 8, "A bank cannot have more than 8 pins");
+    let mut out = PackedBools8::new();
+    let mut failures = Failures::new();
+    for (idx, pin) in (0..).zip(pins) {
+        if let Some(high) = failures.record(idx, pin.is_high()) {
+            out.set(high, idx);
+        }
+    }
+    failures.finish(out)
+}
+
+/// Drives each pin in the bank high or low to match the given values.
+///
+/// The indices past the end of the bank are ignored.
+///
+/// # Panics
+///
+/// Panics if there are more than 8 pins.
+pub fn write<P: OutputPin>(pins: &mut [P], values: PackedBools8) -> Result<(), PinBankError<P::Error>> {
+    assert!(pins.len() <= 8, "A bank cannot have more than 8 pins");
+    let mut failures = Failures::new();
+    for (idx, pin) in (0..).zip(pins) {
+        failures.record(idx, pin.set_state(PinState::from(values.get(idx))));
+    }
+    failures.finish(())
+}
+
+/// Drives the pins where `mask` is true to match `values`, leaving the others alone.
+///
+/// # Panics
+///
+/// Panics if there are more than 8 pins.
+pub fn write_masked<P: OutputPin>(
+    pins: &mut [P],
+    values: PackedBools8,
+    mask: PackedBools8,
+) -> Result<(), PinBankError<P::Error>> {
+    assert!(pins.len() <= 8, "A bank cannot have more than 8 pins");
+    let mut failures = Failures::new();
+    for (idx, pin) in (0..).zip(pins) {
+        if mask.get(idx) {
+            failures.record(idx, pin.set_state(PinState::from(values.get(idx))));
+        }
+    }
+    failures.finish(())
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::digital::{Error, ErrorKind, ErrorType, InputPin, OutputPin};
+
+    use super::{read, write, write_masked};
+    use crate::PackedBools8;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Broken(u8);
+
+    impl Error for Broken {
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    /// A pin that fails if it is broken, and otherwise remembers its level.
+    struct Pin {
+        high: bool,
+        broken: Option<u8>,
+    }
+
+    impl Pin {
+        fn bank(levels: u8, broken: u8) -> [Pin; 8] {
+            core::array::from_fn(|i| Pin {
+                high: levels & (1 << i) != 0,
+                broken: (broken & (1 << i) != 0).then_some(i as u8),
+            })
+        }
+
+        fn check(&self) -> Result<(), Broken> {
+            self.broken.map_or(Ok(()), |i| Err(Broken(i)))
+        }
+    }
+
+    impl ErrorType for Pin {
+        type Error = Broken;
+    }
+
+    impl InputPin for Pin {
+        fn is_high(&mut self) -> Result<bool, Broken> {
+            self.check().map(|()| self.high)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Broken> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    impl OutputPin for Pin {
+        fn set_low(&mut self) -> Result<(), Broken> {
+            self.check()?;
+            self.high = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Broken> {
+            self.check()?;
+            self.high = true;
+            Ok(())
+        }
+    }
+
+    fn levels(pins: &[Pin]) -> u8 {
+        pins.iter().enumerate().fold(0, |acc, (i, pin)| acc | (u8::from(pin.high) << i))
+    }
+
+    #[test]
+    fn read_bank() {
+        assert_eq!(read(&mut Pin::bank(0b1001_0110, 0)), Ok(PackedBools8::from_bits(0b1001_0110)));
+        assert_eq!(read(&mut Pin::bank(0xff, 0)[..3]), Ok(PackedBools8::from_bits(0b111)));
+
+        let err = read(&mut Pin::bank(0xff, 0b0100_0100)).unwrap_err();
+        assert_eq!(err.failed().to_bits(), 0b0100_0100);
+        assert_eq!(*err.first_error(), Broken(2));
+        assert_eq!(err.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn write_bank() {
+        let mut pins = Pin::bank(0, 0);
+        write(&mut pins, PackedBools8::from_bits(0xa5)).unwrap();
+        assert_eq!(levels(&pins), 0xa5);
+        write_masked(&mut pins, PackedBools8::from_bits(0x0f), PackedBools8::from_bits(0x3c)).unwrap();
+        assert_eq!(levels(&pins), 0b1000_1101);
+
+        // the pins that work are still written
+        let mut pins = Pin::bank(0, 0b1000_0001);
+        let err = write(&mut pins, PackedBools8::from_bits(0xff)).unwrap_err();
+        assert_eq!(err.failed().to_bits(), 0b1000_0001);
+        assert_eq!(err.into_first_error(), Broken(0));
+        assert_eq!(levels(&pins), 0b0111_1110);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_pins() {
+        let mut pins: [Pin; 9] = core::array::from_fn(|_| Pin { high: false, broken: None });
+        let _ = read(&mut pins);
+    }
+}
@@ -0,0 +1,186 @@
+//! Rank and select queries over packed buffers.
+
+use alloc::vec::Vec;
+
+use crate::slices;
+
+/// How many bytes each block of the index covers.
+const BLOCK_BYTES: usize = 64;
+const BLOCK_BITS: u64 = BLOCK_BYTES as u64 * 8;
+
+/// An index over a packed buffer that answers rank and select queries quickly.
+///
+/// Bit `i` of the buffer is bit `i % 8` of byte `i / 8`.
+///
+/// The index stores the number of true values before every 512-bit block,
+/// so `rank` only has to count within a single block,
+/// and `select` binary searches for the right block before counting within it.
+/// This takes an extra 8 bytes of memory for every 64 bytes of the buffer.
+#[derive(Debug, Clone)]
+pub struct RankSelectIndex<'a> {
+    bytes: &'a [u8],
+    blocks: Blocks,
+}
+
+impl<'a> RankSelectIndex<'a> {
+    /// Builds an index over the given buffer.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            blocks: Blocks::new(bytes),
+        }
+    }
+
+    /// Returns the indexed buffer.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the number of booleans in the buffer.
+    pub fn len(&self) -> u64 {
+        self.bytes.len() as u64 * 8
+    }
+
+    /// Returns true if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Counts how many true values there are in the buffer.
+    pub fn count_true(&self) -> u64 {
+        self.blocks.count_true()
+    }
+
+    /// Counts how many true values there are before the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the length of the buffer.
+    pub fn rank(&self, idx: u64) -> u64 {
+        self.blocks.rank(self.bytes, idx)
+    }
+
+    /// Returns the index of the `n`th true value, counting from 0,
+    /// if there are more than `n` true values.
+    pub fn select(&self, n: u64) -> Option<u64> {
+        self.blocks.select(self.bytes, n)
+    }
+}
+
+/// The per-block counts of an index, kept separate from the buffer they describe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Blocks {
+    /// The number of true values before each block, followed by the total.
+    counts: Vec<u64>,
+}
+
+impl Blocks {
+    pub(crate) fn new(bytes: &[u8]) -> Self {
+        let mut counts = Vec::with_capacity(bytes.len() / BLOCK_BYTES + 2);
+        let mut total = 0;
+        counts.push(0);
+        for block in bytes.chunks(BLOCK_BYTES) {
+            total += slices::count_true(block);
+            counts.push(total);
+        }
+        Self { counts }
+    }
+
+    pub(crate) fn count_true(&self) -> u64 {
+        *self.counts.last().unwrap()
+    }
+
+    pub(crate) fn rank(&self, bytes: &[u8], idx: u64) -> u64 {
+        assert!(idx <= bytes.len() as u64 * 8, "The index cannot be greater than the length of the buffer");
+        let block = (idx / BLOCK_BITS) as usize;
+        let byte = (idx / 8) as usize;
+        let bit = idx % 8;
+
+        let mut rank = self.counts[block] + slices::count_true(&bytes[block * BLOCK_BYTES..byte]);
+        if bit != 0 {
+            rank += u64::from((bytes[byte] & ((1 << bit) - 1)).count_ones());
+        }
+        rank
+    }
+
+    pub(crate) fn select(&self, bytes: &[u8], n: u64) -> Option<u64> {
+        if n >= self.count_true() {
+            return None;
+        }
+        // the last block with fewer than n + 1 true values before it
+        let block = self.counts.partition_point(|&count| count <= n) - 1;
+        let mut remaining = n - self.counts[block];
+        for (offset, &byte) in bytes[block * BLOCK_BYTES..].iter().enumerate() {
+            let ones = u64::from(byte.count_ones());
+            if remaining < ones {
+                let mut byte = byte;
+                for _ in 0..remaining {
+                    // clear the lowest set bit
+                    byte &= byte - 1;
+                }
+                let byte_idx = (block * BLOCK_BYTES + offset) as u64;
+                return Some(byte_idx * 8 + u64::from(byte.trailing_zeros()));
+            }
+            remaining -= ones;
+        }
+        unreachable!("the block counts are out of date")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::RankSelectIndex;
+
+    fn bytes() -> Vec<u8> {
+        (0..300u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect()
+    }
+
+    fn get(bytes: &[u8], idx: u64) -> bool {
+        (bytes[(idx / 8) as usize] >> (idx % 8)) & 1 != 0
+    }
+
+    #[test]
+    fn rank() {
+        let bytes = bytes();
+        let index = RankSelectIndex::new(&bytes);
+        let mut expected = 0;
+        for idx in 0..index.len() {
+            assert_eq!(index.rank(idx), expected);
+            expected += u64::from(get(&bytes, idx));
+        }
+        assert_eq!(index.rank(index.len()), expected);
+        assert_eq!(index.count_true(), expected);
+    }
+
+    #[test]
+    fn select() {
+        let bytes = bytes();
+        let index = RankSelectIndex::new(&bytes);
+        let ones: Vec<u64> = (0..index.len()).filter(|&idx| get(&bytes, idx)).collect();
+        for (n, &idx) in ones.iter().enumerate() {
+            assert_eq!(index.select(n as u64), Some(idx));
+        }
+        assert_eq!(index.select(ones.len() as u64), None);
+    }
+
+    #[test]
+    fn empty() {
+        let index = RankSelectIndex::new(&[]);
+        assert!(index.is_empty());
+        assert_eq!(index.rank(0), 0);
+        assert_eq!(index.select(0), None);
+
+        let zeros = [0; 100];
+        let index = RankSelectIndex::new(&zeros);
+        assert_eq!(index.rank(800), 0);
+        assert_eq!(index.select(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rank_out_of_range() {
+        RankSelectIndex::new(&[0xff]).rank(9);
+    }
+}
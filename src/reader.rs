@@ -0,0 +1,212 @@
+//! Reading booleans out of a byte stream.
+
+use std::io::{self, Read};
+
+use crate::{PackedBools8, PackedBools16};
+
+/// The size of the internal buffer of a `BitReader`.
+const BUF_SIZE: usize = 64;
+
+/// A reader that yields booleans, packed values, or n-bit fields from a byte stream.
+///
+/// Bits are read starting from the least significant bit of each byte,
+/// so reading 8 bits from a byte gives the same booleans as `PackedBools8::from_bits`.
+///
+/// Reads from the underlying reader are buffered internally.
+///
+/// All the reading methods return `Ok(None)` if the stream ended
+/// before any bit of the value was read, and an error of kind
+/// `UnexpectedEof` if it ended partway through the value.
+pub struct BitReader<R> {
+    inner: R,
+    buf: [u8; BUF_SIZE],
+    /// The index of the current byte in `buf`.
+    pos: usize,
+    /// How many bytes of `buf` hold data.
+    filled: usize,
+    /// How many bits of the current byte have been read.
+    bit: u8,
+}
+
+impl<R: Read> BitReader<R> {
+    /// Creates a new `BitReader` reading from the given reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            filled: 0,
+            bit: 0,
+        }
+    }
+
+    /// Reads a single boolean.
+    pub fn read_bool(&mut self) -> io::Result<Option<bool>> {
+        Ok(self.read_bits(1)?.map(|bit| bit != 0))
+    }
+
+    /// Reads 8 booleans into a `PackedBools8`.
+    pub fn read_packed8(&mut self) -> io::Result<Option<PackedBools8>> {
+        Ok(self.read_bits(8)?.map(|bits| PackedBools8::from_bits(bits as u8)))
+    }
+
+    /// Reads 16 booleans into a `PackedBools16`.
+    pub fn read_packed16(&mut self) -> io::Result<Option<PackedBools16>> {
+        Ok(self.read_bits(16)?.map(|bits| PackedBools16::from_bits(bits as u16)))
+    }
+
+    /// Reads an `n` bit field.
+    ///
+    /// The first bit read is the least significant bit of the returned value.
+    /// If the stream ends partway through the field, the bits that were read are lost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 32.
+    pub fn read_bits(&mut self, n: u8) -> io::Result<Option<u32>> {
+        assert!(n <= 32, "Cannot read more than 32 bits at once");
+        let mut out = 0u32;
+        let mut read = 0;
+        while read < n {
+            if !self.fill_buf()? {
+                return if read == 0 {
+                    Ok(None)
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "the stream ended in the middle of a bit field",
+                    ))
+                };
+            }
+            let byte = self.buf[self.pos] >> self.bit;
+            let take = (8 - self.bit).min(n - read);
+            let mask = (1u32 << take) - 1;
+            out |= (u32::from(byte) & mask) << read;
+            read += take;
+            self.bit += take;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.pos += 1;
+            }
+        }
+        Ok(Some(out))
+    }
+
+    /// Returns true if the reader is at the start of a byte.
+    pub fn is_aligned(&self) -> bool {
+        self.bit == 0
+    }
+
+    /// Skips the rest of the current byte, if it has been partially read.
+    pub fn align_to_byte(&mut self) {
+        if self.bit != 0 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Note that reading from it directly will skip over any buffered data.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `BitReader`, returning the underlying reader.
+    ///
+    /// Any buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Makes sure the current byte is in the buffer.
+    /// Returns false if the underlying reader has reached EOF.
+    fn fill_buf(&mut self) -> io::Result<bool> {
+        if self.pos < self.filled {
+            return Ok(true);
+        }
+        loop {
+            match self.inner.read(&mut self.buf) {
+                Ok(n) => {
+                    self.pos = 0;
+                    self.filled = n;
+                    return Ok(n != 0);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<R> core::fmt::Debug for BitReader<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BitReader")
+            .field("buffered_bytes", &(self.filled - self.pos))
+            .field("bit", &self.bit)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::BitReader;
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn read_bools() {
+        let mut reader = BitReader::new(&[0b0000_0101][..]);
+        assert_eq!(reader.read_bool().unwrap(), Some(true));
+        assert_eq!(reader.read_bool().unwrap(), Some(false));
+        assert_eq!(reader.read_bool().unwrap(), Some(true));
+        for _ in 0..5 {
+            assert_eq!(reader.read_bool().unwrap(), Some(false));
+        }
+        assert_eq!(reader.read_bool().unwrap(), None);
+    }
+
+    #[test]
+    fn read_packed() {
+        let mut reader = BitReader::new(&[0xd4, 0x34, 0x12][..]);
+        assert_eq!(reader.read_packed8().unwrap(), Some(PackedBools8::from_bits(0xd4)));
+        assert_eq!(reader.read_packed16().unwrap(), Some(PackedBools16::from_bits(0x1234)));
+        assert_eq!(reader.read_packed8().unwrap(), None);
+    }
+
+    #[test]
+    fn read_unaligned() {
+        let mut reader = BitReader::new(&[0b1011_0110, 0b0000_0011][..]);
+        assert_eq!(reader.read_bits(3).unwrap(), Some(0b110));
+        assert!(!reader.is_aligned());
+        assert_eq!(reader.read_bits(7).unwrap(), Some(0b111_0110));
+        assert_eq!(reader.read_bits(0).unwrap(), Some(0));
+        reader.align_to_byte();
+        assert!(reader.is_aligned());
+        assert_eq!(reader.read_bool().unwrap(), None);
+    }
+
+    #[test]
+    fn eof_in_field() {
+        let mut reader = BitReader::new(&[0xff][..]);
+        assert_eq!(reader.read_bits(4).unwrap(), Some(0xf));
+        let err = reader.read_packed8().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn refills_buffer() {
+        let bytes: std::vec::Vec<u8> = (0..=255).collect();
+        let mut reader = BitReader::new(&bytes[..]);
+        for byte in 0..=255 {
+            assert_eq!(reader.read_packed8().unwrap(), Some(PackedBools8::from_bits(byte)));
+        }
+        assert_eq!(reader.read_packed8().unwrap(), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Run-length encoding of packed buffers.
+//!
+//! Bit `i` of a buffer is bit `i % 8` of byte `i / 8`.
+//! A run is a value and how many times it repeats.
+
+use core::iter::FusedIterator;
+
+use crate::{BitCursor, BufferExhausted};
+
+/// Encodes a packed buffer as runs of equal values.
+///
+/// Runs longer than `u32::MAX` are split up.
+///
+/// ```
+/// let runs: Vec<_> = packed_booleans::rle::encode(&[0b0000_0111, 0xff]).collect();
+/// assert_eq!(runs, [(true, 3), (false, 5), (true, 8)]);
+/// ```
+pub fn encode(bytes: &[u8]) -> Runs<'_> {
+    Runs { bytes, pos: 0 }
+}
+
+/// Decodes runs into a packed buffer, returning how many bits were written.
+///
+/// Bits after the last run are left unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the runs don't fit in the buffer.
+/// The buffer is still filled up to its end, including the start of the run that didn't fit.
+pub fn decode(runs: impl IntoIterator<Item = (bool, u32)>, out: &mut [u8]) -> Result<usize, BufferExhausted> {
+    let mut cursor = BitCursor::new(out);
+    for (val, len) in runs {
+        let fill = if val { u32::MAX } else { 0 };
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(32) as u8;
+            if usize::from(n) > cursor.remaining() {
+                // fill the rest of the buffer before reporting that it ran out
+                cursor.write_bits(fill, cursor.remaining() as u8)?;
+                return Err(BufferExhausted);
+            }
+            cursor.write_bits(fill, n)?;
+            remaining -= u32::from(n);
+        }
+    }
+    Ok(cursor.position())
+}
+
+/// An iterator over the runs in a packed buffer.
+///
+/// This is created by [`encode`].
+#[derive(Debug, Clone)]
+pub struct Runs<'a> {
+    bytes: &'a [u8],
+    /// The index of the start of the next run.
+    pos: u64,
+}
+
+impl Iterator for Runs<'_> {
+    type Item = (bool, u32);
+
+    fn next(&mut self) -> Option<(bool, u32)> {
+        let len = self.bytes.len() as u64 * 8;
+        if self.pos >= len {
+            return None;
+        }
+        let start = self.pos;
+        let val = (self.bytes[(start / 8) as usize] >> (start % 8)) & 1 != 0;
+        // XORing with this turns the values in the run into zeros
+        let flip = if val { 0xff } else { 0 };
+        while self.pos < len {
+            let bit = self.pos % 8;
+            let diff = (self.bytes[(self.pos / 8) as usize] ^ flip) >> bit;
+            if diff != 0 {
+                self.pos += u64::from(diff.trailing_zeros());
+                break;
+            }
+            self.pos += 8 - bit;
+        }
+        let run = (self.pos - start).min(u32::MAX.into());
+        self.pos = start + run;
+        Some((val, run as u32))
+    }
+}
+
+impl FusedIterator for Runs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use super::{decode, encode};
+    use crate::BufferExhausted;
+
+    #[test]
+    fn encode_runs() {
+        let runs: Vec<_> = encode(&[0b1111_0000, 0xff, 0x00, 0b1]).collect();
+        assert_eq!(runs, [(false, 4), (true, 12), (false, 8), (true, 1), (false, 7)]);
+        assert_eq!(encode(&[]).next(), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes: Vec<u8> = (0..64u32).map(|i| [0x00, 0xff, 0x0f, 0x81][(i % 7 % 4) as usize]).collect();
+        let mut out = [0x5a; 64];
+        assert_eq!(decode(encode(&bytes), &mut out), Ok(512));
+        assert_eq!(&out[..], &bytes[..]);
+    }
+
+    #[test]
+    fn long_runs() {
+        let mut out = [0; 10];
+        assert_eq!(decode([(false, 3), (true, 70)], &mut out), Ok(73));
+        assert_eq!(out, [0xf8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        assert_eq!(decode([(true, 81)], &mut out), Err(BufferExhausted));
+    }
+
+    #[test]
+    fn partial_output() {
+        let mut out = [0; 2];
+        assert_eq!(decode([(true, 3), (false, 2), (true, 40)], &mut out), Err(BufferExhausted));
+        assert_eq!(out, [0b1110_0111, 0xff]);
+        let mut out = [0; 3];
+        assert_eq!(decode([(false, 1), (true, 100)], &mut out), Err(BufferExhausted));
+        assert_eq!(out, [0xfe, 0xff, 0xff]);
+    }
+}
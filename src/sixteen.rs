@@ -4,6 +4,8 @@ use core::{iter::FusedIterator, ops};
 
 crate::macros::packed_bools_type!{
     NAME = PackedBools16,
+    BUILDER = PackedBools16Builder,
+    INDEX = BitIndex16,
     REPR = u16,
     BOOL_COUNT = 16,
     BCOUNT_MINUS1 = 15,
@@ -12,7 +14,9 @@ crate::macros::packed_bools_type!{
     DEBUG = "PackedBools16({:#018b})",
     BINARY = "{:016b}",
     LOW_HEX = "{:04x}",
-    UPPER_HEX = "{:04X}"
+    UPPER_HEX = "{:04X}",
+    WIDER_INTS = [u32, u64, u128],
+    WIDER_TYPES = [PackedBools32, PackedBools64, PackedBools128]
 }
 
 impl IntoIterator for PackedBools16 {
@@ -24,6 +28,43 @@ impl IntoIterator for PackedBools16 {
     }
 }
 
+impl PackedBools16 {
+    /// Returns a byte for each value, which is 0xFF if the value is true and 0x00 if it is false.
+    ///
+    /// This is the layout of SIMD comparison results and select masks.
+    pub const fn to_mask_bytes(&self) -> [u8; 16] {
+        let [lo, hi] = self.0.to_le_bytes();
+        let lo = crate::PackedBools8::from_bits(lo).to_mask_bytes();
+        let hi = crate::PackedBools8::from_bits(hi).to_mask_bytes();
+        let mut out = [0; 16];
+        let mut i = 0;
+        while i < 8 {
+            out[i] = lo[i];
+            out[i + 8] = hi[i];
+            i += 1;
+        }
+        out
+    }
+
+    /// Creates a new `PackedBools16` from the top bit of each byte,
+    /// like the `movemask` SIMD instructions.
+    ///
+    /// This is the inverse of [`to_mask_bytes`](Self::to_mask_bytes).
+    pub const fn from_mask_bytes(bytes: [u8; 16]) -> Self {
+        let mut lo = [0; 8];
+        let mut hi = [0; 8];
+        let mut i = 0;
+        while i < 8 {
+            lo[i] = bytes[i];
+            hi[i] = bytes[i + 8];
+            i += 1;
+        }
+        let lo = crate::PackedBools8::from_mask_bytes(lo).to_bits();
+        let hi = crate::PackedBools8::from_mask_bytes(hi).to_bits();
+        Self(u16::from_le_bytes([lo, hi]))
+    }
+}
+
 /// An iterator over the booleans in a [`PackedBools16`].
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -37,6 +78,16 @@ impl IntoIter16 {
     fn new(bools: PackedBools16) -> Self {
         Self { bools, range: 0..16 }
     }
+
+    /// Returns the next boolean without advancing the iterator.
+    pub fn peek(&self) -> Option<bool> {
+        self.range.clone().next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    /// Returns the next boolean from the back without advancing the iterator.
+    pub fn peek_back(&self) -> Option<bool> {
+        self.range.clone().next_back().and_then(|idx| self.bools.try_get(idx))
+    }
 }
 
 impl Iterator for IntoIter16 {
@@ -101,6 +152,107 @@ mod tests {
     }
 
     #[test]
+    fn builder() {
+        const PKD: PackedBools16 = PackedBools16::builder().set(15).set_range(2..10).clear(4).build();
+        assert_eq!(PKD.get_all(), [F,F,T,T,F,T,T,T,T,T,F,F,F,F,F,T]);
+    }
+
+    #[test]
+    fn increment() {
+        let pkd = PackedBools16::from_bits(0x00ff).checked_increment();
+        assert_eq!(pkd, Some(PackedBools16::from_bits(0x0100)));
+        assert_eq!(PackedBools16::from_bits(u16::MAX).checked_increment(), None);
+        assert_eq!(PackedBools16::new().checked_decrement(), None);
+    }
+
+    #[test]
+    fn bytes() {
+        let pkd = PackedBools16::from_bits(0x1234);
+        assert_eq!(pkd.to_le_bytes(), [0x34, 0x12]);
+        assert_eq!(pkd.to_be_bytes(), [0x12, 0x34]);
+        assert_eq!(pkd.to_ne_bytes(), 0x1234u16.to_ne_bytes());
+        assert_eq!(PackedBools16::from_le_bytes([0x34, 0x12]), pkd);
+        assert_eq!(PackedBools16::from_be_bytes([0x12, 0x34]), pkd);
+        assert_eq!(PackedBools16::from_ne_bytes(pkd.to_ne_bytes()), pkd);
+        assert!(PackedBools16::from_le_bytes([0b1, 0]).get(0));
+    }
+
+    #[test]
+    fn prefix_scans() {
+        let pkd = PackedBools16::from_bits(0b0000_0100_0001_0000);
+        assert_eq!(pkd.prefix_or(), PackedBools16::from_bits(0xfff0));
+        assert_eq!(pkd.prefix_xor(), PackedBools16::from_bits(0b0000_0011_1111_0000));
+    }
+
+    #[test]
+    fn int_ops() {
+        let mut pkd = PackedBools16::from_bits(0xff00);
+        assert_eq!((pkd & 0x0ff0).to_bits(), 0x0f00);
+        pkd ^= 0xffff;
+        assert_eq!(pkd.to_bits(), 0x00ff);
+    }
+
+    #[test]
+    fn array_ops() {
+        let mut pkd = PackedBools16::from_bits(0x00ff);
+        pkd |= [F, F, F, F, F, F, F, F, T, F, F, F, F, F, F, F];
+        assert_eq!(pkd.to_bits(), 0x01ff);
+        assert_eq!((pkd & [T; 16]).to_bits(), 0x01ff);
+    }
+
+    #[test]
+    fn eq_arrays_and_ints() {
+        let pkd = PackedBools16::from_bits(0x8000);
+        assert_eq!(pkd, [F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, T]);
+        assert_eq!(pkd, 0x8000);
+        assert_ne!(0x0001, pkd);
+    }
+
+    #[test]
+    fn majority() {
+        let a = PackedBools16::from_bits(0xff00);
+        let b = PackedBools16::from_bits(0xf0f0);
+        let c = PackedBools16::from_bits(0xcccc);
+        assert_eq!(PackedBools16::majority(a, b, c), 0xfcc0);
+        assert_eq!(PackedBools16::ternary(a, b, c, |a, b, c| a ^ b ^ c), 0xc33c);
+    }
+
+    #[test]
+    fn count_matching() {
+        let a = PackedBools16::from_bits(0xff00);
+        assert_eq!(a.count_matching(PackedBools16::from_bits(0xf000)), 12);
+    }
+
+    #[test]
+    fn widen() {
+        let pkd = PackedBools16::from_bits(0xbeef);
+        assert_eq!(u32::from(pkd), 0xbeef);
+        assert_eq!(u128::from(pkd), 0xbeef);
+    }
+
+    #[test]
+    fn replace() {
+        let mut pkd = PackedBools16::new();
+        assert!(!pkd.set_replace(true, 15));
+        assert!(pkd.toggle_get(15));
+        assert_eq!(pkd.try_set_replace(true, 16), None);
+        assert_eq!(pkd, PackedBools16::new());
+    }
+
+    #[test]
+    fn find_from() {
+        let pkd = PackedBools16::from_bits(0x8001);
+        assert_eq!(pkd.find_true_from(1), Some(15));
+        assert_eq!(pkd.rfind_true_from(14), Some(0));
+        assert_eq!(pkd.find_false_from(15), None);
+        assert_eq!(pkd.rfind_false_from(15), Some(14));
+        assert_eq!(pkd.next_true_after(0), Some(15));
+        assert_eq!(pkd.next_true_after(15), None);
+        assert_eq!(pkd.prev_true_before(15), Some(0));
+    }
+
+    #[test]
+    #[allow(clippy::useless_conversion)]
     fn iter() {
         let pkd = PackedBools16::new();
         for b in pkd {
@@ -113,6 +265,18 @@ mod tests {
             .for_each(|(a, b)| assert_eq!(a, b));
     }
 
+    #[test]
+    fn peek() {
+        let mut iter = PackedBools16::from_bits(0x8002).into_iter();
+        assert_eq!(iter.peek(), Some(false));
+        assert_eq!(iter.peek_back(), Some(true));
+        iter.next();
+        assert_eq!(iter.peek(), Some(true));
+        assert_eq!(iter.len(), 15);
+        iter.by_ref().for_each(drop);
+        assert_eq!((iter.peek(), iter.peek_back()), (None, None));
+    }
+
     #[test]
     fn iter_back() {
         let arr = [F,F,T,T,T,T,F,T,F,F,T,F,F,F,T,T];
@@ -120,4 +284,17 @@ mod tests {
             .zip(arr.into_iter().rev())
             .for_each(|(a, b)| assert_eq!(a, b));
     }
+
+    #[test]
+    fn mask_bytes() {
+        let pkd = PackedBools16::from_bits(0x8101);
+        let mut expected = [0; 16];
+        expected[0] = 0xff;
+        expected[8] = 0xff;
+        expected[15] = 0xff;
+        assert_eq!(pkd.to_mask_bytes(), expected);
+        assert_eq!(PackedBools16::from_mask_bytes(expected), pkd);
+        let pkd = PackedBools16::from_bits(0x5a3c);
+        assert_eq!(PackedBools16::from_mask_bytes(pkd.to_mask_bytes()), pkd);
+    }
 }
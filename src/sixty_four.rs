@@ -0,0 +1,136 @@
+//! Packing 64 booleans together into 8 bytes.
+
+use core::{iter::FusedIterator, ops};
+
+crate::macros::packed_bools_type!{
+    NAME = PackedBools64,
+    BUILDER = PackedBools64Builder,
+    INDEX = BitIndex64,
+    REPR = u64,
+    BOOL_COUNT = 64,
+    BCOUNT_MINUS1 = 63,
+    BYTE_DESCRIPTION = "eight bytes",
+    PRETTY_DEBUG = "PackedBools64(\n    {:#066b},\n)",
+    DEBUG = "PackedBools64({:#066b})",
+    BINARY = "{:064b}",
+    LOW_HEX = "{:016x}",
+    UPPER_HEX = "{:016X}",
+    WIDER_INTS = [u128],
+    WIDER_TYPES = [PackedBools128]
+}
+
+impl IntoIterator for PackedBools64 {
+    type Item = bool;
+    type IntoIter = IntoIter64;
+
+    fn into_iter(self) -> IntoIter64 {
+        IntoIter64::new(self)
+    }
+}
+
+/// An iterator over the booleans in a [`PackedBools64`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(not(doc), repr(C))]
+pub struct IntoIter64 {
+    bools: PackedBools64,
+    range: ops::Range<u8>
+}
+
+impl IntoIter64 {
+    fn new(bools: PackedBools64) -> Self {
+        Self { bools, range: 0..64 }
+    }
+
+    /// Returns the next boolean without advancing the iterator.
+    pub fn peek(&self) -> Option<bool> {
+        self.range.clone().next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    /// Returns the next boolean from the back without advancing the iterator.
+    pub fn peek_back(&self) -> Option<bool> {
+        self.range.clone().next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl Iterator for IntoIter64 {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        // try_get here because it strips the panicking path entirely
+        // None should never be returned
+        // but it should hopefully optimize the unreachable paths out
+        self.range.next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<bool> {
+        self.range.nth(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter64 {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<bool> {
+        self.range.nth_back(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl ExactSizeIterator for IntoIter64 {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+impl FusedIterator for IntoIter64 {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::PackedBools64;
+
+    #[test]
+    fn set_get() {
+        let mut pkd = PackedBools64::new();
+        pkd.set(true, 0);
+        pkd.set(true, 33);
+        pkd.set(true, 63);
+        assert!(pkd.get(33));
+        pkd.toggle(33);
+        assert!(!pkd.get(33));
+        assert_eq!(pkd.try_get(64), None);
+        assert_eq!(pkd.to_bits(), 0x8000_0000_0000_0001);
+        assert_eq!(pkd.count_true(), 2);
+    }
+
+    #[test]
+    fn formatting() {
+        let pkd = PackedBools64::from_bits(0xd4);
+        assert_eq!(format!("{pkd:x}"), "00000000000000d4");
+        assert_eq!(format!("{pkd:#X}"), "0x00000000000000D4");
+        assert_eq!(format!("{pkd:?}"), format!("PackedBools64({:#066b})", 0xd4));
+    }
+
+    #[test]
+    fn iter() {
+        let bits = 0x0123_4567_89ab_cdef;
+        let pkd = PackedBools64::from_bits(bits);
+        assert_eq!(pkd.into_iter().len(), 64);
+        pkd.into_iter()
+            .enumerate()
+            .for_each(|(idx, b)| assert_eq!(b, (bits >> idx) & 1 != 0));
+        pkd.into_iter()
+            .rev()
+            .zip(pkd.get_all().into_iter().rev())
+            .for_each(|(a, b)| assert_eq!(a, b));
+    }
+}
@@ -0,0 +1,780 @@
+//! Operations over slices of packed booleans.
+//!
+//! Functions that take a buffer of bytes treat bit `i` of the buffer as bit `i % 8` of byte `i / 8`.
+//!
+//! The bulk operations here work on whole 128-bit blocks at a time,
+//! which the compiler turns into vector instructions where the target has them,
+//! and fall back to handling single bytes at the end of the slices.
+
+use core::cell::Cell;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::PackedBools8;
+
+/// The size of the blocks the bulk operations work on.
+const BLOCK_SIZE: usize = core::mem::size_of::<u128>();
+
+/// Sets every value in `dst` to the bitwise AND of itself and the matching value in `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn and_assign(dst: &mut [PackedBools8], src: &[PackedBools8]) {
+    combine(dst, src, |a, b| a & b);
+}
+
+/// Sets every value in `dst` to the bitwise OR of itself and the matching value in `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn or_assign(dst: &mut [PackedBools8], src: &[PackedBools8]) {
+    combine(dst, src, |a, b| a | b);
+}
+
+/// Sets every value in `dst` to the bitwise XOR of itself and the matching value in `src`.
+///
+/// # Panics
+///
+/// Panics if the two slices have different lengths.
+pub fn xor_assign(dst: &mut [PackedBools8], src: &[PackedBools8]) {
+    combine(dst, src, |a, b| a ^ b);
+}
+
+/// Inverts every value in `dst`.
+pub fn not_assign(dst: &mut [PackedBools8]) {
+    let dst = PackedBools8::slice_as_bytes_mut(dst);
+    let mut blocks = dst.chunks_exact_mut(BLOCK_SIZE);
+    for block in &mut blocks {
+        let val = !u128::from_ne_bytes((&*block).try_into().unwrap());
+        block.copy_from_slice(&val.to_ne_bytes());
+    }
+    for byte in blocks.into_remainder() {
+        *byte = !*byte;
+    }
+}
+
+/// Gets the boolean at the given index of a buffer.
+///
+/// # Panics
+///
+/// Panics if the index is not less than the number of bits in the buffer.
+pub fn get_bit(bytes: &[u8], idx: u64) -> bool {
+    let (byte, bit) = bit_position(bytes, idx);
+    (bytes[byte] >> bit) & 1 != 0
+}
+
+/// Sets the boolean at the given index of a buffer to val.
+///
+/// # Panics
+///
+/// Panics if the index is not less than the number of bits in the buffer.
+pub fn set_bit(bytes: &mut [u8], val: bool, idx: u64) {
+    let (byte, bit) = bit_position(bytes, idx);
+    match val {
+        true => bytes[byte] |= 1 << bit,
+        false => bytes[byte] &= !(1 << bit),
+    }
+}
+
+/// Toggles the boolean at the given index of a buffer.
+///
+/// # Panics
+///
+/// Panics if the index is not less than the number of bits in the buffer.
+pub fn toggle_bit(bytes: &mut [u8], idx: u64) {
+    let (byte, bit) = bit_position(bytes, idx);
+    bytes[byte] ^= 1 << bit;
+}
+
+/// Counts how many true values there are in a buffer, a block at a time.
+pub fn count_true(bytes: &[u8]) -> u64 {
+    let mut blocks = bytes.chunks_exact(BLOCK_SIZE);
+    let mut count: u64 = (&mut blocks)
+        .map(|block| u64::from(u128::from_ne_bytes(block.try_into().unwrap()).count_ones()))
+        .sum();
+    count += blocks.remainder().iter().map(|b| u64::from(b.count_ones())).sum::<u64>();
+    count
+}
+
+/// Returns true if any value in a buffer is true.
+pub fn any(bytes: &[u8]) -> bool {
+    let mut blocks = bytes.chunks_exact(BLOCK_SIZE);
+    blocks.any(|block| u128::from_ne_bytes(block.try_into().unwrap()) != 0)
+        || blocks.remainder().iter().any(|&b| b != 0)
+}
+
+/// Returns true if the first `len` values in a buffer are all true.
+///
+/// The bits after those are ignored.
+///
+/// # Panics
+///
+/// Panics if `len` is greater than the number of bits in the buffer.
+pub fn all(bytes: &[u8], len: u64) -> bool {
+    assert!(len <= bytes.len() as u64 * 8, "The length cannot be greater than the number of bits in the buffer");
+    let (full, partial) = bytes[..len.div_ceil(8) as usize].split_at((len / 8) as usize);
+    let mut blocks = full.chunks_exact(BLOCK_SIZE);
+    let full_true = blocks.all(|block| u128::from_ne_bytes(block.try_into().unwrap()) == u128::MAX)
+        && blocks.remainder().iter().all(|&b| b == u8::MAX);
+    full_true && partial.iter().all(|&b| {
+        let mask = (1 << (len % 8)) - 1;
+        b & mask == mask
+    })
+}
+
+/// Returns the index of the first true value in a buffer.
+pub fn first_true(bytes: &[u8]) -> Option<u64> {
+    first_set(bytes, false)
+}
+
+/// Returns the index of the first false value in a buffer.
+pub fn first_false(bytes: &[u8]) -> Option<u64> {
+    first_set(bytes, true)
+}
+
+/// Returns the index of the first value that is different between two buffers.
+///
+/// If one buffer is a prefix of the other, this returns the length in bits of the shorter one,
+/// and if they are equal, this returns `None`.
+pub fn mismatch(a: &[u8], b: &[u8]) -> Option<u64> {
+    let len = a.len().min(b.len());
+    let same_len = a.len() == b.len();
+    let (a, b) = (&a[..len], &b[..len]);
+    let mut a_blocks = a.chunks_exact(BLOCK_SIZE);
+    let mut b_blocks = b.chunks_exact(BLOCK_SIZE);
+    for (i, (x, y)) in a_blocks.by_ref().zip(b_blocks.by_ref()).enumerate() {
+        let diff = u128::from_le_bytes(x.try_into().unwrap()) ^ u128::from_le_bytes(y.try_into().unwrap());
+        if diff != 0 {
+            return Some((i * BLOCK_SIZE) as u64 * 8 + u64::from(diff.trailing_zeros()));
+        }
+    }
+    let start = len - a_blocks.remainder().len();
+    let diff = a_blocks.remainder().iter()
+        .zip(b_blocks.remainder())
+        .enumerate()
+        .find_map(|(i, (x, y))| {
+            let diff = x ^ y;
+            (diff != 0).then(|| (start + i) as u64 * 8 + u64::from(diff.trailing_zeros()))
+        });
+    diff.or_else(|| (!same_len).then_some(len as u64 * 8))
+}
+
+/// Returns the first index in a buffer where a pattern of `pattern_len` values starts.
+///
+/// Value `i` of the pattern is bit `i` of `pattern`, and the bits past `pattern_len` are ignored.
+/// The pattern can start at any index, not just at the start of a byte.
+///
+/// # Panics
+///
+/// Panics if `pattern_len` is greater than 64.
+pub fn find_pattern(haystack: &[u8], pattern: u64, pattern_len: u8) -> Option<u64> {
+    assert!(pattern_len <= 64, "The pattern length cannot be greater than 64");
+    if pattern_len == 0 {
+        return Some(0);
+    }
+    let mask = u64::MAX >> (64 - pattern_len);
+    let pattern = pattern & mask;
+    let top = pattern_len - 1;
+    // the last `pattern_len` values read, with the oldest in the lowest bit
+    let mut window = 0;
+    for (byte_idx, &byte) in haystack.iter().enumerate() {
+        for bit in 0..8 {
+            window = (window >> 1) | (u64::from((byte >> bit) & 1) << top);
+            let end = byte_idx as u64 * 8 + bit + 1;
+            if end >= u64::from(pattern_len) && window == pattern {
+                return Some(end - u64::from(pattern_len));
+            }
+        }
+    }
+    None
+}
+
+/// Copies `len` values from `src`, starting at index `src_offset`,
+/// into `dst`, starting at index `dst_offset`.
+///
+/// The offsets don't have to be byte aligned, and the values in `dst`
+/// outside of the copied range are left unchanged.
+///
+/// # Panics
+///
+/// Panics if either range goes past the end of its buffer.
+pub fn copy_bits(dst: &mut [u8], dst_offset: u64, src: &[u8], src_offset: u64, len: u64) {
+    check_range(src, src_offset, len);
+    check_range(dst, dst_offset, len);
+    let mut done = 0;
+    while done < len {
+        let n = (len - done).min(64) as u8;
+        store_bits(dst, dst_offset + done, n, load_bits(src, src_offset + done, n));
+        done += u64::from(n);
+    }
+}
+
+/// Copies `len` values within a buffer, from the index `src_offset` to the index `dst_offset`.
+///
+/// The two ranges may overlap, in which case
+/// the copied values are the ones from before the copy started.
+///
+/// # Panics
+///
+/// Panics if either range goes past the end of the buffer.
+pub fn copy_bits_within(bytes: &mut [u8], src_offset: u64, dst_offset: u64, len: u64) {
+    check_range(bytes, src_offset, len);
+    check_range(bytes, dst_offset, len);
+    if dst_offset <= src_offset {
+        // copying forwards only overwrites values that have already been read
+        let mut done = 0;
+        while done < len {
+            let n = (len - done).min(64) as u8;
+            let val = load_bits(bytes, src_offset + done, n);
+            store_bits(bytes, dst_offset + done, n, val);
+            done += u64::from(n);
+        }
+    } else {
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(64) as u8;
+            remaining -= u64::from(n);
+            let val = load_bits(bytes, src_offset + remaining, n);
+            store_bits(bytes, dst_offset + remaining, n, val);
+        }
+    }
+}
+
+/// Packs a slice of booleans into bytes, with bool `i` going to bit `i % 8` of byte `i / 8`.
+///
+/// Bits in the last byte past the end of `bools` are set to false,
+/// and bytes past that are left unchanged.
+///
+/// This packs 8 booleans at a time with a multiplication,
+/// which is much faster than packing them one by one.
+///
+/// # Panics
+///
+/// Panics if `out` has less than `bools.len().div_ceil(8)` bytes.
+pub fn pack_bools(bools: &[bool], out: &mut [u8]) {
+    let out_len = bools.len().div_ceil(8);
+    assert!(out.len() >= out_len, "The output slice is too short");
+    let mut chunks = bools.chunks_exact(8);
+    for (chunk, byte) in (&mut chunks).zip(out.iter_mut()) {
+        let chunk: &[bool; 8] = chunk.try_into().unwrap();
+        *byte = pack_byte(chunk.map(u8::from));
+    }
+    let rem = chunks.remainder();
+    if !rem.is_empty() {
+        let mut last = [0; 8];
+        last.iter_mut().zip(rem).for_each(|(b, &val)| *b = val.into());
+        out[out_len - 1] = pack_byte(last);
+    }
+}
+
+/// Unpacks bytes into a slice of booleans, with bool `i` coming from bit `i % 8` of byte `i / 8`.
+///
+/// This fills all of `out`, and unpacks 8 booleans at a time.
+///
+/// # Panics
+///
+/// Panics if `bytes` has less than `out.len().div_ceil(8)` bytes.
+pub fn unpack_bools(bytes: &[u8], out: &mut [bool]) {
+    assert!(bytes.len() >= out.len().div_ceil(8), "The input slice is too short");
+    for (chunk, &byte) in out.chunks_mut(8).zip(bytes) {
+        let unpacked = unpack_byte(byte);
+        chunk.iter_mut().zip(unpacked).for_each(|(b, val)| *b = val != 0);
+    }
+}
+
+/// Returns the bits of a buffer as a string of `0`s and `1`s, in index order.
+///
+/// Unlike formatting the bytes with `{:b}`, the first character is bit 0.
+#[cfg(feature = "alloc")]
+pub fn to_binary_string(bytes: &[u8]) -> alloc::string::String {
+    bytes.iter()
+        .flat_map(|&byte| (0..8).map(move |i| if (byte >> i) & 1 != 0 { '1' } else { '0' }))
+        .collect()
+}
+
+/// Returns the bits of a buffer as lowercase hexadecimal digits, 4 bits at a time in index order.
+///
+/// Digit `k` holds bits `4k` to `4k + 3`, with bit `4k` as its lowest bit,
+/// so each byte is written low nibble first.
+#[cfg(feature = "alloc")]
+pub fn to_hex_string(bytes: &[u8]) -> alloc::string::String {
+    bytes.iter()
+        .flat_map(|&byte| [byte & 0xf, byte >> 4])
+        .map(|nibble| char::from(HEX_DIGITS[usize::from(nibble)]))
+        .collect()
+}
+
+pub(crate) const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Returns an iterator over every boolean in a slice, in order,
+/// as proxies that can read and write the boolean they point to.
+///
+/// ```
+/// use packed_booleans::{slices, PackedBools8};
+///
+/// let mut values = [PackedBools8::new(); 2];
+/// for (i, bit) in slices::bits_mut(&mut values).enumerate() {
+///     bit.set(i % 3 == 0);
+/// }
+/// assert_eq!(values[0].to_bits(), 0b0100_1001);
+/// assert_eq!(values[1].to_bits(), 0b1001_0010);
+/// ```
+pub fn bits_mut(values: &mut [PackedBools8]) -> BitsMut<'_> {
+    let len = values.len() * 8;
+    BitsMut::new(values, 0..len)
+}
+
+/// An iterator over the booleans in a slice of packed values, as [`BitMut`] proxies.
+///
+/// This is created by [`bits_mut`] and [`PackedBoolVec::iter_mut`](crate::PackedBoolVec::iter_mut).
+#[derive(Debug)]
+pub struct BitsMut<'a> {
+    cells: &'a [Cell<PackedBools8>],
+    range: Range<usize>,
+}
+
+impl<'a> BitsMut<'a> {
+    /// Creates an iterator over the given range of bits, which must be in bounds.
+    pub(crate) fn new(values: &'a mut [PackedBools8], range: Range<usize>) -> Self {
+        Self { cells: Cell::from_mut(values).as_slice_of_cells(), range }
+    }
+
+    fn proxy(&self, idx: usize) -> BitMut<'a> {
+        BitMut { cell: &self.cells[idx / 8], idx: (idx % 8) as u8 }
+    }
+}
+
+impl<'a> Iterator for BitsMut<'a> {
+    type Item = BitMut<'a>;
+
+    fn next(&mut self) -> Option<BitMut<'a>> {
+        self.range.next().map(|idx| self.proxy(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<BitMut<'a>> {
+        self.range.nth(n).map(|idx| self.proxy(idx))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BitsMut<'a> {
+    fn next_back(&mut self) -> Option<BitMut<'a>> {
+        self.range.next_back().map(|idx| self.proxy(idx))
+    }
+}
+
+impl ExactSizeIterator for BitsMut<'_> {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+impl FusedIterator for BitsMut<'_> {}
+
+/// A proxy for a single boolean in a slice of packed values.
+///
+/// Writes through the proxy go straight to the slice.
+#[derive(Debug, Clone, Copy)]
+pub struct BitMut<'a> {
+    cell: &'a Cell<PackedBools8>,
+    idx: u8,
+}
+
+impl BitMut<'_> {
+    /// Gets the boolean.
+    pub fn get(&self) -> bool {
+        self.cell.get().get(self.idx)
+    }
+
+    /// Sets the boolean to val.
+    pub fn set(&self, val: bool) {
+        let mut pkd = self.cell.get();
+        pkd.set(val, self.idx);
+        self.cell.set(pkd);
+    }
+
+    /// Sets the boolean to val, returning its previous value.
+    pub fn replace(&self, val: bool) -> bool {
+        let prev = self.get();
+        self.set(val);
+        prev
+    }
+
+    /// Toggles the boolean.
+    pub fn toggle(&self) {
+        let mut pkd = self.cell.get();
+        pkd.toggle(self.idx);
+        self.cell.set(pkd);
+    }
+}
+
+/// Splits a bit index into a byte index and a bit within that byte.
+fn bit_position(bytes: &[u8], idx: u64) -> (usize, u8) {
+    assert!(idx / 8 < bytes.len() as u64, "The index must be less than the number of bits in the buffer");
+    ((idx / 8) as usize, (idx % 8) as u8)
+}
+
+fn check_range(bytes: &[u8], offset: u64, len: u64) {
+    let end = offset.checked_add(len);
+    assert!(
+        end.is_some_and(|end| end <= bytes.len() as u64 * 8),
+        "The range cannot go past the end of the buffer"
+    );
+}
+
+/// Reads `n` bits, up to 64, starting at the given index.
+pub(crate) fn load_bits(bytes: &[u8], offset: u64, n: u8) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let first = (offset / 8) as usize;
+    let last = ((offset + u64::from(n) - 1) / 8) as usize;
+    let mut val = 0u128;
+    for (i, &b) in bytes[first..=last].iter().enumerate() {
+        val |= u128::from(b) << (i * 8);
+    }
+    let mask = u128::MAX >> (128 - u32::from(n));
+    ((val >> (offset % 8)) & mask) as u64
+}
+
+/// Writes the low `n` bits of `val`, up to 64, starting at the given index.
+pub(crate) fn store_bits(bytes: &mut [u8], offset: u64, n: u8, val: u64) {
+    if n == 0 {
+        return;
+    }
+    let first = (offset / 8) as usize;
+    let last = ((offset + u64::from(n) - 1) / 8) as usize;
+    let shift = offset % 8;
+    let mask = (u128::MAX >> (128 - u32::from(n))) << shift;
+    let val = u128::from(val) << shift;
+    for (i, b) in bytes[first..=last].iter_mut().enumerate() {
+        let byte_mask = (mask >> (i * 8)) as u8;
+        *b = (*b & !byte_mask) | ((val >> (i * 8)) as u8 & byte_mask);
+    }
+}
+
+/// Finds the first set bit in a buffer, a block at a time,
+/// inverting every bit first if `invert` is true.
+fn first_set(bytes: &[u8], invert: bool) -> Option<u64> {
+    let flip = if invert { u128::MAX } else { 0 };
+    let mut blocks = bytes.chunks_exact(BLOCK_SIZE);
+    for (i, block) in blocks.by_ref().enumerate() {
+        // little endian, so that bit i of the block is bit i of the buffer
+        let val = u128::from_le_bytes(block.try_into().unwrap()) ^ flip;
+        if val != 0 {
+            return Some((i * BLOCK_SIZE) as u64 * 8 + u64::from(val.trailing_zeros()));
+        }
+    }
+    let start = bytes.len() - blocks.remainder().len();
+    blocks.remainder().iter().enumerate().find_map(|(i, &b)| {
+        let val = b ^ flip as u8;
+        (val != 0).then(|| (start + i) as u64 * 8 + u64::from(val.trailing_zeros()))
+    })
+}
+
+/// Packs 8 bytes which are each 0 or 1 into the bits of a byte.
+#[inline]
+fn pack_byte(bytes: [u8; 8]) -> u8 {
+    // the multiplication moves the low bit of byte i to bit 56 + i,
+    // with no carries as the partial products never overlap
+    (u64::from_le_bytes(bytes).wrapping_mul(0x0102_0408_1020_4080) >> 56) as u8
+}
+
+/// Unpacks the bits of a byte into 8 bytes which are each 0 or 1.
+#[inline]
+fn unpack_byte(byte: u8) -> [u8; 8] {
+    const LSB: u64 = 0x0101_0101_0101_0101;
+    // copy the byte into every lane, and keep only bit i in lane i
+    let spread = (u64::from(byte) * LSB) & 0x8040_2010_0804_0201;
+    // adding 0x7f to each lane sets its top bit if any other bit was set
+    let ones = ((spread + 0x7f * LSB) >> 7) & LSB;
+    ones.to_le_bytes()
+}
+
+/// Applies a bitwise operation to every pair of values, a block at a time.
+/// `op` must work on each bit independently.
+fn combine(dst: &mut [PackedBools8], src: &[PackedBools8], op: impl Fn(u128, u128) -> u128) {
+    assert_eq!(dst.len(), src.len(), "The slices must have the same length");
+    let dst = PackedBools8::slice_as_bytes_mut(dst);
+    let src = PackedBools8::slice_as_bytes(src);
+
+    let mut dst_blocks = dst.chunks_exact_mut(BLOCK_SIZE);
+    let mut src_blocks = src.chunks_exact(BLOCK_SIZE);
+    for (d, s) in (&mut dst_blocks).zip(&mut src_blocks) {
+        let val = op(
+            u128::from_ne_bytes((&*d).try_into().unwrap()),
+            u128::from_ne_bytes(s.try_into().unwrap()),
+        );
+        d.copy_from_slice(&val.to_ne_bytes());
+    }
+    for (d, &s) in dst_blocks.into_remainder().iter_mut().zip(src_blocks.remainder()) {
+        *d = op(u128::from(*d), u128::from(s)) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make<const N: usize>(f: impl Fn(usize) -> u8) -> [PackedBools8; N] {
+        core::array::from_fn(|i| PackedBools8::from_bits(f(i)))
+    }
+
+    #[test]
+    fn bitwise() {
+        // long enough to have both full blocks and a remainder
+        let a: [PackedBools8; 37] = make(|i| (i * 7) as u8);
+        let b: [PackedBools8; 37] = make(|i| (i * 13 + 5) as u8);
+
+        let mut and = a;
+        and_assign(&mut and, &b);
+        let mut or = a;
+        or_assign(&mut or, &b);
+        let mut xor = a;
+        xor_assign(&mut xor, &b);
+        let mut not = a;
+        not_assign(&mut not);
+
+        for i in 0..a.len() {
+            assert_eq!(and[i], a[i] & b[i]);
+            assert_eq!(or[i], a[i] | b[i]);
+            assert_eq!(xor[i], a[i] ^ b[i]);
+            assert_eq!(not[i], !a[i]);
+        }
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let bools: [bool; 29] = core::array::from_fn(|i| (i * 5) % 3 == 0 || i % 7 == 1);
+        let mut bytes = [0xff; 5];
+        pack_bools(&bools, &mut bytes);
+        for (i, &b) in bools.iter().enumerate() {
+            assert_eq!((bytes[i / 8] >> (i % 8)) & 1 != 0, b);
+        }
+        // the padding bits are cleared, and the extra byte is untouched
+        assert_eq!(bytes[3] >> 5, 0);
+        assert_eq!(bytes[4], 0xff);
+
+        let mut unpacked = [false; 29];
+        unpack_bools(&bytes, &mut unpacked);
+        assert_eq!(unpacked, bools);
+    }
+
+    #[test]
+    fn pack_byte_exhaustive() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(pack_byte(unpack_byte(byte)), byte);
+            assert_eq!(
+                unpack_byte(byte).map(|b| b != 0),
+                PackedBools8::from_bits(byte).get_all()
+            );
+        }
+    }
+
+    #[test]
+    fn single_bits() {
+        let mut bytes = [0b1000_0001, 0];
+        assert!(get_bit(&bytes, 0));
+        assert!(get_bit(&bytes, 7));
+        assert!(!get_bit(&bytes, 8));
+        set_bit(&mut bytes, true, 9);
+        set_bit(&mut bytes, false, 0);
+        toggle_bit(&mut bytes, 15);
+        toggle_bit(&mut bytes, 7);
+        assert_eq!(bytes, [0, 0b1000_0010]);
+    }
+
+    #[test]
+    fn count() {
+        let bytes: [u8; 37] = core::array::from_fn(|i| (i * 29) as u8);
+        let expected: u32 = bytes.iter().map(|b| b.count_ones()).sum();
+        assert_eq!(count_true(&bytes), u64::from(expected));
+        assert_eq!(count_true(&[]), 0);
+    }
+
+    #[test]
+    fn any_all() {
+        let mut bytes = [0; 37];
+        assert!(!any(&bytes));
+        assert!(!any(&[]));
+        bytes[36] = 0b1000_0000;
+        assert!(any(&bytes));
+        bytes[20] = 1;
+        bytes[36] = 0;
+        assert!(any(&bytes));
+
+        let mut bytes = [0xff; 37];
+        assert!(all(&bytes, 37 * 8));
+        assert!(all(&[], 0));
+        bytes[36] = 0b0001_1111;
+        assert!(all(&bytes, 36 * 8 + 5));
+        assert!(!all(&bytes, 36 * 8 + 6));
+        bytes[3] = 0xfe;
+        assert!(all(&bytes, 3 * 8));
+        assert!(!all(&bytes, 3 * 8 + 1));
+        assert!(!all(&bytes, 36 * 8 + 5));
+    }
+
+    #[test]
+    fn first() {
+        fn naive(bytes: &[u8], val: bool) -> Option<u64> {
+            (0..bytes.len() as u64 * 8).find(|&i| get_bit(bytes, i) == val)
+        }
+
+        assert_eq!(first_true(&[]), None);
+        assert_eq!(first_false(&[0xff; 40]), None);
+        // every single bit position, set in a sea of the opposite value
+        for idx in 0..40 * 8 {
+            let mut zeros = [0; 40];
+            set_bit(&mut zeros, true, idx);
+            assert_eq!(first_true(&zeros), Some(idx));
+            let mut ones = [0xff; 40];
+            set_bit(&mut ones, false, idx);
+            assert_eq!(first_false(&ones), Some(idx));
+        }
+        // pseudo-random sparse buffers of varying lengths
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for len in 0..70 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let bytes: [u8; 70] = core::array::from_fn(|i| {
+                let x = state.rotate_left(i as u32 * 5);
+                if x.is_multiple_of(9) { x as u8 } else { 0 }
+            });
+            assert_eq!(first_true(&bytes[..len]), naive(&bytes[..len], true));
+            let inverted = bytes.map(|b| !b);
+            assert_eq!(first_false(&inverted[..len]), naive(&inverted[..len], false));
+        }
+    }
+
+    #[test]
+    fn mismatches() {
+        let a: [u8; 40] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        assert_eq!(mismatch(&a, &a), None);
+        assert_eq!(mismatch(&[], &[]), None);
+        for idx in (0..40 * 8).step_by(3) {
+            let mut b = a;
+            toggle_bit(&mut b, idx);
+            assert_eq!(mismatch(&a, &b), Some(idx));
+            toggle_bit(&mut b, 40 * 8 - 1);
+            assert_eq!(mismatch(&b, &a), Some(idx));
+        }
+        assert_eq!(mismatch(&a[..20], &a), Some(160));
+        assert_eq!(mismatch(&a, &a[..33]), Some(264));
+    }
+
+    #[test]
+    fn patterns() {
+        let haystack: [u8; 16] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        for offset in [0, 1, 7, 8, 45, 64, 100] {
+            for len in [1, 5, 13, 32, 64] {
+                if offset + u64::from(len) > 128 {
+                    continue;
+                }
+                let pattern = load_bits(&haystack, offset, len);
+                let found = find_pattern(&haystack, pattern, len).unwrap();
+                assert!(found <= offset);
+                assert_eq!(load_bits(&haystack, found, len), pattern);
+            }
+        }
+        let mut sync = [0; 6];
+        copy_bits(&mut sync, 19, &[0x2d, 0xd4], 0, 16);
+        assert_eq!(find_pattern(&sync, 0xd42d, 16), Some(19));
+        assert_eq!(find_pattern(&sync, 0xffff_d42d, 32), None);
+        assert_eq!(find_pattern(&[], 0, 0), Some(0));
+        assert_eq!(find_pattern(&[], 0, 1), None);
+    }
+
+    #[test]
+    fn proxies() {
+        let mut values: [PackedBools8; 3] = make(|i| (i * 85) as u8);
+        let expected: [PackedBools8; 3] = make(|i| !(i * 85) as u8);
+        let mut bits = bits_mut(&mut values);
+        assert_eq!(bits.len(), 24);
+        let last = bits.next_back().unwrap();
+        assert!(last.get());
+        for bit in bits {
+            bit.toggle();
+        }
+        assert!(last.replace(false));
+        assert_eq!(values, expected);
+
+        let mut values = [PackedBools8::new(); 2];
+        let mut bits = bits_mut(&mut values);
+        let a = bits.nth(3).unwrap();
+        let b = bits.next().unwrap();
+        a.set(true);
+        b.set(true);
+        assert_eq!(values[0].to_bits(), 0b0001_1000);
+    }
+
+    #[test]
+    fn copy() {
+        let src: [u8; 24] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        for (src_offset, dst_offset, len) in [(0, 0, 192), (3, 11, 150), (13, 0, 70), (7, 100, 65), (5, 5, 0), (1, 2, 3)] {
+            let mut dst = [0x5a; 24];
+            copy_bits(&mut dst, dst_offset, &src, src_offset, len);
+            for i in 0..192 {
+                let expected = if (dst_offset..dst_offset + len).contains(&i) {
+                    get_bit(&src, i - dst_offset + src_offset)
+                } else {
+                    get_bit(&[0x5a], i % 8)
+                };
+                assert_eq!(get_bit(&dst, i), expected, "{src_offset} {dst_offset} {len} {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn copy_within() {
+        let original: [u8; 24] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        for (src_offset, dst_offset, len) in [(0, 5, 180), (5, 0, 180), (9, 73, 100), (73, 9, 100), (4, 4, 64)] {
+            let mut bytes = original;
+            copy_bits_within(&mut bytes, src_offset, dst_offset, len);
+            for i in 0..192 {
+                let expected = if (dst_offset..dst_offset + len).contains(&i) {
+                    get_bit(&original, i - dst_offset + src_offset)
+                } else {
+                    get_bit(&original, i)
+                };
+                assert_eq!(get_bit(&bytes, i), expected, "{src_offset} {dst_offset} {len} {i}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_out_of_range() {
+        copy_bits(&mut [0; 2], 9, &[0; 4], 0, 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn all_too_long() {
+        all(&[0xff], 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bit_out_of_range() {
+        get_bit(&[0; 2], 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pack_too_short() {
+        pack_bools(&[true; 9], &mut [0; 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_mismatch() {
+        or_assign(&mut [PackedBools8::new(); 3], &[PackedBools8::new(); 2]);
+    }
+}
@@ -0,0 +1,289 @@
+//! Helpers for testing code built on the packed types.
+//!
+//! The exhaustive iterators visit every value of the small types,
+//! the generators make deterministic values of any type with a given number of true booleans,
+//! and the assertions check the laws every packed type follows for a given value.
+//!
+//! The generators and assertions work on any type implementing [`BitAccess`],
+//! which is implemented for the packed types and can be implemented for other types of booleans.
+//!
+//! This module needs the `test-support` feature.
+//!
+//! ```
+//! use packed_booleans::test_support::{assert_invariants, exhaustive8, with_popcount, XorShift};
+//! use packed_booleans::PackedBools64;
+//!
+//! exhaustive8().for_each(assert_invariants);
+//!
+//! let mut rng = XorShift::new(7);
+//! for k in 0..=64 {
+//!     let value: PackedBools64 = with_popcount(k, &mut rng);
+//!     assert_eq!(usize::from(value.count_true()), k);
+//!     assert_invariants(value);
+//! }
+//! ```
+
+use core::fmt;
+
+use crate::{PackedBools8, PackedBools16, PackedBools32, PackedBools64, PackedBools128};
+
+/// A value holding a fixed number of booleans, which the generators and assertions work on.
+pub trait BitAccess: Copy + PartialEq + fmt::Debug {
+    /// Returns how many booleans the value holds.
+    fn bit_len(&self) -> usize;
+
+    /// Gets the boolean at the given index, which is less than `bit_len()`.
+    fn bit(&self, idx: usize) -> bool;
+
+    /// Sets the boolean at the given index, which is less than `bit_len()`, to val.
+    fn set_bit(&mut self, idx: usize, val: bool);
+
+    /// Toggles the boolean at the given index, which is less than `bit_len()`.
+    fn toggle_bit(&mut self, idx: usize);
+}
+
+macro_rules! impl_bit_access {
+    ($($pkd:ident),*) => {
+        $(
+            impl BitAccess for $pkd {
+                fn bit_len(&self) -> usize {
+                    usize::from(<$pkd as crate::FlagStorage>::CAPACITY)
+                }
+
+                fn bit(&self, idx: usize) -> bool {
+                    self.get(idx as u8)
+                }
+
+                fn set_bit(&mut self, idx: usize, val: bool) {
+                    self.set(val, idx as u8);
+                }
+
+                fn toggle_bit(&mut self, idx: usize) {
+                    self.toggle(idx as u8);
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_access!(PackedBools8, PackedBools16, PackedBools32, PackedBools64, PackedBools128);
+
+/// Returns an iterator over all 256 values of a [`PackedBools8`], in increasing order of their bits.
+pub fn exhaustive8() -> impl DoubleEndedIterator<Item = PackedBools8> + ExactSizeIterator + Clone {
+    (0..=u8::MAX).map(PackedBools8::from_bits)
+}
+
+/// Returns an iterator over all 65536 values of a [`PackedBools16`], in increasing order of their bits.
+pub fn exhaustive16() -> impl DoubleEndedIterator<Item = PackedBools16> + Clone {
+    (0..=u16::MAX).map(PackedBools16::from_bits)
+}
+
+/// A small, deterministic pseudo-random number generator for making test values.
+///
+/// This is Marsaglia's xorshift64, which is fast and reproducible but not suitable for anything secure.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// Creates a new generator from a seed.
+    /// A seed of 0 is replaced with a fixed nonzero seed.
+    pub const fn new(seed: u64) -> Self {
+        Self(if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed })
+    }
+
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Returns a pseudo-random number less than `bound`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "The bound cannot be 0");
+        self.next_u64() % bound
+    }
+}
+
+/// Returns a pseudo-random value with exactly `k` true booleans.
+///
+/// # Panics
+///
+/// Panics if `k` is greater than the number of booleans in `T`.
+pub fn with_popcount<T: BitAccess + Default>(k: usize, rng: &mut XorShift) -> T {
+    let mut value = T::default();
+    let len = value.bit_len();
+    assert!(k <= len, "Cannot have more true values than the type stores");
+    // Floyd's algorithm, picking k distinct indices
+    for j in len - k..len {
+        let idx = rng.below(j as u64 + 1) as usize;
+        let idx = if value.bit(idx) { j } else { idx };
+        value.set_bit(idx, true);
+    }
+    value
+}
+
+/// Returns an iterator over `count` pseudo-random values, each with exactly `k` true booleans.
+///
+/// # Panics
+///
+/// The iterator panics if `k` is greater than the number of booleans in `T`.
+pub fn popcount_samples<T: BitAccess + Default>(k: usize, count: usize, seed: u64) -> impl Iterator<Item = T> {
+    let mut rng = XorShift::new(seed);
+    (0..count).map(move |_| with_popcount(k, &mut rng))
+}
+
+/// Asserts that setting a boolean is idempotent, and changes only that boolean.
+pub fn assert_set_idempotent<T: BitAccess>(value: T) {
+    for idx in 0..value.bit_len() {
+        for val in [false, true] {
+            let mut once = value;
+            once.set_bit(idx, val);
+            let mut twice = once;
+            twice.set_bit(idx, val);
+            assert_eq!(once, twice, "setting index {idx} to {val} twice changed {value:?}");
+            assert_eq!(once.bit(idx), val, "setting index {idx} to {val} didn't stick in {value:?}");
+            assert_only_changed(value, once, idx);
+        }
+    }
+}
+
+/// Asserts that toggling a boolean is an involution, and changes only that boolean.
+pub fn assert_toggle_involution<T: BitAccess>(value: T) {
+    for idx in 0..value.bit_len() {
+        let mut toggled = value;
+        toggled.toggle_bit(idx);
+        assert_ne!(toggled.bit(idx), value.bit(idx), "toggling index {idx} of {value:?} didn't change it");
+        assert_only_changed(value, toggled, idx);
+        toggled.toggle_bit(idx);
+        assert_eq!(toggled, value, "toggling index {idx} twice changed {value:?}");
+    }
+}
+
+/// Asserts that writing back every boolean read from a value gives the same value.
+///
+/// Every boolean of a copy is inverted first, so each one has to be written.
+pub fn assert_get_set_roundtrip<T: BitAccess>(value: T) {
+    let mut copy = value;
+    for idx in 0..value.bit_len() {
+        copy.set_bit(idx, !value.bit(idx));
+    }
+    for idx in 0..value.bit_len() {
+        copy.set_bit(idx, value.bit(idx));
+    }
+    assert_eq!(copy, value, "copying every boolean of {value:?} changed it");
+}
+
+/// Runs all the assertions in this module on a value.
+pub fn assert_invariants<T: BitAccess>(value: T) {
+    assert_set_idempotent(value);
+    assert_toggle_involution(value);
+    assert_get_set_roundtrip(value);
+}
+
+fn assert_only_changed<T: BitAccess>(before: T, after: T, changed: usize) {
+    for idx in (0..before.bit_len()).filter(|&idx| idx != changed) {
+        assert_eq!(before.bit(idx), after.bit(idx), "changing index {changed} of {before:?} changed index {idx}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exhaustive() {
+        assert_eq!(exhaustive8().len(), 256);
+        assert_eq!(exhaustive16().count(), 65536);
+        assert_eq!(exhaustive16().next_back(), Some(PackedBools16::from_bits(u16::MAX)));
+    }
+
+    #[test]
+    fn popcounts() {
+        let mut rng = XorShift::new(1);
+        for k in 0..=128 {
+            let value: PackedBools128 = with_popcount(k, &mut rng);
+            assert_eq!(usize::from(value.count_true()), k);
+        }
+        let samples: [PackedBools32; 8] = core::array::from_fn({
+            let mut iter = popcount_samples(5, 8, 3);
+            move |_| iter.next().unwrap()
+        });
+        assert!(samples.iter().all(|s| s.count_true() == 5));
+        assert!(samples.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn invariants_hold() {
+        exhaustive8().for_each(assert_invariants);
+        popcount_samples::<PackedBools128>(40, 16, 9).for_each(assert_invariants);
+    }
+
+    /// A downstream type of booleans, to check the assertions work outside the packed types.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    struct Bools([bool; 12]);
+
+    impl BitAccess for Bools {
+        fn bit_len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn bit(&self, idx: usize) -> bool {
+            self.0[idx]
+        }
+
+        fn set_bit(&mut self, idx: usize, val: bool) {
+            self.0[idx] = val;
+        }
+
+        fn toggle_bit(&mut self, idx: usize) {
+            self.0[idx] = !self.0[idx];
+        }
+    }
+
+    #[test]
+    fn custom_type() {
+        popcount_samples::<Bools>(5, 8, 2).for_each(|value| {
+            assert_eq!(value.0.iter().filter(|&&b| b).count(), 5);
+            assert_invariants(value);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn broken_toggle() {
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        struct NoToggle(Bools);
+
+        impl BitAccess for NoToggle {
+            fn bit_len(&self) -> usize {
+                self.0.bit_len()
+            }
+
+            fn bit(&self, idx: usize) -> bool {
+                self.0.bit(idx)
+            }
+
+            fn set_bit(&mut self, idx: usize, val: bool) {
+                self.0.set_bit(idx, val);
+            }
+
+            fn toggle_bit(&mut self, _idx: usize) {}
+        }
+
+        assert_toggle_involution(NoToggle::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_true() {
+        with_popcount::<PackedBools8>(9, &mut XorShift::new(1));
+    }
+}
@@ -0,0 +1,136 @@
+//! Packing 32 booleans together into 4 bytes.
+
+use core::{iter::FusedIterator, ops};
+
+crate::macros::packed_bools_type!{
+    NAME = PackedBools32,
+    BUILDER = PackedBools32Builder,
+    INDEX = BitIndex32,
+    REPR = u32,
+    BOOL_COUNT = 32,
+    BCOUNT_MINUS1 = 31,
+    BYTE_DESCRIPTION = "four bytes",
+    PRETTY_DEBUG = "PackedBools32(\n    {:#034b},\n)",
+    DEBUG = "PackedBools32({:#034b})",
+    BINARY = "{:032b}",
+    LOW_HEX = "{:08x}",
+    UPPER_HEX = "{:08X}",
+    WIDER_INTS = [u64, u128],
+    WIDER_TYPES = [PackedBools64, PackedBools128]
+}
+
+impl IntoIterator for PackedBools32 {
+    type Item = bool;
+    type IntoIter = IntoIter32;
+
+    fn into_iter(self) -> IntoIter32 {
+        IntoIter32::new(self)
+    }
+}
+
+/// An iterator over the booleans in a [`PackedBools32`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(not(doc), repr(C))]
+pub struct IntoIter32 {
+    bools: PackedBools32,
+    range: ops::Range<u8>
+}
+
+impl IntoIter32 {
+    fn new(bools: PackedBools32) -> Self {
+        Self { bools, range: 0..32 }
+    }
+
+    /// Returns the next boolean without advancing the iterator.
+    pub fn peek(&self) -> Option<bool> {
+        self.range.clone().next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    /// Returns the next boolean from the back without advancing the iterator.
+    pub fn peek_back(&self) -> Option<bool> {
+        self.range.clone().next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl Iterator for IntoIter32 {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        // try_get here because it strips the panicking path entirely
+        // None should never be returned
+        // but it should hopefully optimize the unreachable paths out
+        self.range.next().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<bool> {
+        self.range.nth(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter32 {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().and_then(|idx| self.bools.try_get(idx))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<bool> {
+        self.range.nth_back(n).and_then(|idx| self.bools.try_get(idx))
+    }
+}
+
+impl ExactSizeIterator for IntoIter32 {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+}
+
+impl FusedIterator for IntoIter32 {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::format;
+
+    use super::PackedBools32;
+
+    #[test]
+    fn set_get() {
+        let mut pkd = PackedBools32::new();
+        pkd.set(true, 0);
+        pkd.set(true, 17);
+        pkd.set(true, 31);
+        assert!(pkd.get(17));
+        pkd.toggle(17);
+        assert!(!pkd.get(17));
+        assert_eq!(pkd.try_get(32), None);
+        assert_eq!(pkd.to_bits(), 0x8000_0001);
+        assert_eq!(pkd.count_true(), 2);
+    }
+
+    #[test]
+    fn formatting() {
+        let pkd = PackedBools32::from_bits(0xd4);
+        assert_eq!(format!("{pkd:x}"), "000000d4");
+        assert_eq!(format!("{pkd:#X}"), "0x000000D4");
+        assert_eq!(format!("{pkd:?}"), format!("PackedBools32({:#034b})", 0xd4));
+    }
+
+    #[test]
+    fn iter() {
+        let bits = 0x89ab_cdef;
+        let pkd = PackedBools32::from_bits(bits);
+        assert_eq!(pkd.into_iter().len(), 32);
+        pkd.into_iter()
+            .enumerate()
+            .for_each(|(idx, b)| assert_eq!(b, (bits >> idx) & 1 != 0));
+        pkd.into_iter()
+            .rev()
+            .zip(pkd.get_all().into_iter().rev())
+            .for_each(|(a, b)| assert_eq!(a, b));
+    }
+}
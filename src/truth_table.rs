@@ -0,0 +1,318 @@
+//! Three-input boolean functions, stored as 8-entry truth tables.
+
+use core::{fmt, ops};
+
+use crate::{Ones, PackedBools8};
+
+/// A boolean function of three inputs, stored as an 8-entry truth table.
+///
+/// The result for inputs `a`, `b` and `c` is the value at index `(a << 2) | (b << 1) | c` of the table,
+/// the same layout as [`PackedBools8::ternary_table`].
+/// Every one of the 256 three-input functions has exactly one table.
+///
+/// Because a table is a packed value over all 8 input combinations,
+/// the tables of the inputs themselves are [`A`](Self::A), [`B`](Self::B) and [`C`](Self::C),
+/// and combining tables with `&`, `|`, `^` and `!` combines the functions.
+///
+/// ```
+/// use packed_booleans::{PackedBools16, TruthTable8};
+///
+/// let mux = (TruthTable8::A & TruthTable8::B) | (!TruthTable8::A & TruthTable8::C);
+/// assert_eq!(mux, TruthTable8::MUX);
+/// assert!(mux.eval(true, true, false));
+///
+/// let sel = PackedBools16::from_bits(0xff00);
+/// let a = PackedBools16::from_bits(0x1234);
+/// let b = PackedBools16::from_bits(0xabcd);
+/// assert_eq!(mux.eval_packed(sel, a, b).to_bits(), 0x12cd);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(transparent)]
+pub struct TruthTable8(PackedBools8);
+
+impl TruthTable8 {
+    /// Always false.
+    pub const FALSE: Self = Self::from_bits(0x00);
+    /// Always true.
+    pub const TRUE: Self = Self::from_bits(0xff);
+    /// `a`
+    pub const A: Self = Self::from_bits(0xf0);
+    /// `b`
+    pub const B: Self = Self::from_bits(0xcc);
+    /// `c`
+    pub const C: Self = Self::from_bits(0xaa);
+    /// `a & b & c`
+    pub const AND: Self = Self::from_bits(0x80);
+    /// `a | b | c`
+    pub const OR: Self = Self::from_bits(0xfe);
+    /// `a ^ b ^ c`, which is true when an odd number of the inputs are true.
+    pub const PARITY: Self = Self::from_bits(0x96);
+    /// True when at least two of the inputs are true.
+    pub const MAJORITY: Self = Self::from_bits(0xe8);
+    /// `if a { b } else { c }`
+    pub const MUX: Self = Self::from_bits(0xca);
+
+    /// Creates a function from its truth table.
+    pub const fn new(table: PackedBools8) -> Self {
+        Self(table)
+    }
+
+    /// Creates a function from the bits of its truth table.
+    pub const fn from_bits(table: u8) -> Self {
+        Self(PackedBools8::from_bits(table))
+    }
+
+    /// Creates a function by evaluating `f` on every combination of inputs.
+    pub fn from_fn(f: impl Fn(bool, bool, bool) -> bool) -> Self {
+        let mut table = PackedBools8::new();
+        for i in 0..8 {
+            table.set(f(i & 0b100 != 0, i & 0b010 != 0, i & 0b001 != 0), i);
+        }
+        Self(table)
+    }
+
+    /// Returns the truth table of the function.
+    pub const fn table(self) -> PackedBools8 {
+        self.0
+    }
+
+    /// Returns the bits of the truth table of the function.
+    pub const fn to_bits(self) -> u8 {
+        self.0.to_bits()
+    }
+
+    /// Applies the function to three booleans.
+    pub const fn eval(self, a: bool, b: bool, c: bool) -> bool {
+        self.0.get(((a as u8) << 2) | ((b as u8) << 1) | c as u8)
+    }
+
+    /// Applies the function to each index of three packed values.
+    ///
+    /// This works for every packed type, since it is the same as `T::ternary_table(a, b, c, self.to_bits())`.
+    pub fn eval_packed<T: TruthTableInput>(self, a: T, b: T, c: T) -> T {
+        T::eval_table(a, b, c, self.to_bits())
+    }
+
+    /// Returns the function `self(f(a, b, c), g(a, b, c), h(a, b, c))`.
+    pub const fn compose(self, f: Self, g: Self, h: Self) -> Self {
+        // the tables are packed values over every input combination,
+        // so applying self to them index by index composes the functions
+        Self(PackedBools8::ternary_table(f.0, g.0, h.0, self.to_bits()))
+    }
+
+    /// Returns the function with its inputs reordered.
+    ///
+    /// `order` lists the inputs passed to `self`, where 0 is `a`, 1 is `b` and 2 is `c`,
+    /// so `[2, 1, 0]` swaps `a` and `c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any entry of `order` is greater than 2.
+    pub const fn permute(self, order: [u8; 3]) -> Self {
+        const INPUTS: [TruthTable8; 3] = [TruthTable8::A, TruthTable8::B, TruthTable8::C];
+        assert!(order[0] < 3 && order[1] < 3 && order[2] < 3, "The input indices cannot be greater than 2");
+        self.compose(INPUTS[order[0] as usize], INPUTS[order[1] as usize], INPUTS[order[2] as usize])
+    }
+
+    /// Returns the dual of the function, `!self(!a, !b, !c)`,
+    /// which swaps AND with OR.
+    pub const fn dual(self) -> Self {
+        Self::from_bits(!self.to_bits().reverse_bits())
+    }
+
+    /// Returns true if the result depends on the given input, where 0 is `a`, 1 is `b` and 2 is `c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is greater than 2.
+    pub const fn depends_on(self, input: u8) -> bool {
+        assert!(input < 3, "The input index cannot be greater than 2");
+        // compare the half of the table where the input is true with the half where it is false
+        let shift = 1 << (2 - input);
+        let mask = Self::INPUT_MASKS[input as usize];
+        (self.to_bits() & mask) >> shift != self.to_bits() & !mask
+    }
+
+    const INPUT_MASKS: [u8; 3] = [Self::A.to_bits(), Self::B.to_bits(), Self::C.to_bits()];
+
+    /// Returns the minterms of the function, the input combinations it is true for,
+    /// as indices `(a << 2) | (b << 1) | c`.
+    ///
+    /// The function is the OR of these terms, its canonical sum-of-products form.
+    pub const fn minterms(self) -> Ones<PackedBools8> {
+        self.0.ones()
+    }
+
+    /// Returns the maxterms of the function, the input combinations it is false for,
+    /// as indices `(a << 2) | (b << 1) | c`.
+    ///
+    /// The function is the AND of the negations of these terms, its canonical product-of-sums form.
+    pub const fn maxterms(self) -> Ones<PackedBools8> {
+        PackedBools8::from_bits(!self.to_bits()).ones()
+    }
+}
+
+/// Formats the function in its canonical sum-of-products form, like `!a&b&c | a&!b&c`.
+///
+/// The constant functions are written as `0` and `1`.
+impl fmt::Display for TruthTable8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::FALSE => return f.write_str("0"),
+            Self::TRUE => return f.write_str("1"),
+            _ => {}
+        }
+        for (n, term) in self.minterms().enumerate() {
+            if n != 0 {
+                f.write_str(" | ")?;
+            }
+            for (input, name) in ["a", "b", "c"].into_iter().enumerate() {
+                if input != 0 {
+                    f.write_str("&")?;
+                }
+                if term & (0b100 >> input) == 0 {
+                    f.write_str("!")?;
+                }
+                f.write_str(name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<PackedBools8> for TruthTable8 {
+    fn from(table: PackedBools8) -> Self {
+        Self(table)
+    }
+}
+
+impl From<TruthTable8> for PackedBools8 {
+    fn from(table: TruthTable8) -> Self {
+        table.0
+    }
+}
+
+macro_rules! impl_table_binop {
+    ($op:tt, $trait:ident $method:ident, $assign_trait:ident $assign_method:ident) => {
+        impl ops::$trait for TruthTable8 {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self(self.0 $op rhs.0)
+            }
+        }
+
+        impl ops::$assign_trait for TruthTable8 {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    };
+}
+
+impl_table_binop!(&, BitAnd bitand, BitAndAssign bitand_assign);
+impl_table_binop!(|, BitOr bitor, BitOrAssign bitor_assign);
+impl_table_binop!(^, BitXor bitxor, BitXorAssign bitxor_assign);
+
+impl ops::Not for TruthTable8 {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+/// A type that a [`TruthTable8`] can be applied to.
+///
+/// This is implemented by `bool` and all the packed types, and can't be implemented outside this crate.
+pub trait TruthTableInput: Copy + crate::typed_flags::private::Sealed {
+    /// Applies the truth table to each index of `a`, `b` and `c`.
+    fn eval_table(a: Self, b: Self, c: Self, table: u8) -> Self;
+}
+
+impl crate::typed_flags::private::Sealed for bool {}
+
+impl TruthTableInput for bool {
+    fn eval_table(a: bool, b: bool, c: bool, table: u8) -> bool {
+        TruthTable8::from_bits(table).eval(a, b, c)
+    }
+}
+
+macro_rules! impl_input {
+    ($($pkd:ident),*) => {$(
+        impl TruthTableInput for crate::$pkd {
+            fn eval_table(a: Self, b: Self, c: Self, table: u8) -> Self {
+                Self::ternary_table(a, b, c, table)
+            }
+        }
+    )*};
+}
+
+impl_input!(PackedBools8, PackedBools16, PackedBools32, PackedBools64, PackedBools128);
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::{format, vec::Vec};
+
+    use super::TruthTable8;
+    use crate::PackedBools32;
+
+    type BoolFn = fn(bool, bool, bool) -> bool;
+
+    #[test]
+    fn constants() {
+        let cases: [(TruthTable8, BoolFn); 7] = [
+            (TruthTable8::A, |a, _, _| a),
+            (TruthTable8::B, |_, b, _| b),
+            (TruthTable8::C, |_, _, c| c),
+            (TruthTable8::AND, |a, b, c| a & b & c),
+            (TruthTable8::PARITY, |a, b, c| a ^ b ^ c),
+            (TruthTable8::MAJORITY, |a, b, c| (a & b) | (a & c) | (b & c)),
+            (TruthTable8::MUX, |a, b, c| if a { b } else { c }),
+        ];
+        for (table, f) in cases {
+            assert_eq!(table, TruthTable8::from_fn(f));
+        }
+        assert_eq!(TruthTable8::A | TruthTable8::B | TruthTable8::C, TruthTable8::OR);
+    }
+
+    #[test]
+    fn eval_packed() {
+        let (a, b, c) = (PackedBools32::from_bits(0xdead_beef), PackedBools32::from_bits(0x1234_5678), PackedBools32::from_bits(0x0f0f_f0f0));
+        assert_eq!(TruthTable8::MAJORITY.eval_packed(a, b, c), PackedBools32::majority(a, b, c));
+        assert_eq!(TruthTable8::PARITY.eval_packed(a, b, c), a ^ b ^ c);
+        assert!(TruthTable8::MUX.eval_packed(false, true, true));
+    }
+
+    #[test]
+    fn compose() {
+        let f = TruthTable8::from_bits(0x6b);
+        assert_eq!(f.compose(TruthTable8::A, TruthTable8::B, TruthTable8::C), f);
+        assert_eq!(TruthTable8::AND.compose(TruthTable8::A, TruthTable8::TRUE, TruthTable8::C), TruthTable8::A & TruthTable8::C);
+        let swapped = f.permute([2, 1, 0]);
+        for i in 0..8u8 {
+            let (a, b, c) = (i & 4 != 0, i & 2 != 0, i & 1 != 0);
+            assert_eq!(swapped.eval(a, b, c), f.eval(c, b, a));
+        }
+    }
+
+    #[test]
+    fn dual_and_support() {
+        assert_eq!(TruthTable8::AND.dual(), TruthTable8::OR);
+        assert_eq!(TruthTable8::MAJORITY.dual(), TruthTable8::MAJORITY);
+        assert!(TruthTable8::MUX.depends_on(0));
+        assert!(!(TruthTable8::A ^ TruthTable8::C).depends_on(1));
+        assert!((TruthTable8::A ^ TruthTable8::C).depends_on(2));
+    }
+
+    #[test]
+    fn canonical_forms() {
+        assert_eq!(TruthTable8::MAJORITY.minterms().collect::<Vec<_>>(), [3, 5, 6, 7]);
+        assert_eq!(TruthTable8::MAJORITY.maxterms().collect::<Vec<_>>(), [0, 1, 2, 4]);
+        assert_eq!(format!("{}", TruthTable8::AND), "a&b&c");
+        assert_eq!(format!("{}", TruthTable8::MUX), "!a&!b&c | !a&b&c | a&b&!c | a&b&c");
+        assert_eq!(format!("{}", TruthTable8::FALSE), "0");
+    }
+}
@@ -0,0 +1,277 @@
+//! Sets of flags keyed by a user enum.
+
+use core::{fmt, iter::FusedIterator, marker::PhantomData, ops::Range};
+
+pub(crate) mod private {
+    pub trait Sealed {}
+}
+
+/// A packed type that can store the flags of a [`BitIndexable`] type.
+///
+/// This is implemented by all the packed types, and can't be implemented outside this crate.
+pub trait FlagStorage: Copy + Eq + Default + core::hash::Hash + fmt::Debug + private::Sealed {
+    /// How many booleans the type stores.
+    const CAPACITY: u8;
+
+    /// Gets the boolean at the given index.
+    /// The index must be less than `CAPACITY`.
+    fn flag(&self, idx: u8) -> bool;
+
+    /// Sets the boolean at the given index to val.
+    /// The index must be less than `CAPACITY`.
+    fn set_flag(&mut self, idx: u8, val: bool);
+}
+
+/// A type whose values each name a different index of a packed type.
+///
+/// This is usually derived for fieldless enums with `#[derive(BitIndex)]`,
+/// which numbers the variants in the order they are declared
+/// and picks the smallest packed type that fits them all.
+pub trait BitIndexable: Copy {
+    /// The packed type the flags are stored in.
+    type Storage: FlagStorage;
+
+    /// Returns the index of this value.
+    /// This must be less than `Storage::CAPACITY`, and different for every value.
+    fn bit_index(self) -> u8;
+
+    /// Returns the value with the given index, if there is one.
+    fn from_bit_index(idx: u8) -> Option<Self>;
+}
+
+/// A set of flags, where each flag is a value of the type `E`.
+///
+/// ```
+/// use packed_booleans::{BitIndexable, PackedBools8, PackedFlags};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Motor { On, Reversed }
+///
+/// impl BitIndexable for Motor {
+///     type Storage = PackedBools8;
+///
+///     fn bit_index(self) -> u8 { self as u8 }
+///
+///     fn from_bit_index(idx: u8) -> Option<Self> {
+///         [Motor::On, Motor::Reversed].get(usize::from(idx)).copied()
+///     }
+/// }
+///
+/// let mut flags = PackedFlags::new();
+/// flags.set(Motor::On, true);
+/// assert!(flags.get(Motor::On));
+/// assert!(!flags.get(Motor::Reversed));
+/// assert_eq!(flags.to_packed(), PackedBools8::from_bits(0b01));
+/// ```
+pub struct PackedFlags<E: BitIndexable> {
+    bits: E::Storage,
+    marker: PhantomData<E>,
+}
+
+impl<E: BitIndexable> PackedFlags<E> {
+    /// Creates a new set with no flags set.
+    pub fn new() -> Self {
+        Self::from_packed(E::Storage::default())
+    }
+
+    /// Creates a set from its packed form.
+    pub fn from_packed(bits: E::Storage) -> Self {
+        Self { bits, marker: PhantomData }
+    }
+
+    /// Returns the packed form of the set.
+    pub fn to_packed(self) -> E::Storage {
+        self.bits
+    }
+
+    /// Returns true if the given flag is set.
+    pub fn get(&self, flag: E) -> bool {
+        self.bits.flag(flag.bit_index())
+    }
+
+    /// Sets or clears the given flag.
+    pub fn set(&mut self, flag: E, val: bool) {
+        self.bits.set_flag(flag.bit_index(), val);
+    }
+
+    /// Sets the given flag.
+    pub fn insert(&mut self, flag: E) {
+        self.set(flag, true);
+    }
+
+    /// Clears the given flag.
+    pub fn remove(&mut self, flag: E) {
+        self.set(flag, false);
+    }
+
+    /// Toggles the given flag.
+    pub fn toggle(&mut self, flag: E) {
+        let val = self.get(flag);
+        self.set(flag, !val);
+    }
+
+    /// Returns true if no flags are set.
+    pub fn is_empty(&self) -> bool {
+        self.bits == E::Storage::default()
+    }
+
+    /// Returns an iterator over the flags that are set, in index order.
+    pub fn iter(&self) -> FlagsIter<E> {
+        FlagsIter { bits: self.bits, range: 0..E::Storage::CAPACITY, marker: PhantomData }
+    }
+}
+
+impl<E: BitIndexable> Clone for PackedFlags<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: BitIndexable> Copy for PackedFlags<E> {}
+
+impl<E: BitIndexable> PartialEq for PackedFlags<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<E: BitIndexable> Eq for PackedFlags<E> {}
+
+impl<E: BitIndexable> core::hash::Hash for PackedFlags<E> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<E: BitIndexable> Default for PackedFlags<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: BitIndexable + fmt::Debug> fmt::Debug for PackedFlags<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<E: BitIndexable> FromIterator<E> for PackedFlags<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut flags = Self::new();
+        flags.extend(iter);
+        flags
+    }
+}
+
+impl<E: BitIndexable> Extend<E> for PackedFlags<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|flag| self.insert(flag));
+    }
+}
+
+impl<E: BitIndexable> IntoIterator for PackedFlags<E> {
+    type Item = E;
+    type IntoIter = FlagsIter<E>;
+
+    fn into_iter(self) -> FlagsIter<E> {
+        self.iter()
+    }
+}
+
+/// An iterator over the flags that are set in a [`PackedFlags`].
+pub struct FlagsIter<E: BitIndexable> {
+    bits: E::Storage,
+    range: Range<u8>,
+    marker: PhantomData<E>,
+}
+
+impl<E: BitIndexable> Clone for FlagsIter<E> {
+    fn clone(&self) -> Self {
+        Self { bits: self.bits, range: self.range.clone(), marker: PhantomData }
+    }
+}
+
+impl<E: BitIndexable> fmt::Debug for FlagsIter<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlagsIter")
+            .field("bits", &self.bits)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+impl<E: BitIndexable> Iterator for FlagsIter<E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        let bits = self.bits;
+        // skip the set bits that have no value, instead of stopping at them
+        self.range.find_map(|idx| if bits.flag(idx) { E::from_bit_index(idx) } else { None })
+    }
+}
+
+impl<E: BitIndexable> FusedIterator for FlagsIter<E> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::{format, vec::Vec};
+
+    use super::{BitIndexable, PackedFlags};
+    use crate::PackedBools16;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Light { Red, Amber, Green, Blink }
+
+    impl BitIndexable for Light {
+        type Storage = PackedBools16;
+
+        fn bit_index(self) -> u8 {
+            // spread out, to check nothing assumes the indices are contiguous
+            self as u8 * 4
+        }
+
+        fn from_bit_index(idx: u8) -> Option<Self> {
+            match idx {
+                0 => Some(Light::Red),
+                4 => Some(Light::Amber),
+                8 => Some(Light::Green),
+                12 => Some(Light::Blink),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn set_get() {
+        let mut flags = PackedFlags::new();
+        assert!(flags.is_empty());
+        flags.set(Light::Green, true);
+        flags.insert(Light::Blink);
+        flags.toggle(Light::Red);
+        flags.remove(Light::Blink);
+        assert!(flags.get(Light::Green));
+        assert!(!flags.get(Light::Amber));
+        assert_eq!(flags.to_packed(), 0x0101);
+    }
+
+    #[test]
+    fn iter_and_debug() {
+        let flags: PackedFlags<Light> = [Light::Blink, Light::Amber].into_iter().collect();
+        assert_eq!(flags.iter().collect::<Vec<_>>(), [Light::Amber, Light::Blink]);
+        assert_eq!(format!("{flags:?}"), "{Amber, Blink}");
+        assert_eq!(PackedFlags::<Light>::from_packed(PackedBools16::from_bits(0x1000)).iter().next(), Some(Light::Blink));
+    }
+
+    #[test]
+    fn iter_skips_stray_bits() {
+        // bits 2 and 6 have no value, and come before the mapped bits 4 and 8
+        let flags = PackedFlags::<Light>::from_packed(PackedBools16::from_bits(0x0154));
+        let mut iter = flags.iter();
+        assert_eq!(iter.next(), Some(Light::Amber));
+        assert_eq!(iter.next(), Some(Light::Green));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(format!("{flags:?}"), "{Amber, Green}");
+    }
+}
@@ -0,0 +1,62 @@
+//! Access to packed values in memory-mapped registers.
+
+use core::marker::PhantomData;
+
+/// A handle to a memory-mapped register, read and written as a packed value.
+///
+/// Every read and write of the register is a volatile access of the whole register,
+/// so none of them are removed or merged by the compiler.
+/// Changing a single value reads the register and writes it back.
+///
+/// ```
+/// use packed_booleans::{PackedBools8, RegisterBits};
+///
+/// let mut reg = 0b0001_u8;
+/// // SAFETY: the pointer comes from a live local variable
+/// let status = unsafe { RegisterBits::<PackedBools8>::new(&mut reg) };
+/// status.set(true, 3);
+/// assert_eq!(status.read(), 0b1001);
+/// ```
+#[derive(Debug)]
+pub struct RegisterBits<'a, T> {
+    pub(crate) ptr: *mut T,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T> RegisterBits<'_, T> {
+    pub(crate) const fn from_ptr(ptr: *mut T) -> Self {
+        Self { ptr, marker: PhantomData }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegisterBits;
+    use crate::{PackedBools8, PackedBools16};
+
+    #[test]
+    fn raw_access() {
+        let mut reg = 0xa5_u8;
+        // SAFETY: the pointers come from a live local variable
+        unsafe {
+            assert_eq!(PackedBools8::read_volatile(&reg), 0xa5);
+            PackedBools8::write_volatile(&mut reg, PackedBools8::from_bits(0x3c));
+        }
+        assert_eq!(reg, 0x3c);
+    }
+
+    #[test]
+    fn register() {
+        let mut reg = 0x8000_u16;
+        // SAFETY: the pointer comes from a live local variable
+        let bits = unsafe { RegisterBits::<PackedBools16>::new(&mut reg) };
+        assert!(bits.get(15));
+        bits.set(true, 0);
+        bits.toggle(15);
+        assert_eq!(bits.read(), 0x0001);
+        bits.write(PackedBools16::from_bits(0xffff));
+        assert_eq!(reg, 0xffff);
+        bits.modify(|val| val & 0x00f0);
+        assert_eq!(reg, 0x00f0);
+    }
+}
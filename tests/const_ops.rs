@@ -0,0 +1,28 @@
+//! Run with `RUSTFLAGS="--cfg packed_booleans_nightly" cargo +nightly test`.
+#![cfg(packed_booleans_nightly)]
+#![feature(const_trait_impl, const_ops)]
+
+use packed_booleans::{PackedBools8, PackedBools64};
+
+const A: PackedBools8 = PackedBools8::from_bits(0b0011);
+const B: PackedBools8 = PackedBools8::from_bits(0b0101);
+const MASK: PackedBools8 = A | B;
+const BOTH: PackedBools8 = A & B;
+const EITHER: PackedBools8 = A ^ B;
+const NOT_A: PackedBools8 = !A;
+const WITH_INT: PackedBools64 = PackedBools64::new() | 0xff00;
+const ASSIGNED: PackedBools8 = {
+    let mut pkd = A;
+    pkd ^= &B;
+    pkd
+};
+
+#[test]
+fn const_operators() {
+    assert_eq!(MASK.to_bits(), 0b0111);
+    assert_eq!(BOTH.to_bits(), 0b0001);
+    assert_eq!(EITHER.to_bits(), 0b0110);
+    assert_eq!(NOT_A.to_bits(), 0b1111_1100);
+    assert_eq!(WITH_INT.to_bits(), 0xff00);
+    assert_eq!(ASSIGNED, EITHER);
+}
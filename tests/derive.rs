@@ -0,0 +1,129 @@
+#![cfg(feature = "derive")]
+
+use packed_booleans::{bitfield, BitIndex, BitIndexable, PackBools, PackedBools8, PackedBools16, PackedBools128, PackedFlags};
+
+#[derive(PackBools, Debug, Clone, Copy, PartialEq, Eq)]
+struct Config {
+    verbose: bool,
+    color: bool,
+    dry_run: bool,
+}
+
+#[derive(PackBools, Debug, PartialEq, Eq)]
+struct Channels {
+    c0: bool, c1: bool, c2: bool, c3: bool,
+    c4: bool, c5: bool, c6: bool, c7: bool,
+    c8: bool, c9: bool,
+}
+
+#[derive(PackBools, Debug, Clone, Copy, PartialEq, Eq)]
+struct Keywords {
+    r#type: bool,
+    r#loop: bool,
+}
+
+#[test]
+fn indices() {
+    assert_eq!(Config::VERBOSE_INDEX, 0);
+    assert_eq!(Config::COLOR_INDEX, 1);
+    assert_eq!(Config::DRY_RUN_INDEX, 2);
+    assert_eq!(Channels::C9_INDEX, 9);
+    assert_eq!(Keywords::TYPE_INDEX, 0);
+    assert_eq!(Keywords::LOOP_INDEX, 1);
+}
+
+#[test]
+fn round_trip8() {
+    let config = Config { verbose: true, color: false, dry_run: true };
+    let packed: PackedBools8 = config.into_packed();
+    assert_eq!(packed, PackedBools8::from_bits(0b101));
+    assert!(packed.get(Config::DRY_RUN_INDEX));
+    assert_eq!(Config::from_packed(packed), config);
+    assert_eq!(Config::from(PackedBools8::from(config)), config);
+}
+
+#[test]
+fn round_trip16() {
+    let mut packed = PackedBools16::new();
+    packed.set(true, Channels::C8_INDEX);
+    let channels = Channels::from(packed);
+    assert!(channels.c8);
+    assert!(!channels.c0);
+    assert_eq!(channels.into_packed(), packed);
+}
+
+#[test]
+fn raw_idents() {
+    let keywords = Keywords { r#type: false, r#loop: true };
+    assert_eq!(keywords.into_packed(), PackedBools8::from_bits(0b10));
+    assert_eq!(Keywords::from_packed(keywords.into_packed()), keywords);
+}
+
+#[derive(BitIndex, Debug, Clone, Copy, PartialEq, Eq)]
+enum Motor {
+    On,
+    Reversed = 7,
+    Fault,
+}
+
+#[test]
+fn bit_index() {
+    assert_eq!(Motor::Fault.bit_index(), 2);
+    assert_eq!(Motor::from_bit_index(1), Some(Motor::Reversed));
+    assert_eq!(Motor::from_bit_index(3), None);
+
+    let mut flags = PackedFlags::new();
+    flags.set(Motor::On, true);
+    flags.set(Motor::Fault, true);
+    assert!(!flags.get(Motor::Reversed));
+    let packed: PackedBools8 = flags.to_packed();
+    assert_eq!(packed, PackedBools8::from_bits(0b101));
+}
+
+bitfield! {
+    /// The control register of a timer.
+    pub struct Ctrl(PackedBools16) {
+        /// Whether the timer is running.
+        enable: 0,
+        mode: 1..=3,
+        prescaler: 4..8,
+        interrupt: 15,
+    }
+}
+
+bitfield! {
+    struct Wide(packed_booleans::PackedBools128) {
+        low: 0..64,
+        high: 64..=127,
+    }
+}
+
+#[test]
+fn bitfield_accessors() {
+    const CTRL: Ctrl = Ctrl::new().with_enable(true).with_mode(5).with_prescaler(0xa);
+    assert_eq!(CTRL.to_packed(), PackedBools16::from_bits(0b1010_1011));
+    assert!(CTRL.enable());
+    assert_eq!(CTRL.mode(), 5u8);
+    assert_eq!(CTRL.prescaler(), 0xa);
+    assert!(!CTRL.interrupt());
+
+    let mut ctrl = CTRL;
+    ctrl.set_mode(0xff);
+    assert_eq!(ctrl.mode(), 0b111);
+    assert_eq!(ctrl.prescaler(), 0xa);
+    ctrl.set_interrupt(true);
+    ctrl.set_enable(false);
+    assert_eq!(ctrl.to_packed().to_bits(), 0x80ae);
+
+    // the bits outside the fields are kept
+    let ctrl = Ctrl::from_packed(PackedBools16::from_bits(0x7f00)).with_mode(1);
+    assert_eq!(ctrl.to_packed().to_bits(), 0x7f02);
+    assert_eq!(
+        format!("{ctrl:?}"),
+        "Ctrl { enable: false, mode: 1, prescaler: 0, interrupt: false }"
+    );
+
+    let wide = Wide::new().with_high(u64::MAX).with_low(3);
+    assert_eq!(wide.to_packed(), PackedBools128::from_bits(u128::from(u64::MAX) << 64 | 3));
+    assert_eq!((wide.low(), wide.high()), (3, u64::MAX));
+}